@@ -1,20 +1,36 @@
 use std::{
+    f32::consts::TAU,
     time::{Instant, Duration},
     ops::RangeInclusive
 };
 
+use crate::Point2;
+
 
 pub trait Animatable<T>
 {
     fn set(&mut self, id: &T, value: f32);
 }
 
+// every curve goes from 0 to 1, reversed ones r the same curve played backwards
 #[derive(Debug, Clone)]
 pub enum ValueAnimation
 {
 	Linear,
 	EaseIn(f32),
-	EaseOut(f32)
+	EaseOut(f32),
+	// slow at both ends, its its own reverse
+	EaseInOut(f32),
+	// the 2 middle control points like in css, the ends r at 0,0 and 1,1
+	CubicBezier(Point2<f32>, Point2<f32>),
+	BounceIn,
+	BounceOut,
+	ElasticIn,
+	ElasticOut,
+	// jumps at the end of each step, so it only gets to 1 at the very end
+	StepsEnd(u32),
+	// jumps at the start of each step
+	StepsStart(u32)
 }
 
 impl ValueAnimation
@@ -27,17 +43,122 @@ impl ValueAnimation
 		{
 			Self::Linear => value,
 			Self::EaseIn(strength) => value.powf(*strength),
-			Self::EaseOut(strength) => 1.0 - (1.0 - value).powf(*strength)
+			Self::EaseOut(strength) => 1.0 - (1.0 - value).powf(*strength),
+			Self::EaseInOut(strength) => if value < 0.5
+			{
+				(value * 2.0).powf(*strength) / 2.0
+			} else
+			{
+				1.0 - ((1.0 - value) * 2.0).powf(*strength) / 2.0
+			},
+			Self::CubicBezier(a, b) => Self::cubic_bezier(*a, *b, value),
+			Self::BounceIn => 1.0 - Self::bounce(1.0 - value),
+			Self::BounceOut => Self::bounce(value),
+			Self::ElasticIn => 1.0 - Self::elastic(1.0 - value),
+			Self::ElasticOut => Self::elastic(value),
+			Self::StepsEnd(steps) =>
+			{
+				let steps = (*steps).max(1) as f32;
+
+				(value * steps).floor() / steps
+			},
+			Self::StepsStart(steps) =>
+			{
+				let steps = (*steps).max(1) as f32;
+
+				(value * steps).ceil() / steps
+			}
 		}
 	}
 
+    // the y where the curve is at x, the x only ever goes up so it can be searched for
+    fn cubic_bezier(a: Point2<f32>, b: Point2<f32>, x: f32) -> f32
+    {
+        let point = |t: f32, a: f32, b: f32|
+        {
+            let rest = 1.0 - t;
+
+            3.0 * rest * rest * t * a + 3.0 * rest * t * t * b + t * t * t
+        };
+
+        let a_x = a.x.clamp(0.0, 1.0);
+        let b_x = b.x.clamp(0.0, 1.0);
+
+        // way more than enough for anything on a screen
+        let (low, high) = (0..24).fold((0.0, 1.0), |(low, high): (f32, f32), _|
+        {
+            let middle = (low + high) / 2.0;
+
+            if point(middle, a_x, b_x) < x
+            {
+                (middle, high)
+            } else
+            {
+                (low, middle)
+            }
+        });
+
+        point((low + high) / 2.0, a.y, b.y)
+    }
+
+    // bounces 3 times before settling at 1
+    fn bounce(value: f32) -> f32
+    {
+        let strength = 7.5625;
+        let width = 2.75;
+
+        if value < 1.0 / width
+        {
+            strength * value * value
+        } else if value < 2.0 / width
+        {
+            let value = value - 1.5 / width;
+
+            strength * value * value + 0.75
+        } else if value < 2.5 / width
+        {
+            let value = value - 2.25 / width;
+
+            strength * value * value + 0.9375
+        } else
+        {
+            let value = value - 2.625 / width;
+
+            strength * value * value + 0.984375
+        }
+    }
+
+    // overshoots and wobbles around 1 until it settles
+    fn elastic(value: f32) -> f32
+    {
+        if value <= 0.0 || value >= 1.0
+        {
+            return value;
+        }
+
+        2.0_f32.powf(-10.0 * value) * ((value * 10.0 - 0.75) * TAU / 3.0).sin() + 1.0
+    }
+
     pub fn reversed(&self) -> Self
     {
         match self
         {
             Self::Linear => Self::Linear,
             Self::EaseIn(x) => Self::EaseOut(*x),
-            Self::EaseOut(x) => Self::EaseIn(*x)
+            Self::EaseOut(x) => Self::EaseIn(*x),
+            Self::EaseInOut(x) => Self::EaseInOut(*x),
+            Self::CubicBezier(a, b) =>
+            {
+                let flipped = |point: Point2<f32>| Point2::new(1.0 - point.x, 1.0 - point.y);
+
+                Self::CubicBezier(flipped(*b), flipped(*a))
+            },
+            Self::BounceIn => Self::BounceOut,
+            Self::BounceOut => Self::BounceIn,
+            Self::ElasticIn => Self::ElasticOut,
+            Self::ElasticOut => Self::ElasticIn,
+            Self::StepsEnd(x) => Self::StepsStart(*x),
+            Self::StepsStart(x) => Self::StepsEnd(*x)
         }
     }
 }
@@ -160,3 +281,58 @@ impl<T> Animator<T>
         range.start() * (1.0 - a) + range.end() * a
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn curves() -> Vec<ValueAnimation>
+    {
+        vec![
+            ValueAnimation::Linear,
+            ValueAnimation::EaseIn(2.0),
+            ValueAnimation::EaseOut(3.0),
+            ValueAnimation::EaseInOut(2.5),
+            ValueAnimation::CubicBezier(Point2::new(0.1, 0.7), Point2::new(0.4, 1.3)),
+            ValueAnimation::BounceIn,
+            ValueAnimation::BounceOut,
+            ValueAnimation::ElasticIn,
+            ValueAnimation::ElasticOut,
+            ValueAnimation::StepsEnd(4),
+            ValueAnimation::StepsStart(4)
+        ]
+    }
+
+    fn close(a: f32, b: f32) -> bool
+    {
+        (a - b).abs() < 0.0001
+    }
+
+    #[test]
+    fn ends_at_0_and_1()
+    {
+        curves().into_iter().for_each(|curve|
+        {
+            assert!(close(curve.apply(0.0), 0.0), "{curve:?} at 0");
+            assert!(close(curve.apply(1.0), 1.0), "{curve:?} at 1");
+        });
+    }
+
+    #[test]
+    fn reversed_mirrors()
+    {
+        curves().into_iter().for_each(|curve|
+        {
+            let reversed = curve.reversed();
+
+            // 37 doesnt divide into the steps so nothing lands right on a jump
+            (0..=37).map(|x| x as f32 / 37.0).for_each(|x|
+            {
+                let expected = 1.0 - curve.apply(1.0 - x);
+
+                assert!(close(reversed.apply(x), expected), "{curve:?} at {x}");
+            });
+        });
+    }
+}
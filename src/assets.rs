@@ -0,0 +1,1680 @@
+use std::{
+    fs,
+    io,
+    mem,
+    iter,
+    rc::Rc,
+    cell::RefCell,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::{Instant, Duration}
+};
+
+use serde::Deserialize;
+
+use sdl2::rect::Rect;
+
+use crate::{
+    Point2,
+    Image,
+    Tile,
+    font,
+    loader::{ImageLoader, LoadedImage},
+    theme::Theme,
+    renderer::{Renderer, RenderTexture, Filter},
+    spritesheet::Spritesheet,
+    tileset::{self, Tileset, TileFit},
+    format::TileSource,
+    registry::{TileRegistry, RegistryError}
+};
+
+
+// in pixels, most gpus handle way bigger textures but this fits plenty of tiles
+const ATLAS_WIDTH: usize = 2048;
+const ATLAS_MAX_HEIGHT: usize = 8192;
+
+// longest side in pixels of the shrunk copies of tiles that the tile picker draws,
+// the smallest one thats at least as big as the button gets used
+const PREVIEW_SIZES: [usize; 3] = [16, 32, 64];
+
+// browsers show gif frames without a delay like this so the files expect it
+const DEFAULT_FRAME_DURATION: Duration = Duration::from_millis(100);
+
+// how many times the atlas gets halved for zoomed out views when thats turned on
+const MIP_LEVELS: usize = 3;
+
+// how many pictures the loading spinner goes through and how long each one stays
+const SPINNER_FRAMES: usize = 8;
+const SPINNER_FRAME_MS: u128 = 100;
+
+// a tileset in the tiles directory with this name gets used instead of scanning it
+const TILES_MANIFEST: &str = "manifest.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureId(usize);
+
+// the corners keep their size and the edges only stretch along themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct NineSlice
+{
+    // in pixels of the texture
+    pub corner: u32,
+    // how many pixels on screen 1 pixel of the texture takes up
+    #[serde(default = "NineSlice::default_scale")]
+    pub scale: u32
+}
+
+impl NineSlice
+{
+    fn default_scale() -> u32
+    {
+        1
+    }
+}
+
+// a part of a texture, lets spritesheet tiles share a single texture
+struct TextureView
+{
+    texture: usize,
+    source: Option<Rect>
+}
+
+struct TileFrame
+{
+    texture: TextureId,
+    duration: Duration
+}
+
+struct TileInfo
+{
+    frames: Vec<TileFrame>,
+    variants: Vec<TextureId>,
+    category: Option<String>,
+    display_name: Option<String>,
+    properties: toml::Table
+}
+
+impl TileInfo
+{
+    fn single(texture: TextureId) -> Self
+    {
+        Self::animated(vec![TileFrame{texture, duration: Duration::ZERO}])
+    }
+
+    fn animated(frames: Vec<TileFrame>) -> Self
+    {
+        Self{
+            frames,
+            variants: Vec::new(),
+            category: None,
+            display_name: None,
+            properties: toml::Table::new()
+        }
+    }
+
+    fn frame_at(&self, time: Duration) -> TextureId
+    {
+        let total: u128 = self.frames.iter().map(|frame| frame.duration.as_millis()).sum();
+
+        if self.frames.len() == 1 || total == 0
+        {
+            return self.frames[0].texture;
+        }
+
+        let mut time = time.as_millis() % total;
+
+        self.frames.iter().find(|frame|
+        {
+            let duration = frame.duration.as_millis();
+
+            if time < duration
+            {
+                true
+            } else
+            {
+                time -= duration;
+
+                false
+            }
+        }).unwrap_or(&self.frames[0]).texture
+    }
+}
+
+// splitmix64 finalizer, anything that scrambles neighboring positions well enough works
+fn variant_hash(pos: Point2<i32>, seed: u32) -> u64
+{
+    let mut x = (pos.x as u32 as u64) | ((pos.y as u32 as u64) << 32);
+    x ^= (seed as u64).wrapping_mul(0x9e3779b97f4a7c15);
+
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    x ^ (x >> 31)
+}
+
+// everything needed to draw a tile at 1 size, looked up once instead of for every position
+pub struct ResolvedTile
+{
+    pub animated: bool,
+    // the current frame and then the variants
+    textures: Vec<(RenderTexture, Option<Rect>)>
+}
+
+impl ResolvedTile
+{
+    // same pick as tile_variant_texture_id
+    pub fn pick(&self, pos: Point2<i32>, seed: u32) -> (RenderTexture, Option<Rect>)
+    {
+        if self.textures.len() == 1
+        {
+            return self.textures[0];
+        }
+
+        self.textures[(variant_hash(pos, seed) % self.textures.len() as u64) as usize]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextureMemory
+{
+    pub textures: usize,
+    pub bytes: usize,
+    // loaded but nothing uses them, unload_unused gets rid of these
+    pub unused: usize,
+    pub unused_bytes: usize
+}
+
+pub struct Assets
+{
+    renderer: Rc<RefCell<dyn Renderer>>,
+    texture_ids: HashMap<PathBuf, usize>,
+    tiles: TileRegistry<TileInfo>,
+    start: Instant,
+    // none once its been unloaded
+    textures: Vec<Option<RenderTexture>>,
+    texture_paths: Vec<PathBuf>,
+    // how many tiles and other things use each texture, unused ones can get unloaded
+    texture_refs: Vec<usize>,
+    // known before the image is done loading, it only has to read the header for it
+    texture_sizes: Vec<Point2<u32>>,
+    views: Vec<TextureView>,
+    // where the tile views ended up in the atlas, views that arent in it draw by themselves
+    atlas: HashMap<usize, Rect>,
+    atlas_texture: Option<usize>,
+    // smaller and smaller copies of the atlas, the first is half as big
+    atlas_mips: Vec<usize>,
+    mip_levels: usize,
+    // 1 texture per preview size with the previews of every tile in a grid
+    preview_textures: Vec<usize>,
+    // smallest first, built together with the atlas from the same images
+    previews: HashMap<usize, Vec<(usize, Rect)>>,
+    tile_filter: Filter,
+    // spritesheets without a tile size of their own get sliced into these
+    tile_size: Option<Point2<usize>>,
+    tile_fit: TileFit,
+    // tile textures that got padded or scaled to the tile size and how big their files r
+    fitted: HashMap<usize, Point2<u32>>,
+    slices: HashMap<usize, NineSlice>,
+    ui_directory: PathBuf,
+    theme: Theme,
+    font: usize,
+    // drawn instead of textures that got unloaded while something still had their id
+    placeholder: usize,
+    // tile images get decoded on other threads if theres a loader
+    loader: Option<ImageLoader>,
+    loading: HashSet<usize>,
+    // the images that finished loading, kept so the atlas doesnt have to decode them again
+    decoded: HashMap<usize, Image>,
+    spinner: Vec<usize>
+}
+
+impl Assets
+{
+    pub fn new(renderer: Rc<RefCell<dyn Renderer>>) -> Self
+    {
+        let mut this = Self{
+            renderer,
+            texture_ids: HashMap::new(),
+            tiles: TileRegistry::new(),
+            start: Instant::now(),
+            textures: Vec::new(),
+            texture_paths: Vec::new(),
+            texture_refs: Vec::new(),
+            texture_sizes: Vec::new(),
+            views: Vec::new(),
+            atlas: HashMap::new(),
+            atlas_texture: None,
+            atlas_mips: Vec::new(),
+            mip_levels: 0,
+            preview_textures: Vec::new(),
+            previews: HashMap::new(),
+            tile_filter: Filter::Nearest,
+            tile_size: None,
+            tile_fit: TileFit::default(),
+            fitted: HashMap::new(),
+            slices: HashMap::new(),
+            ui_directory: PathBuf::new(),
+            theme: Theme::default(),
+            font: 0,
+            placeholder: 0,
+            loader: None,
+            loading: HashSet::new(),
+            decoded: HashMap::new(),
+            spinner: Vec::new()
+        };
+
+        // built in so text works without any files around
+        this.font = this.push_texture(Path::new("<font>"), &font::atlas());
+        let placeholder = Image::placeholder(Point2::repeat(16));
+        this.placeholder = this.push_texture(Path::new("<placeholder>"), &placeholder);
+
+        this.spinner = (0..SPINNER_FRAMES).map(|frame|
+        {
+            this.push_texture(Path::new("<spinner>"), &Image::spinner(16, frame, SPINNER_FRAMES))
+        }).collect();
+
+        // these never go away
+        this.texture_refs[this.font] += 1;
+        this.texture_refs[this.placeholder] += 1;
+        this.spinner.clone().into_iter().for_each(|index| this.texture_refs[index] += 1);
+
+        this
+    }
+
+    // tiles in subdirectories get the subdirectories as their category, like nature/trees,
+    // unless theres a manifest.toml tileset in the directory which says what the tiles r instead
+    pub fn load_tiles(&mut self, directory: impl AsRef<Path>)
+    {
+        let directory = directory.as_ref();
+
+        let manifest = directory.join(TILES_MANIFEST);
+        if manifest.exists()
+        {
+            if let Err(err) = self.load_tileset(&manifest)
+            {
+                eprintln!("cant load {}: {err}", manifest.display());
+            }
+
+            return;
+        }
+
+        self.load_tiles_in(directory, directory);
+    }
+
+    fn load_tiles_in(&mut self, root: &Path, directory: &Path)
+    {
+        let entries = match fs::read_dir(directory)
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant read {}: {err}", directory.display());
+
+                return;
+            }
+        };
+
+        // sorted so new tiles get the same ids no matter what order the os lists them in
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+
+        paths.sort();
+
+        let (directories, files): (Vec<PathBuf>, Vec<PathBuf>) = paths.into_iter()
+            .partition(|path| path.is_dir());
+
+        let before: HashSet<Tile> = self.tiles().collect();
+
+        self.load_tile_files(root, files);
+
+        if let Some(category) = Self::category_from_path(root, directory)
+        {
+            let added: Vec<Tile> = self.tiles().filter(|tile| !before.contains(tile)).collect();
+            added.into_iter().for_each(|tile| self.set_tile_category(tile, category.clone()));
+        }
+
+        directories.iter().for_each(|directory| self.load_tiles_in(root, directory));
+    }
+
+    // the folders between the tiles directory and the tile
+    fn category_from_path(root: &Path, directory: &Path) -> Option<String>
+    {
+        let relative = directory.strip_prefix(root).ok()?;
+
+        let parts: Vec<String> = relative.components()
+            .map(|part| part.as_os_str().to_string_lossy().into_owned())
+            .collect();
+
+        (!parts.is_empty()).then(|| parts.join("/"))
+    }
+
+    fn set_tile_category(&mut self, tile: Tile, category: String)
+    {
+        if let Some(entry) = self.tiles.get_mut(tile)
+        {
+            entry.value.category = Some(category);
+        }
+    }
+
+    // anything the image crate knows the extension of
+    pub fn is_image(path: &Path) -> bool
+    {
+        image::ImageFormat::from_path(path).is_ok()
+    }
+
+    fn load_tile_files(&mut self, root: &Path, tile_paths: Vec<PathBuf>)
+    {
+        let is_manifest = |path: &PathBuf| path.extension().map(|x| x == "toml").unwrap_or(false);
+
+        let spritesheets: Vec<Spritesheet> = tile_paths.iter().filter(|path| is_manifest(path))
+            .filter_map(|path|
+            {
+                Spritesheet::load(path).map_err(|err|
+                {
+                    eprintln!("cant load spritesheet {}: {err}", path.display());
+                }).ok()
+            })
+            .collect();
+
+        // images that r a part of a spritesheet shouldnt also be loaded as tiles
+        let sheet_images: HashSet<&PathBuf> = spritesheets.iter().map(|sheet| &sheet.image)
+            .collect();
+
+        tile_paths.iter()
+            .filter(|path| !is_manifest(path) && !sheet_images.contains(path))
+            .filter(|path| Self::is_image(path))
+            .for_each(|path|
+            {
+                if let Err(err) = self.add_tile(root, path)
+                {
+                    eprintln!("cant add tile {}: {err}", path.display());
+                }
+            });
+
+        spritesheets.iter().for_each(|sheet| self.add_spritesheet(root, sheet));
+    }
+
+    pub fn load_tileset(&mut self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        let tileset = Tileset::load(path)?;
+
+        tileset.tiles.iter().try_for_each(|definition|
+        {
+            let definition_frames = definition.frames();
+
+            // a gif or an animated png brings its own frames
+            let animated = match definition_frames.as_slice()
+            {
+                [frame] => self.load_animated_frames(&frame.image),
+                _ => None
+            };
+
+            let frames = animated.unwrap_or_else(||
+            {
+                definition_frames.into_iter().map(|frame|
+                {
+                    TileFrame{texture: self.load_texture(&frame.image), duration: frame.duration()}
+                }).collect()
+            });
+
+            let variants = definition.variants.iter().map(|path| self.load_texture(path)).collect();
+
+            let mut properties = tileset.default_properties.clone();
+            properties.extend(definition.properties.clone());
+
+            let info = TileInfo{
+                frames,
+                variants,
+                category: definition.category.clone(),
+                display_name: definition.display_name.clone(),
+                properties
+            };
+
+            let tile = definition.id.map(Tile::from_id);
+
+            self.insert_tile(tile, &definition.name, info).map(|_| ())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+        })
+    }
+
+    // slices.toml in the directory says which textures get drawn as nine slices, like
+    // "panel.png" = { corner = 1, scale = 3 }
+    // and theme.toml can swap out the textures, colors and font
+    pub fn load_textures(&mut self, directory: impl AsRef<Path>)
+    {
+        let directory = directory.as_ref();
+        self.ui_directory = directory.to_owned();
+
+        match fs::read_dir(directory)
+        {
+            Ok(entries) =>
+            {
+                entries.filter_map(|entry| entry.ok()).for_each(|entry|
+                {
+                    let path = entry.path();
+
+                    if path.extension().map(|x| x == "png").unwrap_or(false)
+                    {
+                        self.add_texture(path);
+                    }
+                });
+            },
+            Err(err) => eprintln!("cant read {}: {err}", directory.display())
+        }
+
+        let slices_path = directory.join("slices.toml");
+        if slices_path.exists()
+        {
+            if let Err(err) = self.load_slices(directory, &slices_path)
+            {
+                eprintln!("cant load {}: {err}", slices_path.display());
+            }
+        }
+
+        let theme_path = directory.join("theme.toml");
+        if theme_path.exists()
+        {
+            match Theme::load(&theme_path)
+            {
+                Ok(theme) => self.set_theme(theme),
+                Err(err) => eprintln!("cant load {}: {err}", theme_path.display())
+            }
+        }
+    }
+
+    fn set_theme(&mut self, mut theme: Theme)
+    {
+        theme.textures.retain(|name, file|
+        {
+            let exists = self.texture_ids.contains_key(&self.ui_directory.join(&file));
+
+            if !exists
+            {
+                eprintln!("theme texture {name} points to {file} which doesnt exist");
+            }
+
+            exists
+        });
+
+        if let Some(file) = theme.font.as_ref()
+        {
+            if let Some(&id) = self.texture_ids.get(&self.ui_directory.join(file))
+            {
+                self.font = self.views[id].texture;
+            } else
+            {
+                eprintln!("theme font {file} doesnt exist");
+            }
+        }
+
+        self.theme = theme;
+    }
+
+    fn load_slices(&mut self, directory: &Path, path: &Path) -> io::Result<()>
+    {
+        let text = fs::read_to_string(path)?;
+        let slices: HashMap<String, NineSlice> = toml::from_str(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        slices.into_iter().for_each(|(name, slice)|
+        {
+            if let Some(&id) = self.texture_ids.get(&directory.join(&name))
+            {
+                self.slices.insert(id, slice);
+            } else
+            {
+                eprintln!("{} has slices for {name} which doesnt exist", path.display());
+            }
+        });
+
+        Ok(())
+    }
+
+    // named after the path from the tiles directory, gets the first free id
+    pub fn add_tile(
+        &mut self,
+        root: &Path,
+        path: impl Into<PathBuf>
+    ) -> Result<Tile, RegistryError>
+    {
+        let path = path.into();
+        let name = tileset::tile_name(root, &path);
+
+        let info = match self.load_animated_frames(&path)
+        {
+            Some(frames) => TileInfo::animated(frames),
+            None => TileInfo::single(self.load_texture(path))
+        };
+
+        self.insert_tile(None, name, info)
+    }
+
+    // gifs and animated pngs turn into 1 texture with the frames next to each other,
+    // none if it isnt animated (or broken, then the usual loading gives it a placeholder)
+    fn load_animated_frames(&mut self, path: &Path) -> Option<Vec<TileFrame>>
+    {
+        let frames = match Image::load_animation(path)
+        {
+            Ok(x) => x?,
+            Err(err) =>
+            {
+                eprintln!("cant load animation {}: {err}", path.display());
+
+                return None;
+            }
+        };
+
+        let size = frames[0].0.size().map(|x| x as u32);
+
+        let strip = Image::strip(frames.iter().map(|(image, _)| image));
+        let texture = self.push_texture(path, &strip);
+
+        let whole = self.push_view(texture, None);
+        self.texture_ids.insert(path.to_owned(), whole.0);
+
+        Some(frames.iter().enumerate().map(|(index, (_, duration))|
+        {
+            let source = Rect::new(index as i32 * size.x as i32, 0, size.x, size.y);
+
+            let duration = if duration.is_zero() { DEFAULT_FRAME_DURATION } else { *duration };
+
+            TileFrame{texture: self.push_view(texture, Some(source)), duration}
+        }).collect())
+    }
+
+    // animated ones come as a strip of their frames like when they got added
+    fn load_tile_image(path: &Path) -> Result<Image, image::ImageError>
+    {
+        match Image::load_animation(path)?
+        {
+            Some(frames) => Ok(Image::strip(frames.iter().map(|(image, _)| image))),
+            None => Image::load(path)
+        }
+    }
+
+    // a tile that showed up in the tiles directory while running
+    pub fn add_tile_in(
+        &mut self,
+        root: impl AsRef<Path>,
+        path: impl Into<PathBuf>
+    ) -> Result<Tile, RegistryError>
+    {
+        let path = path.into();
+
+        let category = path.parent()
+            .and_then(|directory| Self::category_from_path(root.as_ref(), directory));
+
+        let tile = self.add_tile(root.as_ref(), path)?;
+
+        if let Some(category) = category
+        {
+            self.set_tile_category(tile, category);
+        }
+
+        Ok(tile)
+    }
+
+    // for when the id has to stay the same no matter what else gets loaded
+    pub fn register_tile(
+        &mut self,
+        tile: Tile,
+        name: impl Into<String>,
+        path: impl Into<PathBuf>
+    ) -> Result<(), RegistryError>
+    {
+        let id = self.load_texture(path);
+
+        self.insert_tile(Some(tile), name, TileInfo::single(id)).map(|_| ())
+    }
+
+    // the tile keeps its textures loaded until its removed
+    fn insert_tile(
+        &mut self,
+        tile: Option<Tile>,
+        name: impl Into<String>,
+        info: TileInfo
+    ) -> Result<Tile, RegistryError>
+    {
+        let textures: Vec<TextureId> = info.frames.iter().map(|frame| frame.texture)
+            .chain(info.variants.iter().copied())
+            .collect();
+
+        let tile = if let Some(tile) = tile
+        {
+            self.tiles.register(tile, name, info).map(|_| tile)?
+        } else
+        {
+            self.tiles.push(name, info)?
+        };
+
+        textures.into_iter().for_each(|id| self.retain_texture(id));
+
+        Ok(tile)
+    }
+
+    pub fn add_spritesheet(&mut self, root: &Path, sheet: &Spritesheet)
+    {
+        let Some(tile_size) = sheet.tile_size.or(self.tile_size)
+        else
+        {
+            eprintln!(
+                "cant slice {}, it has no tile size and neither does the project",
+                sheet.image.display()
+            );
+
+            return;
+        };
+
+        let image = match Image::load(&sheet.image)
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant load spritesheet {}: {err}", sheet.image.display());
+
+                return;
+            }
+        };
+
+        let image_size = *image.size();
+
+        let texture = self.push_texture(&sheet.image, &image);
+
+        let name = tileset::tile_name(root, &sheet.image);
+
+        sheet.tiles(image_size, tile_size).enumerate().for_each(|(index, pos)|
+        {
+            let source = Rect::new(
+                pos.x as i32,
+                pos.y as i32,
+                tile_size.x as u32,
+                tile_size.y as u32
+            );
+
+            let id = self.push_view(texture, Some(source));
+
+            let tile_name = format!("{name}_{index}");
+
+            if let Err(err) = self.insert_tile(None, tile_name, TileInfo::single(id))
+            {
+                eprintln!("cant add tile from {}: {err}", sheet.image.display());
+            }
+        });
+    }
+
+    // stays loaded until its released, like ui textures that r needed the whole time
+    pub fn add_texture(&mut self, path: impl Into<PathBuf>) -> TextureId
+    {
+        let id = self.load_texture_now(path.into(), false);
+
+        self.retain_texture(id);
+
+        id
+    }
+
+    // tiles dont block startup if theres a loader, they show a spinner until their image is done
+    pub fn set_background_loading(&mut self, enabled: bool)
+    {
+        if !enabled
+        {
+            self.finish_loading();
+        }
+
+        self.loader = enabled.then(ImageLoader::new);
+    }
+
+    pub fn is_loading(&self) -> bool
+    {
+        !self.loading.is_empty()
+    }
+
+    // makes textures for the images that finished loading, returns if there were any
+    pub fn poll_loading(&mut self) -> bool
+    {
+        let Some(loader) = self.loader.as_mut()
+        else
+        {
+            return false;
+        };
+
+        let loaded = loader.take_loaded();
+
+        let any = !loaded.is_empty();
+
+        self.add_loaded(loaded);
+
+        any
+    }
+
+    // for when the tiles have to be there right now, like in the benchmark
+    pub fn finish_loading(&mut self)
+    {
+        if let Some(loader) = self.loader.as_mut()
+        {
+            let loaded = loader.take_all();
+
+            self.add_loaded(loaded);
+        }
+    }
+
+    fn add_loaded(&mut self, loaded: Vec<LoadedImage>)
+    {
+        loaded.into_iter().for_each(|(index, path, image)|
+        {
+            self.loading.remove(&index);
+
+            let image = image.map(|image| self.fit_image(index, image)).map_err(|err|
+            {
+                eprintln!("cant load {}: {err}", path.display());
+            });
+
+            let size = self.texture_sizes[index].map(|x| x as usize);
+            let texture = self.renderer.borrow_mut().create_texture(
+                image.as_ref().unwrap_or(&Image::placeholder(size))
+            );
+
+            self.renderer.borrow_mut().set_filter(texture, self.tile_filter);
+
+            if let Some(old) = self.textures[index].replace(texture)
+            {
+                self.renderer.borrow_mut().destroy_texture(old);
+            }
+
+            if let Ok(image) = image
+            {
+                self.decoded.insert(index, image);
+            }
+        });
+    }
+
+    // broken or missing images get a placeholder instead so everything else keeps working,
+    // nothing uses it yet so it gets unloaded with the unused ones unless something retains it
+    fn load_texture(&mut self, path: impl Into<PathBuf>) -> TextureId
+    {
+        let path = path.into();
+
+        // if the header cant be read the image is broken anyway, the placeholder comes right away
+        let size = image::image_dimensions(&path).ok().filter(|_| self.loader.is_some());
+        if let Some((width, height)) = size
+        {
+            let size = Point2::new(width, height);
+            let fit_size = self.fit_size(size.map(|x| x as usize));
+
+            let slot_size = fit_size.map_or(size, |x| x.map(|x| x as u32));
+
+            let index = self.push_texture_slot(&path, slot_size);
+            self.textures.push(None);
+
+            if fit_size.is_some()
+            {
+                self.fitted.insert(index, size);
+            }
+
+            self.loading.insert(index);
+            self.loader.as_mut().unwrap().request(index, path.clone());
+
+            let id = self.push_view(index, None);
+            self.texture_ids.insert(path, id.0);
+
+            return id;
+        }
+
+        self.load_texture_now(path, true)
+    }
+
+    // only tiles get fitted to the tile size
+    fn load_texture_now(&mut self, path: PathBuf, fit: bool) -> TextureId
+    {
+        let image = Image::load(&path).unwrap_or_else(|err|
+        {
+            eprintln!("cant load {}: {err}", path.display());
+
+            Image::placeholder(self.tile_size.unwrap_or(Point2::repeat(16)))
+        });
+
+        let size = image.size().map(|x| x as u32);
+        if let Some(fit_size) = self.fit_size(*image.size()).filter(|_| fit)
+        {
+            let image = self.fitted_image(image, fit_size);
+            let id = self.add_image(path, image);
+
+            self.fitted.insert(self.views[id.0].texture, size);
+
+            return id;
+        }
+
+        self.add_image(path, image)
+    }
+
+    // the size a tile image of this size gets fitted to, none if it stays like it is
+    fn fit_size(&self, size: Point2<usize>) -> Option<Point2<usize>>
+    {
+        let tile_size = self.tile_size?;
+
+        (!self.tile_fit.is_stretch() && !tileset::fits_size(tile_size, size)).then_some(tile_size)
+    }
+
+    fn fitted_image(&self, image: Image, size: Point2<usize>) -> Image
+    {
+        match self.tile_fit
+        {
+            TileFit::Stretch => image,
+            TileFit::Pad => image.padded(size),
+            TileFit::Scale => image.scaled(size)
+        }
+    }
+
+    // fits it again if the texture got fitted when it was loaded
+    fn fit_image(&self, index: usize, image: Image) -> Image
+    {
+        match self.tile_size.filter(|_| self.fitted.contains_key(&index))
+        {
+            Some(size) => self.fitted_image(image, size),
+            None => image
+        }
+    }
+
+    // for images that dont come with the editor and might be broken, release it once its done
+    pub fn try_add_texture(
+        &mut self,
+        path: impl Into<PathBuf>
+    ) -> Result<TextureId, image::ImageError>
+    {
+        let path = path.into();
+
+        let image = Image::load(&path)?;
+
+        let id = self.add_image(path, image);
+
+        self.retain_texture(id);
+
+        Ok(id)
+    }
+
+    pub fn retain_texture(&mut self, id: TextureId)
+    {
+        self.texture_refs[self.views[id.0].texture] += 1;
+    }
+
+    pub fn release_texture(&mut self, id: TextureId)
+    {
+        let refs = &mut self.texture_refs[self.views[id.0].texture];
+        *refs = refs.saturating_sub(1);
+    }
+
+    // frees every texture nothing uses anymore, returns how many bytes that was
+    pub fn unload_unused(&mut self) -> usize
+    {
+        let unused: Vec<usize> = (0..self.textures.len())
+            .filter(|index| self.texture_refs[*index] == 0 && self.textures[*index].is_some())
+            .collect();
+
+        let freed = unused.iter().map(|index| self.texture_bytes(*index)).sum();
+
+        let mut renderer = self.renderer.borrow_mut();
+        unused.iter().for_each(|index|
+        {
+            if let Some(texture) = self.textures[*index].take()
+            {
+                renderer.destroy_texture(texture);
+            }
+        });
+
+        // its path could get loaded again later, it shouldnt find the old one
+        let views = &self.views;
+        self.texture_ids.retain(|_, id| !unused.contains(&views[*id].texture));
+
+        freed
+    }
+
+    pub fn texture_memory(&self) -> TextureMemory
+    {
+        self.textures.iter().enumerate().filter(|(_, texture)| texture.is_some())
+            .fold(TextureMemory::default(), |mut memory, (index, _)|
+            {
+                let bytes = self.texture_bytes(index);
+
+                memory.textures += 1;
+                memory.bytes += bytes;
+
+                if self.texture_refs[index] == 0
+                {
+                    memory.unused += 1;
+                    memory.unused_bytes += bytes;
+                }
+
+                memory
+            })
+    }
+
+    fn texture_bytes(&self, index: usize) -> usize
+    {
+        let size = self.texture_sizes[index];
+
+        size.x as usize * size.y as usize * 4
+    }
+
+    // unloaded ones shouldnt get drawn anymore but if they do they show up as the placeholder,
+    // ones that r still loading show the spinner
+    fn render_texture(&self, index: usize) -> RenderTexture
+    {
+        let fallback = if self.loading.contains(&index)
+        {
+            let frame = self.start.elapsed().as_millis() / SPINNER_FRAME_MS;
+
+            self.spinner[frame as usize % self.spinner.len()]
+        } else
+        {
+            self.placeholder
+        };
+
+        self.textures[index].or(self.textures[fallback])
+            .expect("placeholder never gets unloaded")
+    }
+
+    fn add_image(&mut self, path: PathBuf, image: Image) -> TextureId
+    {
+        let texture = self.push_texture(&path, &image);
+        let id = self.push_view(texture, None);
+
+        self.texture_ids.insert(path, id.0);
+
+        id
+    }
+
+    // every texture loaded from this path gets the new image
+    pub fn reload_texture(&mut self, path: impl AsRef<Path>)
+    {
+        let path = path.as_ref();
+
+        // probably still being written, the old pixels r better than nothing
+        let image = match Self::load_tile_image(path)
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant reload {}: {err}", path.display());
+
+                return;
+            }
+        };
+
+        let indices: Vec<usize> = self.texture_paths.iter().enumerate()
+            .filter(|(index, texture_path)|
+            {
+                *texture_path == path && self.textures[*index].is_some()
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        // the atlas has the old pixels until its built again
+        if !indices.is_empty()
+        {
+            self.atlas.clear();
+        }
+
+        indices.into_iter().for_each(|index|
+        {
+            if self.fitted.contains_key(&index)
+            {
+                self.fitted.insert(index, image.size().map(|x| x as u32));
+            }
+
+            let image = self.fit_image(index, image.clone());
+            self.replace_texture(index, &image);
+        });
+    }
+
+    // packs every tile into 1 texture so drawing a scene doesnt keep switching between them,
+    // tiles added after this just get drawn from their own textures until its built again
+    pub fn build_atlas(&mut self)
+    {
+        self.atlas.clear();
+        self.previews.clear();
+
+        let mut ids: Vec<usize> = self.tiles.iter().flat_map(|(_, entry)|
+        {
+            let frames = entry.value.frames.iter().map(|frame| frame.texture.0);
+
+            frames.chain(entry.value.variants.iter().map(|variant| variant.0))
+        }).collect();
+
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut sources: Vec<(usize, usize, Rect)> = ids.into_iter().map(|id|
+        {
+            let view = &self.views[id];
+
+            let source = view.source.unwrap_or_else(||
+            {
+                let size = self.texture_sizes[view.texture];
+
+                Rect::new(0, 0, size.x, size.y)
+            });
+
+            (id, view.texture, source)
+        }).collect();
+
+        // tallest first so the shelves waste less space
+        sources.sort_by_key(|(_, _, source)| Reverse(source.height()));
+
+        let mut placed = Vec::new();
+
+        // so every tile starts on a whole pixel of the halved atlases too
+        let align = 1 << self.mip_levels;
+
+        let mut pos = Point2::new(0, 0);
+        let mut shelf_height = 0;
+        for (id, texture, source) in sources
+        {
+            let size = Point2::new(source.width() as usize, source.height() as usize)
+                .map(|x| x.next_multiple_of(align));
+
+            if size.x > ATLAS_WIDTH
+            {
+                continue;
+            }
+
+            if pos.x + size.x > ATLAS_WIDTH
+            {
+                pos = Point2::new(0, pos.y + shelf_height);
+                shelf_height = 0;
+            }
+
+            placed.push((id, texture, source, pos));
+
+            pos.x += size.x;
+            shelf_height = shelf_height.max(size.y);
+        }
+
+        let height = pos.y + shelf_height;
+        if placed.is_empty() || height > ATLAS_MAX_HEIGHT
+        {
+            if height > ATLAS_MAX_HEIGHT
+            {
+                eprintln!("tiles dont fit in an atlas, drawing them separately");
+            }
+
+            return;
+        }
+
+        let mut data = vec![0; ATLAS_WIDTH * height * 4];
+
+        // the textures r on the gpu already so the images get loaded again,
+        // unless they just finished loading in the background
+        let mut decoded = mem::take(&mut self.decoded);
+        let mut images: HashMap<usize, Option<Image>> = HashMap::new();
+        placed.retain(|(_, texture, source, pos)|
+        {
+            // gets in the next time its built
+            if self.loading.contains(texture)
+            {
+                return false;
+            }
+
+            let image = images.entry(*texture).or_insert_with(||
+            {
+                decoded.remove(texture).or_else(||
+                {
+                    let image = Self::load_tile_image(&self.texture_paths[*texture]).ok()?;
+
+                    Some(self.fit_image(*texture, image))
+                })
+            });
+
+            let Some(image) = image
+            else
+            {
+                return false;
+            };
+
+            let fits = source.right() as usize <= image.size().x
+                && source.bottom() as usize <= image.size().y;
+
+            if !fits
+            {
+                return false;
+            }
+
+            let row_bytes = source.width() as usize * 4;
+            (0..source.height() as usize).for_each(|row|
+            {
+                let from = (source.y() as usize + row) * image.bytes_row()
+                    + source.x() as usize * 4;
+                let to = ((pos.y + row) * ATLAS_WIDTH + pos.x) * 4;
+
+                data[to..to + row_bytes].copy_from_slice(&image.data()[from..from + row_bytes]);
+            });
+
+            true
+        });
+
+        let image = Image::from_raw(Point2::new(ATLAS_WIDTH, height), data);
+
+        let mips: Vec<Image> = (0..self.mip_levels).scan(image.halved(), |mip, _|
+        {
+            let next = mip.halved();
+
+            Some(mem::replace(mip, next))
+        }).collect();
+
+        match self.atlas_texture
+        {
+            Some(index) => self.replace_texture(index, &image),
+            None =>
+            {
+                let index = self.push_texture(Path::new("<atlas>"), &image);
+                self.texture_refs[index] += 1;
+
+                self.atlas_texture = Some(index);
+            }
+        }
+
+        self.atlas_mips.truncate(mips.len());
+        mips.iter().enumerate().for_each(|(level, mip)|
+        {
+            if let Some(&index) = self.atlas_mips.get(level)
+            {
+                self.replace_texture(index, mip);
+            } else
+            {
+                let index = self.push_texture(Path::new("<atlas>"), mip);
+                self.texture_refs[index] += 1;
+
+                self.atlas_mips.push(index);
+            }
+        });
+
+        self.apply_tile_filter();
+
+        self.build_previews(&placed, &images);
+
+        self.atlas = placed.into_iter().map(|(id, _, source, pos)|
+        {
+            (id, Rect::new(pos.x as i32, pos.y as i32, source.width(), source.height()))
+        }).collect();
+    }
+
+    // shrinking the images on the cpu looks way better than the gpu skipping pixels
+    fn build_previews(
+        &mut self,
+        placed: &[(usize, usize, Rect, Point2<usize>)],
+        images: &HashMap<usize, Option<Image>>
+    )
+    {
+        PREVIEW_SIZES.into_iter().enumerate().for_each(|(level, preview_size)|
+        {
+            // only tiles larger than the preview size get shrunk down, smaller ones
+            // can draw themselves
+            let needs_preview: Vec<_> = placed.iter().filter(|(_, _, source, _)|
+            {
+                (source.width().max(source.height()) as usize) > preview_size
+            }).collect();
+
+            let columns = ATLAS_WIDTH / preview_size;
+            let height = needs_preview.len().div_ceil(columns) * preview_size;
+
+            if needs_preview.is_empty() || height > ATLAS_MAX_HEIGHT
+            {
+                return;
+            }
+
+            let mut data = vec![0; ATLAS_WIDTH * height * 4];
+
+            let previews: Vec<(usize, Rect)> = needs_preview.into_iter().enumerate()
+                .filter_map(|(cell, (id, texture, source, _))|
+                {
+                    let image = images.get(texture)?.as_ref()?;
+
+                    let longest = source.width().max(source.height()) as usize;
+                    let size = Point2::new(source.width() as usize, source.height() as usize)
+                        .map(|x| (x * preview_size / longest).max(1));
+
+                    let preview = image.cropped(
+                        Point2::new(source.x() as usize, source.y() as usize),
+                        Point2::new(source.width() as usize, source.height() as usize)
+                    ).shrunk(size);
+
+                    let pos = Point2::new(cell % columns, cell / columns).map(|x| x * preview_size);
+
+                    (0..size.y).for_each(|row|
+                    {
+                        let from = row * preview.bytes_row();
+                        let to = ((pos.y + row) * ATLAS_WIDTH + pos.x) * 4;
+
+                        data[to..to + preview.bytes_row()]
+                            .copy_from_slice(&preview.data()[from..from + preview.bytes_row()]);
+                    });
+
+                    let rect = Rect::new(pos.x as i32, pos.y as i32, size.x as u32, size.y as u32);
+
+                    Some((*id, rect))
+                }).collect();
+
+            let image = Image::from_raw(Point2::new(ATLAS_WIDTH, height), data);
+
+            let texture = if let Some(&index) = self.preview_textures.get(level)
+            {
+                self.replace_texture(index, &image);
+
+                index
+            } else
+            {
+                let index = self.push_texture(Path::new("<previews>"), &image);
+                self.texture_refs[index] += 1;
+
+                self.preview_textures.push(index);
+
+                index
+            };
+
+            // they get drawn a bit bigger or smaller than they r, its not pixel art anymore anyway
+            let preview_texture = self.render_texture(texture);
+            self.renderer.borrow_mut().set_filter(preview_texture, Filter::Linear);
+
+            previews.into_iter().for_each(|(id, rect)|
+            {
+                self.previews.entry(id).or_default().push((texture, rect));
+            });
+        });
+    }
+
+    // the projects tile size, has to be set before loading the tiles
+    pub fn set_tile_size(&mut self, size: Option<Point2<usize>>)
+    {
+        self.tile_size = size;
+    }
+
+    // also has to be set before loading the tiles, it only does anything with a tile size
+    pub fn set_tile_fit(&mut self, fit: TileFit)
+    {
+        self.tile_fit = fit;
+    }
+
+    // linear filtering looks smoother when zoomed out but blurs pixel art up close
+    pub fn set_tile_filter(&mut self, filter: Filter)
+    {
+        self.tile_filter = filter;
+
+        self.apply_tile_filter();
+    }
+
+    // zoomed out views draw tiles from halved copies of the atlas so they dont alias
+    pub fn set_mipmaps(&mut self, enabled: bool)
+    {
+        let levels = if enabled { MIP_LEVELS } else { 0 };
+
+        if levels != self.mip_levels
+        {
+            self.mip_levels = levels;
+
+            self.build_atlas();
+        }
+    }
+
+    fn apply_tile_filter(&mut self)
+    {
+        let mut textures: Vec<usize> = self.tiles.iter().flat_map(|(_, entry)|
+        {
+            let frames = entry.value.frames.iter().map(|frame| frame.texture);
+
+            frames.chain(entry.value.variants.iter().copied())
+        }).map(|id| self.views[id.0].texture).collect();
+
+        textures.extend(self.atlas_texture);
+        textures.extend(self.atlas_mips.iter().copied());
+
+        textures.sort_unstable();
+        textures.dedup();
+
+        // the ones still loading get it when they r done
+        let mut renderer = self.renderer.borrow_mut();
+        textures.into_iter().filter_map(|texture| self.textures[texture]).for_each(|texture|
+        {
+            renderer.set_filter(texture, self.tile_filter);
+        });
+    }
+
+    // removes the tiles using the texture at this path, returns if any got removed
+    pub fn remove_tiles_with(&mut self, path: impl AsRef<Path>) -> bool
+    {
+        let path = path.as_ref();
+
+        let removed: Vec<Tile> = self.tiles.iter().filter(|(_, entry)|
+        {
+            entry.value.frames.iter().any(|frame|
+            {
+                let view = &self.views[frame.texture.0];
+
+                self.texture_paths[view.texture] == path
+            })
+        }).map(|(tile, _)| tile).collect();
+
+        removed.iter().for_each(|tile|
+        {
+            if let Some(entry) = self.tiles.remove(*tile)
+            {
+                entry.value.frames.iter().map(|frame| frame.texture)
+                    .chain(entry.value.variants.iter().copied())
+                    .for_each(|id| self.release_texture(id));
+            }
+        });
+
+        !removed.is_empty()
+    }
+
+    fn push_texture(&mut self, path: &Path, image: &Image) -> usize
+    {
+        let size = image.size().map(|x| x as u32);
+        let id = self.push_texture_slot(path, size);
+
+        let texture = self.renderer.borrow_mut().create_texture(image);
+        self.textures.push(Some(texture));
+
+        id
+    }
+
+    // everything but the texture itself
+    fn push_texture_slot(&mut self, path: &Path, size: Point2<u32>) -> usize
+    {
+        let id = self.textures.len();
+
+        self.texture_paths.push(path.to_owned());
+        self.texture_refs.push(0);
+        self.texture_sizes.push(size);
+
+        id
+    }
+
+    fn replace_texture(&mut self, index: usize, image: &Image)
+    {
+        let mut renderer = self.renderer.borrow_mut();
+
+        if let Some(texture) = self.textures[index]
+        {
+            renderer.destroy_texture(texture);
+        }
+
+        self.textures[index] = Some(renderer.create_texture(image));
+        self.texture_sizes[index] = image.size().map(|x| x as u32);
+    }
+
+    fn push_view(&mut self, texture: usize, source: Option<Rect>) -> TextureId
+    {
+        let id = self.views.len();
+
+        self.views.push(TextureView{texture, source});
+
+        TextureId(id)
+    }
+
+    pub fn texture_id(&self, name: impl AsRef<Path>) -> TextureId
+    {
+        TextureId(self.texture_ids[name.as_ref()])
+    }
+
+    // names like "panel", the theme decides which file that actually is
+    // missing ones show up as the placeholder, like when the ui dir points somewhere wrong
+    pub fn ui_texture(&self, name: &str) -> TextureId
+    {
+        let path = self.ui_directory.join(self.theme.texture_file(name));
+
+        TextureId(self.texture_ids.get(&path).copied().unwrap_or(self.placeholder))
+    }
+
+    pub fn theme(&self) -> &Theme
+    {
+        &self.theme
+    }
+
+    // the current frame if the tile is animated
+    pub fn tile_texture_id(&self, tile: Tile) -> TextureId
+    {
+        self.tile_info(tile).frame_at(self.start.elapsed())
+    }
+
+    // tiles can disappear when their file gets deleted
+    pub fn try_tile_texture_id(&self, tile: Tile) -> Option<TextureId>
+    {
+        self.tiles.get(tile).map(|entry| entry.value.frame_at(self.start.elapsed()))
+    }
+
+    // picks between the tile and its variants, same position and seed always give the same one
+    pub fn tile_variant_texture_id(
+        &self,
+        tile: Tile,
+        pos: Point2<i32>,
+        seed: u32
+    ) -> Option<TextureId>
+    {
+        let info = &self.tiles.get(tile)?.value;
+
+        if info.variants.is_empty()
+        {
+            return Some(info.frame_at(self.start.elapsed()));
+        }
+
+        let index = (variant_hash(pos, seed) % (info.variants.len() as u64 + 1)) as usize;
+
+        Some(if index == 0
+        {
+            info.frame_at(self.start.elapsed())
+        } else
+        {
+            info.variants[index - 1]
+        })
+    }
+
+    pub fn resolve_tile(&self, tile: Tile, size: Point2<u32>) -> Option<ResolvedTile>
+    {
+        let info = &self.tiles.get(tile)?.value;
+
+        let current = info.frame_at(self.start.elapsed());
+        let textures = iter::once(current).chain(info.variants.iter().copied())
+            .map(|id| self.texture_for_size(id, size))
+            .collect();
+
+        Some(ResolvedTile{animated: info.frames.len() > 1, textures})
+    }
+
+    pub fn is_animated(&self, tile: Tile) -> bool
+    {
+        self.tiles.get(tile).map(|entry| entry.value.frames.len() > 1).unwrap_or(false)
+    }
+
+    pub fn tile_properties(&self, tile: Tile) -> &toml::Table
+    {
+        &self.tile_info(tile).properties
+    }
+
+    // how many tiles around it a tile lights up, from a `light` property in its tileset
+    pub fn tile_light(&self, tile: Tile) -> Option<f32>
+    {
+        let value = self.tiles.get(tile)?.value.properties.get("light")?;
+
+        value.as_float().or_else(|| value.as_integer().map(|x| x as f64))
+            .map(|x| x as f32)
+            .filter(|x| *x > 0.0)
+    }
+
+    fn tile_info(&self, tile: Tile) -> &TileInfo
+    {
+        assert!(!tile.is_none());
+
+        &self.tiles.get(tile).unwrap_or_else(||
+        {
+            panic!("tile id {} isnt registered", tile.id())
+        }).value
+    }
+
+    pub fn tile(&self, name: &str) -> Option<Tile>
+    {
+        self.tiles.tile(name)
+    }
+
+    pub fn tile_name(&self, tile: Tile) -> Option<&str>
+    {
+        self.tiles.get(tile).map(|entry| entry.name.as_str())
+    }
+
+    // what the tile gets shown as, its name if it doesnt have a display name
+    pub fn tile_display_name(&self, tile: Tile) -> Option<&str>
+    {
+        self.tiles.get(tile).map(|entry|
+        {
+            entry.value.display_name.as_deref().unwrap_or(entry.name.as_str())
+        })
+    }
+
+    pub fn tile_category(&self, tile: Tile) -> Option<&str>
+    {
+        self.tiles.get(tile).and_then(|entry| entry.value.category.as_deref())
+    }
+
+    // in the order they first show up
+    pub fn categories(&self) -> Vec<String>
+    {
+        let mut categories: Vec<String> = Vec::new();
+        self.tiles.iter().filter_map(|(_, entry)| entry.value.category.as_ref()).for_each(|x|
+        {
+            if !categories.contains(x)
+            {
+                categories.push(x.clone());
+            }
+        });
+
+        categories
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item=Tile> + '_
+    {
+        self.tiles.iter().map(|(tile, _)| tile)
+    }
+
+    pub fn tile_source(&self, tile: Tile) -> TileSource
+    {
+        self.view_source(self.tile_info(tile).frames[0].texture)
+    }
+
+    // every frame of every tile
+    pub fn tile_sources(&self) -> Vec<TileSource>
+    {
+        self.tiles.iter().flat_map(|(_, entry)|
+        {
+            entry.value.frames.iter().map(|frame| self.view_source(frame.texture))
+        }).collect()
+    }
+
+    fn view_source(&self, id: TextureId) -> TileSource
+    {
+        let view = &self.views[id.0];
+
+        let (pos, size) = if let Some(source) = view.source
+        {
+            (
+                Point2::new(source.x() as usize, source.y() as usize),
+                Point2::new(source.width() as usize, source.height() as usize)
+            )
+        } else
+        {
+            // the size of the file, so fitted tiles still show up as the wrong size
+            let size = self.fitted.get(&view.texture).unwrap_or(&self.texture_sizes[view.texture]);
+
+            (Point2::new(0, 0), size.map(|x| x as usize))
+        };
+
+        TileSource{path: self.texture_paths[view.texture].clone(), pos, size}
+    }
+
+    pub fn texture(&self, id: TextureId) -> (RenderTexture, Option<Rect>)
+    {
+        let (texture, source) = self.texture_source(id);
+
+        (self.render_texture(texture), source)
+    }
+
+    // a halved atlas if the texture ends up a lot smaller than it is on screen
+    pub fn texture_for_size(
+        &self,
+        id: TextureId,
+        size: Point2<u32>
+    ) -> (RenderTexture, Option<Rect>)
+    {
+        let (texture, source) = self.texture_source(id);
+
+        let mip = source.filter(|_| Some(texture) == self.atlas_texture).and_then(|source|
+        {
+            let shrink = source.width() as f32 / size.x.max(1) as f32;
+
+            let level = (shrink.log2().floor().max(0.0) as usize).min(self.atlas_mips.len());
+
+            (level > 0).then_some((level, source))
+        });
+
+        if let Some((level, source)) = mip
+        {
+            let scaled = |x: u32| (x >> level).max(1);
+
+            let source = Rect::new(
+                source.x() >> level,
+                source.y() >> level,
+                scaled(source.width()),
+                scaled(source.height())
+            );
+
+            return (self.render_texture(self.atlas_mips[level - 1]), Some(source));
+        }
+
+        (self.render_texture(texture), source)
+    }
+
+    // a preview if theres one that doesnt have to be stretched up to fit,
+    // everything that isnt a tile just gets drawn like normal
+    pub fn preview_texture(
+        &self,
+        id: TextureId,
+        size: Point2<u32>
+    ) -> (RenderTexture, Option<Rect>)
+    {
+        let preview = self.previews.get(&id.0).and_then(|previews|
+        {
+            previews.iter().find(|(_, rect)|
+            {
+                rect.width() >= size.x && rect.height() >= size.y
+            })
+        });
+
+        if let Some((texture, rect)) = preview
+        {
+            return (self.render_texture(*texture), Some(*rect));
+        }
+
+        self.texture(id)
+    }
+
+    // the atlas if its in there
+    fn texture_source(&self, id: TextureId) -> (usize, Option<Rect>)
+    {
+        if let (Some(atlas), Some(source)) = (self.atlas_texture, self.atlas.get(&id.0))
+        {
+            return (atlas, Some(*source));
+        }
+
+        let view = &self.views[id.0];
+
+        (view.texture, view.source)
+    }
+
+    pub fn nine_slice(&self, id: TextureId) -> Option<NineSlice>
+    {
+        self.slices.get(&id.0).copied()
+    }
+
+    pub fn font(&self) -> RenderTexture
+    {
+        self.render_texture(self.font)
+    }
+}
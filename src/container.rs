@@ -81,7 +81,7 @@ macro_rules! impl_iter
 impl_iter!{Iter, SliceIter}
 impl_iter!{IterMut, SliceIterMut}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Container2d<T>
 {
     data: Box<[T]>,
@@ -102,6 +102,20 @@ impl<T> Container2d<T>
         Self{data, indexer, size}
     }
 
+    pub fn from_raw(size: Point2<usize>, data: Box<[T]>) -> Self
+    {
+        assert_eq!(size.x * size.y, data.len());
+
+        let indexer = Indexer::new(size);
+
+        Self{data, indexer, size}
+    }
+
+    pub fn data(&self) -> &[T]
+    {
+        &self.data
+    }
+
     pub fn size(&self) -> &Point2<usize>
     {
         &self.size
@@ -0,0 +1,121 @@
+use std::{
+    fmt,
+    collections::BTreeSet
+};
+
+use crate::{Point2, Tile, Scene};
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change
+{
+    SceneAdded{scene: usize, tiles: usize},
+    SceneRemoved{scene: usize, tiles: usize},
+    Cell{scene: usize, pos: Point2<i32>, old: Tile, new: Tile}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellChange
+{
+    Added,
+    Removed,
+    Changed
+}
+
+impl CellChange
+{
+    pub fn between(old: Tile, new: Tile) -> Option<Self>
+    {
+        match (old.is_none(), new.is_none())
+        {
+            _ if old == new => None,
+            (true, _) => Some(Self::Added),
+            (_, true) => Some(Self::Removed),
+            _ => Some(Self::Changed)
+        }
+    }
+}
+
+fn tile_text(tile: Tile) -> String
+{
+    if tile.is_none()
+    {
+        "empty".to_owned()
+    } else
+    {
+        tile.id().to_string()
+    }
+}
+
+impl fmt::Display for Change
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::SceneAdded{scene, tiles} => write!(f, "+ scene {scene} ({tiles} tiles)"),
+            Self::SceneRemoved{scene, tiles} => write!(f, "- scene {scene} ({tiles} tiles)"),
+            Self::Cell{scene, pos, old, new} =>
+            {
+                write!(
+                    f,
+                    "~ scene {scene} at {} {}: {} -> {}",
+                    pos.x,
+                    pos.y,
+                    tile_text(*old),
+                    tile_text(*new)
+                )
+            }
+        }
+    }
+}
+
+fn tiles_amount(scene: &Scene) -> usize
+{
+    scene.iter().filter(|(_, tile)| !tile.is_none()).count()
+}
+
+// cells r compared by global position so scenes that only grew dont show up as changed,
+// cells come out row by row from the top as (position, old, new)
+pub fn changed_cells(old: &Scene, new: &Scene) -> Vec<(Point2<i32>, Tile, Tile)>
+{
+    let positions: BTreeSet<(i32, i32)> = old.iter().chain(new.iter())
+        .filter(|(_, tile)| !tile.is_none())
+        .map(|(pos, _)| (-pos.y, pos.x))
+        .collect();
+
+    positions.into_iter().filter_map(|(y, x)|
+    {
+        let pos = Point2::new(x, -y);
+
+        let old = old.get(pos).copied().unwrap_or_default();
+        let new = new.get(pos).copied().unwrap_or_default();
+
+        (old != new).then_some((pos, old, new))
+    }).collect()
+}
+
+pub fn diff(old: &[Scene], new: &[Scene]) -> Vec<Change>
+{
+    let mut changes = Vec::new();
+
+    old.iter().zip(new.iter()).enumerate().for_each(|(index, (old, new))|
+    {
+        changes.extend(changed_cells(old, new).into_iter().map(|(pos, old, new)|
+        {
+            Change::Cell{scene: index, pos, old, new}
+        }));
+    });
+
+    changes.extend(new.iter().enumerate().skip(old.len()).map(|(index, scene)|
+    {
+        Change::SceneAdded{scene: index, tiles: tiles_amount(scene)}
+    }));
+
+    changes.extend(old.iter().enumerate().skip(new.len()).map(|(index, scene)|
+    {
+        Change::SceneRemoved{scene: index, tiles: tiles_amount(scene)}
+    }));
+
+    changes
+}
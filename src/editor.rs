@@ -0,0 +1,6828 @@
+use std::{
+    fs,
+    io::{self, Write},
+    mem,
+    iter,
+    thread,
+    rc::Rc,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+    time::{Instant, Duration}
+};
+
+use serde::{Serialize, Deserialize};
+
+use sdl2::{
+    EventPump,
+    event::{Event, WindowEvent},
+    rect::Rect,
+    clipboard::ClipboardUtil,
+    keyboard::{Keycode, Scancode, Mod},
+    mouse::MouseButton,
+    pixels::Color as SdlColor
+};
+
+use crate::{
+    Point2,
+    Tile,
+    Scene,
+    scene::{NO_TINT, CellMap, SceneLink},
+    Assets,
+    assets::ResolvedTile,
+    TextureId,
+    Image,
+    Renderer,
+    RenderTexture,
+    Filter,
+    SdlRenderer,
+    profiler::{Profiler, Phase},
+    history::History,
+    ui::{
+        Ui,
+        RadioGroupId,
+        UiElement,
+        UiElementType,
+        ElementId,
+        UiAnimatableId,
+        Label,
+        Align,
+        Anchor,
+        Length,
+        Layout,
+        draw_label
+    },
+    animator::{Animatable, Animator, AnimatedValue, AnimationState, ValueAnimation},
+    watcher::{DirectoryWatcher, FileChange},
+    notifier::{Notifier, Change},
+    diff::{self, CellChange},
+    tileset::{self, TileFit},
+    task::{Task, Progress},
+    format::{
+        ExportFormat,
+        ExportOptions,
+        project::{Project, AssetDirectories},
+        backup::Backups,
+        prefab::Prefab,
+        template::Template,
+        binary::MappedScene
+    }
+};
+
+
+#[derive(Debug, Clone, Copy)]
+struct Camera
+{
+    pub pos: Point2<f32>,
+    pub height: f32
+}
+
+impl Camera
+{
+    pub fn new(height: f32) -> Self
+    {
+        Self{pos: Point2::new(0.0, 0.0), height}
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CameraValue
+{
+    X,
+    Y,
+    Height
+}
+
+impl Animatable<CameraValue> for Camera
+{
+    fn set(&mut self, id: &CameraValue, value: f32)
+    {
+        match id
+        {
+            CameraValue::X => self.pos.x = value,
+            CameraValue::Y => self.pos.y = value,
+            CameraValue::Height => self.height = value
+        }
+    }
+}
+
+// the tiles around the view drawn once, so a still camera doesnt copy every tile each frame
+struct SceneCache
+{
+    texture: RenderTexture,
+    size: Point2<usize>,
+    // what it got drawn for, anything different means drawing it again
+    scene: usize,
+    revision: u64,
+    camera: Camera,
+    view_size: Point2<usize>,
+    // animated tiles change by themselves so it cant be kept
+    animated: bool
+}
+
+// how dark each visible tile is, 1 pixel per tile and smoothed out when its stretched
+struct LightCache
+{
+    texture: RenderTexture,
+    scene: usize,
+    revision: u64,
+    min: Point2<i32>,
+    max: Point2<i32>
+}
+
+// an image drawn with the scene to trace over
+struct Reference
+{
+    texture: TextureId,
+    // in tiles, where the top left corner is
+    pos: Point2<f32>,
+    // image pixels per tile
+    scale: f32,
+    alpha: u8,
+    // over the tiles instead of under them
+    above: bool
+}
+
+// the camera and scene of the half that isnt being edited right now
+struct SplitView
+{
+    camera: Camera,
+    scene: usize,
+    // if the half the mouse is in (and self.camera belongs to) is the right one
+    active_right: bool
+}
+
+#[derive(Debug, Clone)]
+struct Bookmark
+{
+    name: String,
+    camera: Camera
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlName
+{
+    Forward = 0,
+    Back,
+    Right,
+    Left,
+    ZoomOut,
+    ZoomIn,
+    CreateTile,
+    DeleteTile,
+    Select,
+    // these happen once when pressed instead of being held
+    NextScene,
+    PreviousScene,
+    Undo,
+    Redo,
+    PaintTool,
+    EraseTool,
+    FillTool,
+    SelectTool,
+    LAST
+}
+
+impl ControlName
+{
+    const ALL: [Self; Self::LAST as usize] = [
+        Self::Forward,
+        Self::Back,
+        Self::Right,
+        Self::Left,
+        Self::ZoomOut,
+        Self::ZoomIn,
+        Self::CreateTile,
+        Self::DeleteTile,
+        Self::Select,
+        Self::NextScene,
+        Self::PreviousScene,
+        Self::Undo,
+        Self::Redo,
+        Self::PaintTool,
+        Self::EraseTool,
+        Self::FillTool,
+        Self::SelectTool
+    ];
+
+    // what its called in the keybinds file
+    fn id(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Forward => "forward",
+            Self::Back => "back",
+            Self::Right => "right",
+            Self::Left => "left",
+            Self::ZoomOut => "zoom_out",
+            Self::ZoomIn => "zoom_in",
+            Self::CreateTile => "create_tile",
+            Self::DeleteTile => "delete_tile",
+            Self::Select => "select",
+            Self::NextScene => "next_scene",
+            Self::PreviousScene => "previous_scene",
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::PaintTool => "paint_tool",
+            Self::EraseTool => "erase_tool",
+            Self::FillTool => "fill_tool",
+            Self::SelectTool => "select_tool",
+            Self::LAST => ""
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|control| control.id() == id)
+    }
+
+    fn category(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Forward
+            | Self::Back
+            | Self::Right
+            | Self::Left
+            | Self::ZoomOut
+            | Self::ZoomIn => "camera",
+            Self::NextScene | Self::PreviousScene => "scenes",
+            Self::PaintTool | Self::EraseTool | Self::FillTool | Self::SelectTool => "tools",
+            Self::CreateTile
+            | Self::DeleteTile
+            | Self::Select
+            | Self::Undo
+            | Self::Redo
+            | Self::LAST => "editing"
+        }
+    }
+
+    fn is_action(&self) -> bool
+    {
+        matches!(
+            self,
+            Self::NextScene
+                | Self::PreviousScene
+                | Self::Undo
+                | Self::Redo
+                | Self::PaintTool
+                | Self::EraseTool
+                | Self::FillTool
+                | Self::SelectTool
+        )
+    }
+
+    fn description(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Forward => "move up",
+            Self::Back => "move down",
+            Self::Right => "move right",
+            Self::Left => "move left",
+            Self::ZoomOut => "zoom out",
+            Self::ZoomIn => "zoom in",
+            Self::CreateTile => "use tool",
+            Self::DeleteTile => "remove tile",
+            Self::Select => "select (hold and drag)",
+            Self::NextScene => "next scene",
+            Self::PreviousScene => "previous scene",
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::PaintTool => "paint tool",
+            Self::EraseTool => "erase tool",
+            Self::FillTool => "fill tool",
+            Self::SelectTool => "select tool",
+            Self::LAST => ""
+        }
+    }
+}
+
+const FPS: u32 = 60;
+
+// without any input or animation for this long the editor stops redrawing every frame
+const IDLE_DELAY: Duration = Duration::from_secs(2);
+// how long to wait for an event while idle before drawing anyway
+const IDLE_WAIT_MS: u32 = 250;
+
+// tiles visible vertically at 100% zoom
+const CAMERA_HEIGHT: f32 = 10.0;
+
+// after this many columns the tile picker scrolls instead of the tiles getting any smaller
+const MAX_TILES_ROW: usize = 8;
+
+// the search box and page buttons take up about 2 rows
+const TILES_PER_PAGE: usize = MAX_TILES_ROW * (MAX_TILES_ROW - 2);
+
+// in tiles, how far past the edges of the scene the camera can go when its clamped
+const CAMERA_MARGIN: f32 = 5.0;
+
+// in pixels, how thick the rulers along the top and left edges r
+const RULER_SIZE: i32 = 20;
+
+// how bright tiles far away from any light r, 0 is pitch black
+const AMBIENT_LIGHT: f32 = 0.15;
+
+// bigger lights get cut down to this so finding them stays cheap
+const MAX_LIGHT_RADIUS: i32 = 32;
+
+// zoomed out further than this many tiles across lighting just gets skipped
+const MAX_LIGHT_TILES: usize = 2048;
+
+// ctrl and a number key goes to one, with shift it gets saved there
+const BOOKMARKS: usize = 4;
+
+// in screen pixels, moving less than this while holding the mouse still counts as a click
+const CLICK_DISTANCE: i32 = 4;
+
+// held longer than this its a drag even without moving
+const CLICK_TIME: Duration = Duration::from_millis(300);
+
+// the number row picks between these without ctrl, 1 is the newest and 0 the oldest
+const RECENT_TILES: usize = 10;
+
+// the new scene dialog offers every json file in here
+const TEMPLATES_DIRECTORY: &str = "templates";
+
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+// older ones get pushed out early when theres more
+const MAX_TOASTS: usize = 4;
+
+// what the tint button offers, painted tiles get multiplied by it
+const TINTS: [(&str, [u8; 3]); 8] = [
+    ("no tint", NO_TINT),
+    ("red", [255, 150, 150]),
+    ("orange", [255, 200, 140]),
+    ("yellow", [255, 245, 150]),
+    ("green", [160, 230, 140]),
+    ("blue", [150, 180, 255]),
+    ("purple", [210, 160, 255]),
+    ("dark", [150, 150, 150])
+];
+
+pub struct GameWindow
+{
+    window_size: Point2<u32>,
+    // presenting waits for the screen so the loop doesnt have to sleep
+    vsync: bool,
+    renderer: Rc<RefCell<dyn Renderer>>,
+    // same renderer, for the window specific stuff
+    sdl_renderer: Rc<RefCell<SdlRenderer>>,
+    events: EventPump,
+    clipboard: ClipboardUtil,
+    assets: Rc<RefCell<Assets>>
+}
+
+impl GameWindow
+{
+    pub fn new(window_size: Point2<u32>) -> Self
+    {
+        Self::create(window_size, false)
+    }
+
+    pub fn with_vsync(window_size: Point2<u32>) -> Self
+    {
+        Self::create(window_size, true)
+    }
+
+    fn create(window_size: Point2<u32>, vsync: bool) -> Self
+    {
+        let ctx = sdl2::init().unwrap();
+        let video = ctx.video().unwrap();
+
+        let mut window = video.window("tile thingeringy", window_size.x, window_size.y)
+            .resizable()
+            .build()
+            .unwrap();
+
+        // the ui doesnt fit in anything smaller
+        window.set_minimum_size(320, 240).unwrap();
+
+        let canvas = if vsync
+        {
+            window.into_canvas().present_vsync().build().unwrap()
+        } else
+        {
+            window.into_canvas().build().unwrap()
+        };
+
+        let events = ctx.event_pump().unwrap();
+
+        let clipboard = video.clipboard();
+
+        let sdl_renderer = Rc::new(RefCell::new(SdlRenderer::new(canvas)));
+        let renderer: Rc<RefCell<dyn Renderer>> = sdl_renderer.clone();
+
+        let assets = Rc::new(RefCell::new(Assets::new(renderer.clone())));
+
+        Self{
+            window_size,
+            vsync,
+            renderer,
+            sdl_renderer,
+            events,
+            clipboard,
+            assets
+        }
+    }
+
+    pub fn window_size(&self) -> &Point2<u32>
+    {
+        &self.window_size
+    }
+
+    pub fn assets(&self) -> &Rc<RefCell<Assets>>
+    {
+        &self.assets
+    }
+
+    pub fn renderer(&self) -> &Rc<RefCell<dyn Renderer>>
+    {
+        &self.renderer
+    }
+
+    pub fn set_title(&self, title: &str)
+    {
+        self.sdl_renderer.borrow_mut().set_title(title);
+    }
+}
+
+pub enum EventFlow
+{
+    Pass,
+    Consume
+}
+
+pub type EventHook = Box<dyn FnMut(&Event) -> EventFlow>;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeybindEntry
+{
+    control: String,
+    key: String
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ShortcutEntry
+{
+    shortcut: String,
+    // pressed with ctrl
+    key: String
+}
+
+// replaces all the default keybinds when it exists, shortcuts that arent in it stay the same
+#[derive(Debug, Serialize, Deserialize)]
+struct KeybindsConfig
+{
+    keybinds: Vec<KeybindEntry>,
+    #[serde(default)]
+    shortcuts: Vec<ShortcutEntry>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keybind
+{
+    Keyboard(Keycode),
+    Mouse(u32)
+}
+
+impl From<Keycode> for Keybind
+{
+    fn from(value: Keycode) -> Self
+    {
+        Self::Keyboard(value)
+    }
+}
+
+impl From<u32> for Keybind
+{
+    fn from(value: u32) -> Self
+    {
+        Self::Mouse(value)
+    }
+}
+
+impl Keybind
+{
+    // keys go by their sdl name, mouse buttons r like mouse 0
+    fn from_config(text: &str) -> Option<Self>
+    {
+        if let Some(button) = text.strip_prefix("mouse ")
+        {
+            button.parse().ok().map(Self::Mouse)
+        } else
+        {
+            Keycode::from_name(text).map(Self::Keyboard)
+        }
+    }
+
+    fn to_config(self) -> String
+    {
+        match self
+        {
+            Self::Keyboard(key) => key.name(),
+            Self::Mouse(x) => format!("mouse {x}")
+        }
+    }
+
+    fn name(&self) -> String
+    {
+        match self
+        {
+            Self::Keyboard(key) => key.name(),
+            Self::Mouse(0) => "left mouse".to_owned(),
+            Self::Mouse(1) => "middle mouse".to_owned(),
+            Self::Mouse(2) => "right mouse".to_owned(),
+            Self::Mouse(x) => format!("mouse {x}")
+        }
+    }
+}
+
+// things that happen when a key is pressed with ctrl held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shortcut
+{
+    New,
+    Open,
+    Save,
+    RestoreBackup,
+    Export(ExportFormat),
+    QuickExport,
+    ExportPrefab,
+    ImportPrefab,
+    Copy,
+    Paste,
+    TogglePasteSnap,
+    Reroll,
+    ToggleCrop,
+    MirrorX,
+    MirrorY,
+    Rotate,
+    Translate(i32, i32),
+    Undo,
+    Redo,
+    ToggleGrid,
+    ToggleRulers,
+    ToggleSplit,
+    ToggleZoomSnap,
+    ToggleLighting,
+    ZoomIn,
+    ZoomOut,
+    FitScene,
+    // how many screen pixels each tile pixel takes up
+    PixelZoom(u32),
+    Bookmark(usize),
+    ReferenceAlpha(i32),
+    ReferenceScale(i32),
+    ToggleReferenceAbove
+}
+
+impl Shortcut
+{
+    // what its called in keybinds.toml
+    fn id(&self) -> String
+    {
+        let id = match self
+        {
+            Self::New => "new",
+            Self::Open => "open",
+            Self::Save => "save",
+            Self::RestoreBackup => "restore_backup",
+            Self::Export(format) => return format!("export_{}", format.extension()),
+            Self::QuickExport => "quick_export",
+            Self::ExportPrefab => "export_prefab",
+            Self::ImportPrefab => "import_prefab",
+            Self::Copy => "copy",
+            Self::Paste => "paste",
+            Self::TogglePasteSnap => "toggle_paste_snap",
+            Self::Reroll => "reroll",
+            Self::ToggleCrop => "toggle_crop",
+            Self::MirrorX => "mirror_x",
+            Self::MirrorY => "mirror_y",
+            Self::Rotate => "rotate",
+            Self::Translate(0, 1) => "move_up",
+            Self::Translate(0, -1) => "move_down",
+            Self::Translate(1, 0) => "move_right",
+            Self::Translate(-1, 0) => "move_left",
+            Self::Translate(x, y) => return format!("move_{x}_{y}"),
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::ToggleGrid => "toggle_grid",
+            Self::ToggleRulers => "toggle_rulers",
+            Self::ToggleSplit => "toggle_split",
+            Self::ToggleZoomSnap => "toggle_zoom_snap",
+            Self::ToggleLighting => "toggle_lighting",
+            Self::ZoomIn => "zoom_in",
+            Self::ZoomOut => "zoom_out",
+            Self::FitScene => "fit_scene",
+            Self::PixelZoom(x) => return format!("pixel_zoom_{x}"),
+            Self::Bookmark(x) => return format!("bookmark_{}", x + 1),
+            Self::ReferenceAlpha(-1) => "reference_alpha_down",
+            Self::ReferenceAlpha(_) => "reference_alpha_up",
+            Self::ReferenceScale(-1) => "reference_smaller",
+            Self::ReferenceScale(_) => "reference_bigger",
+            Self::ToggleReferenceAbove => "toggle_reference_above"
+        };
+
+        id.to_owned()
+    }
+
+    fn category(&self) -> &'static str
+    {
+        match self
+        {
+            Self::New | Self::Open | Self::Save | Self::RestoreBackup => "file",
+            Self::Undo | Self::Redo => "edit",
+            Self::ToggleGrid
+            | Self::ToggleRulers
+            | Self::ToggleSplit
+            | Self::ToggleZoomSnap
+            | Self::ToggleLighting
+            | Self::ZoomIn
+            | Self::ZoomOut
+            | Self::FitScene
+            | Self::PixelZoom(_)
+            | Self::Bookmark(_) => "view",
+            Self::Export(_) | Self::QuickExport | Self::ToggleCrop => "export",
+            Self::ExportPrefab
+            | Self::ImportPrefab
+            | Self::Copy
+            | Self::Paste
+            | Self::TogglePasteSnap
+            | Self::Reroll => "selection",
+            Self::MirrorX | Self::MirrorY | Self::Rotate | Self::Translate(..) => "scene",
+            Self::ReferenceAlpha(_)
+            | Self::ReferenceScale(_)
+            | Self::ToggleReferenceAbove => "reference image"
+        }
+    }
+
+    fn description(&self) -> &'static str
+    {
+        match self
+        {
+            Self::New => "new project",
+            Self::Open => "reopen project",
+            Self::Save => "save",
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::ToggleGrid => "toggle grid",
+            Self::ToggleRulers => "toggle rulers",
+            Self::ToggleSplit => "toggle split view",
+            Self::ToggleZoomSnap => "toggle whole pixel zoom",
+            Self::ToggleLighting => "toggle lighting",
+            Self::ZoomIn => "zoom in",
+            Self::ZoomOut => "zoom out",
+            Self::FitScene => "fit scene in view",
+            Self::PixelZoom(1) => "zoom to 1:1 pixels",
+            Self::PixelZoom(2) => "zoom to 2x pixels",
+            Self::PixelZoom(4) => "zoom to 4x pixels",
+            Self::PixelZoom(_) => "zoom to pixels",
+            Self::RestoreBackup => "restore older backup",
+            Self::Export(ExportFormat::Godot) => "export godot scene",
+            Self::Export(ExportFormat::Rust) => "export rust array",
+            Self::Export(ExportFormat::C) => "export c array",
+            Self::Export(ExportFormat::Binary) => "export binary map",
+            Self::QuickExport => "repeat last export",
+            Self::ToggleCrop => "toggle crop on export",
+            Self::ExportPrefab => "save selection as prefab",
+            Self::ImportPrefab => "paste last prefab",
+            Self::Copy => "copy selection",
+            Self::Paste => "paste",
+            Self::TogglePasteSnap => "toggle paste grid snap",
+            Self::Reroll => "reroll tile variants",
+            Self::MirrorX => "mirror horizontally",
+            Self::MirrorY => "mirror vertically",
+            Self::Rotate => "rotate clockwise",
+            Self::Translate(0, 1) => "move up",
+            Self::Translate(0, -1) => "move down",
+            Self::Translate(1, 0) => "move right",
+            Self::Translate(-1, 0) => "move left",
+            Self::Translate(..) => "move",
+            Self::Bookmark(_) => "go to bookmark (with shift saves it)",
+            Self::ReferenceAlpha(-1) => "more see through",
+            Self::ReferenceAlpha(_) => "less see through",
+            Self::ReferenceScale(-1) => "smaller",
+            Self::ReferenceScale(_) => "bigger",
+            Self::ToggleReferenceAbove => "toggle over or under tiles"
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExportSettings
+{
+    kind: ExportFormat,
+    scene: usize,
+    path: PathBuf,
+    options: ExportOptions
+}
+
+// what the left mouse button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool
+{
+    Paint,
+    Erase,
+    Fill,
+    Select
+}
+
+impl Tool
+{
+    const ALL: [Self; 4] = [Self::Paint, Self::Erase, Self::Fill, Self::Select];
+
+    fn name(self) -> &'static str
+    {
+        match self
+        {
+            Self::Paint => "paint",
+            Self::Erase => "erase",
+            Self::Fill => "fill",
+            Self::Select => "select"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UiMessage
+{
+    Scene(usize),
+    NewScene,
+    ToggleTiles,
+    Tool(Tool),
+    OpenTints,
+    // index into the menu bar
+    OpenMenu(usize)
+}
+
+#[derive(Debug, Clone)]
+enum TilesMessage
+{
+    Tab(Option<String>),
+    PreviousPage,
+    NextPage,
+    Scrolled(f32),
+    Tile(Tile)
+}
+
+struct TilesUi
+{
+    ui: Ui<TilesMessage>,
+    panel: ElementId
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuAction
+{
+    Fill,
+    PickTile,
+    ClearRegion,
+    Help,
+    EditKeybinds,
+    RenameScene(usize),
+    DeleteScene(usize),
+    SceneProperties(usize),
+    // swaps it with the one before or after it
+    MoveSceneLeft(usize),
+    MoveSceneRight(usize),
+    SaveTemplate(usize),
+    CompareSaved(usize),
+    // the current scene against this one
+    CompareWith(usize),
+    StopComparing,
+    SaveBookmark,
+    MoveReference,
+    StartLink,
+    FollowLink,
+    RemoveLink,
+    // index into the tints
+    Tint(usize),
+    Shortcut(Shortcut)
+}
+
+impl MenuAction
+{
+    fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Fill => "fill here",
+            Self::PickTile => "pick tile",
+            Self::ClearRegion => "clear region",
+            Self::Help => "keybinds",
+            Self::EditKeybinds => "edit keybinds",
+            Self::RenameScene(_) => "rename",
+            Self::DeleteScene(_) => "delete",
+            Self::SceneProperties(_) => "properties",
+            Self::MoveSceneLeft(_) => "move left",
+            Self::MoveSceneRight(_) => "move right",
+            Self::SaveTemplate(_) => "save as template",
+            Self::CompareSaved(_) => "compare with saved",
+            Self::CompareWith(_) => "compare current with this",
+            Self::StopComparing => "stop comparing",
+            Self::SaveBookmark => "save bookmark",
+            Self::MoveReference => "move reference here",
+            Self::StartLink => "link from here",
+            Self::FollowLink => "follow link",
+            Self::RemoveLink => "remove link",
+            Self::Tint(index) => TINTS[*index].0,
+            Self::Shortcut(shortcut) => shortcut.description()
+        }
+    }
+}
+
+// a message in the bottom right corner that slides in and leaves by itself,
+// its element in the toasts ui has the same index as it has in the queue
+struct Toast
+{
+    shown: Instant,
+    // leaving plays it backwards
+    slide: Animator<UiAnimatableId>,
+    leaving: bool
+}
+
+// waits for yes or no before doing something that cant be easily taken back,
+// nothing else gets any input while its open
+struct ConfirmDialog
+{
+    ui: Ui<bool>,
+    on_yes: Box<dyn FnOnce(&mut Game)>
+}
+
+// typing goes into the line at the top like `key = value`, enter sets it on the scene
+struct PropertiesDialog
+{
+    ui: Ui<usize>,
+    scene: usize,
+    input: String
+}
+
+// picks what a new scene starts out as, the first row is an empty one and then the templates
+struct NewSceneDialog
+{
+    ui: Ui<usize>,
+    templates: Vec<PathBuf>
+}
+
+enum DiffTarget
+{
+    // another scene in the project, edits to it show up right away
+    Scene(usize),
+    // how the scene looked in the save file
+    Saved(Box<Scene>)
+}
+
+// highlights how a scene differs from the target while its some
+struct SceneDiff
+{
+    scene: usize,
+    target: DiffTarget,
+    // of the target and the scene when the cells were found
+    revisions: Option<(u64, u64)>,
+    cells: Vec<(Point2<i32>, CellChange)>
+}
+
+// runs on the main thread after the task is done
+type TaskDone = Box<dyn FnOnce(&mut Game) + Send>;
+
+// only 1 at a time, its progress gets drawn over everything
+struct RunningTask
+{
+    task: Task<TaskDone>,
+    ui: Ui,
+    bar: ElementId
+}
+
+// in chunks so the progress moves for big files
+fn write_with_progress(path: &Path, bytes: &[u8], progress: impl Fn(f32)) -> io::Result<()>
+{
+    const CHUNK: usize = 1 << 16;
+
+    let mut file = fs::File::create(path)?;
+
+    bytes.chunks(CHUNK).enumerate().try_for_each(|(index, chunk)|
+    {
+        file.write_all(chunk)?;
+
+        progress(((index + 1) * CHUNK).min(bytes.len()) as f32 / bytes.len() as f32);
+
+        Ok(())
+    })
+}
+
+// goes away after anything gets clicked
+struct ContextMenu
+{
+    ui: Ui<MenuAction>,
+    // the tile that got right clicked
+    pos: Point2<i32>
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Selection
+{
+    start: Point2<i32>,
+    end: Point2<i32>
+}
+
+impl Selection
+{
+    pub fn bounds(&self) -> (Point2<i32>, Point2<i32>)
+    {
+        (
+            self.start.zip(self.end).map(|(a, b)| a.min(b)),
+            self.start.zip(self.end).map(|(a, b)| a.max(b))
+        )
+    }
+}
+
+// where and when the left button went down on the scene, tells clicks and drags apart
+#[derive(Debug, Clone, Copy)]
+struct MousePress
+{
+    pos: Point2<i32>,
+    time: Instant,
+    // 2 if its the second press of a double click
+    clicks: u8,
+    // if it was with the select tool
+    select: bool
+}
+
+impl MousePress
+{
+    fn is_click(&self, pos: Point2<i32>) -> bool
+    {
+        let moved = (pos - self.pos).map(|x| x.abs());
+
+        moved.x.max(moved.y) < CLICK_DISTANCE && self.time.elapsed() < CLICK_TIME
+    }
+
+    fn is_double(&self) -> bool
+    {
+        self.clicks >= 2
+    }
+}
+
+enum UiVariant
+{
+    Normal,
+    Tiles
+}
+
+// giga super big struct cuz im lazy
+pub struct Game
+{
+    aspect: f32,
+    window_size: Point2<usize>,
+    camera: Camera,
+    // flying to a bookmark, moving by hand stops it
+    camera_flight: Option<Animator<CameraValue>>,
+    bookmarks: [Option<Bookmark>; BOOKMARKS],
+    controls: [bool; ControlName::LAST as usize],
+    scenes: Vec<Scene>,
+    current_scene: usize,
+    current_tile: Tile,
+    // newest first, picking one thats already in here doesnt move it so the numbers stay put
+    recent_tiles: Vec<Tile>,
+    window: Rc<RefCell<GameWindow>>,
+    renderer: Rc<RefCell<dyn Renderer>>,
+    assets: Rc<RefCell<Assets>>,
+    scene_tabs: ElementId,
+    // what the tabs were built for, the labels, current scene and window size
+    scene_tabs_shown: Option<(Vec<String>, usize, Point2<usize>)>,
+    // the scene and its name from before
+    renaming_scene: Option<(usize, Option<String>)>,
+    // where a new link starts while the place it leads to is being picked
+    linking: Option<(usize, Point2<i32>)>,
+    status_label: ElementId,
+    // so the window only gets a new title when it actually changes
+    window_title: String,
+    // the button and whats in its menu
+    menu_bar: Vec<(ElementId, Vec<MenuAction>)>,
+    current_tile_button: ElementId,
+    tool: Tool,
+    tools: RadioGroupId,
+    brush_tint: [u8; 3],
+    tint_button: ElementId,
+    tiles_paged: bool,
+    clamp_camera: bool,
+    snap_zoom: bool,
+    // where the zoom was last snapped to, tells which way its going
+    snapped_height: f32,
+    tiles_pages: HashMap<Option<String>, usize>,
+    tiles_category: Option<String>,
+    tiles_search: String,
+    // stays the same when the picker gets rebuilt
+    tiles_scroll: f32,
+    keybinds: Vec<(Keybind, ControlName)>,
+    shortcuts: Vec<(Keycode, Shortcut)>,
+    help_ui: Ui,
+    show_help: bool,
+    show_profiler: bool,
+    profiler: Profiler,
+    context_menu: Option<ContextMenu>,
+    toasts: VecDeque<Toast>,
+    toasts_ui: Ui,
+    confirm_dialog: Option<ConfirmDialog>,
+    properties_dialog: Option<PropertiesDialog>,
+    scene_diff: Option<SceneDiff>,
+    new_scene_dialog: Option<NewSceneDialog>,
+    task: Option<RunningTask>,
+    // open while its some, clicking a keybind waits for the next key to replace it
+    keybinds_ui: Option<Ui<usize>>,
+    rebinding: Option<usize>,
+    keybinds_path: Option<PathBuf>,
+    mouse_pos: Point2<i32>,
+    mouse_held: bool,
+    mouse_press: Option<MousePress>,
+    ui: Ui<UiMessage>,
+    tiles_panel: ElementId,
+    tiles_panel_pos: Point2<f32>,
+    tiles_panel_size: Point2<f32>,
+    tiles_window_animator_open: Animator<UiAnimatableId>,
+    tiles_window_animator_close: Animator<UiAnimatableId>,
+    tiles_ui: Ui<TilesMessage>,
+    current_ui: UiVariant,
+    event_hook: Option<EventHook>,
+    injected_events: Vec<Event>,
+    save_path: PathBuf,
+    tilesets: Vec<PathBuf>,
+    // pixel size of the tiles from the project, they can be wider or taller than square
+    tile_pixels: Option<Point2<usize>>,
+    tile_fit: TileFit,
+    // like they r in the project file, they only matter when its loaded again
+    directories: AssetDirectories,
+    backups: Backups,
+    restore_index: usize,
+    export_options: ExportOptions,
+    last_export: Option<ExportSettings>,
+    selection: Option<Selection>,
+    selecting: bool,
+    painting: bool,
+    history: History,
+    show_grid: bool,
+    show_rulers: bool,
+    split: Option<SplitView>,
+    reference: Option<Reference>,
+    // 1 for each half of the split view
+    scene_caches: [Option<SceneCache>; 2],
+    lighting: bool,
+    ambient_light: f32,
+    light_caches: [Option<LightCache>; 2],
+    // the scene gets drawn into a cache thats bigger than the view
+    view_override: Option<Point2<usize>>,
+    last_frame: Instant,
+    fps: u32,
+    idle_throttle: bool,
+    last_activity: Instant,
+    viewer: Option<MappedScene>,
+    watcher: Option<(DirectoryWatcher, PathBuf)>,
+    last_prefab: Option<PathBuf>,
+    notifiers: Vec<Notifier>,
+    paste_snap: bool,
+    paste_grid: Option<Point2<i32>>
+}
+
+impl Game
+{
+    pub fn new(
+        window_size: Point2<usize>,
+        window: Rc<RefCell<GameWindow>>
+    ) -> Self
+    {
+        let aspect = window_size.x as f32 / window_size.y as f32;
+
+        let camera = Camera::new(CAMERA_HEIGHT);
+
+        let controls = [false; ControlName::LAST as usize];
+
+        let scenes = Vec::new();
+
+        let assets = window.borrow().assets.clone();
+        let renderer = window.borrow().renderer.clone();
+
+        let current_tile = assets.borrow().tiles().next().expect("must have at least 1 tile");
+
+        let mut ui = Ui::new(renderer.clone(), assets.clone());
+
+        let ui_texture = |name: &str|
+        {
+            assets.borrow().ui_texture(name)
+        };
+
+        let tile_texture_id = |tile: Tile|
+        {
+            assets.borrow().tile_texture_id(tile)
+        };
+
+        // anchored elements get their position and size from the layout
+        let anchored = |kind, texture|
+        {
+            UiElement{
+                kind,
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(0.0, 0.0),
+                texture,
+                color: None,
+                alpha: 255
+            }
+        };
+
+        let pixels = |x: f32, y: f32| Point2::new(Length::Absolute(x), Length::Absolute(y));
+
+        // the tabs themselves get added every time the scenes change
+        let scene_tabs = ui.push_anchored(
+            anchored(UiElementType::Panel, ui_texture("background")),
+            Self::scene_tabs_layout()
+        );
+
+        let status_label = {
+            let status_bar = ui.push_anchored(
+                anchored(UiElementType::Panel, ui_texture("background")),
+                Layout::new(
+                    Anchor::BottomLeft,
+                    Point2::new(Length::Relative(1.0), Length::Absolute(26.0))
+                )
+            );
+
+            ui.push_child_anchored(
+                &status_bar,
+                anchored(
+                    UiElementType::Label(Label{
+                        text: String::new(),
+                        font_size: None,
+                        color: SdlColor::WHITE,
+                        align: Align::Left
+                    }),
+                    ui_texture("white")
+                ),
+                Layout::new(
+                    Anchor::Left,
+                    Point2::new(Length::Relative(0.98), Length::Relative(0.7))
+                ).with_margin(pixels(assets.borrow().theme().margin, 0.0))
+            )
+        };
+
+        let menu_bar: Vec<(ElementId, Vec<MenuAction>)> = {
+            let menus = [
+                ("file", vec![
+                    Shortcut::New,
+                    Shortcut::Open,
+                    Shortcut::Save,
+                    Shortcut::RestoreBackup,
+                    Shortcut::Export(ExportFormat::Godot),
+                    Shortcut::Export(ExportFormat::Rust),
+                    Shortcut::Export(ExportFormat::C),
+                    Shortcut::Export(ExportFormat::Binary),
+                    Shortcut::QuickExport
+                ]),
+                ("edit", vec![
+                    Shortcut::Undo,
+                    Shortcut::Redo,
+                    Shortcut::Copy,
+                    Shortcut::Paste,
+                    Shortcut::Reroll
+                ]),
+                ("view", vec![
+                    Shortcut::ToggleGrid,
+                    Shortcut::ToggleRulers,
+                    Shortcut::ToggleSplit,
+                    Shortcut::ToggleZoomSnap,
+                    Shortcut::ToggleLighting,
+                    Shortcut::ZoomIn,
+                    Shortcut::ZoomOut,
+                    Shortcut::FitScene,
+                    Shortcut::PixelZoom(1),
+                    Shortcut::PixelZoom(2),
+                    Shortcut::PixelZoom(4)
+                ])
+            ];
+
+            menus.into_iter().enumerate().map(|(index, (name, shortcuts))|
+            {
+                let margin = pixels(83.0 + index as f32 * 70.0, 0.0);
+
+                let button = ui.push_anchored(
+                    anchored(UiElementType::Button, ui_texture("background")),
+                    Layout::new(Anchor::TopLeft, pixels(64.0, 32.0)).with_margin(margin)
+                );
+
+                // stays over the scene label when the window is narrow
+                ui.set_z(&button, 1);
+
+                ui.on_click(&button, UiMessage::OpenMenu(index));
+
+                ui.push_child(&button, UiElement{
+                    kind: UiElementType::Label(Label{
+                        text: name.to_owned(),
+                        font_size: None,
+                        color: SdlColor::WHITE,
+                        align: Align::Center
+                    }),
+                    pos: Point2::new(0.1, 0.2),
+                    size: Point2::new(0.8, 0.6),
+                    texture: ui_texture("white"),
+                    color: None,
+                    alpha: 255
+                });
+
+                let mut actions: Vec<MenuAction> = shortcuts.into_iter()
+                    .map(MenuAction::Shortcut)
+                    .collect();
+
+                if name == "view"
+                {
+                    actions.push(MenuAction::SaveBookmark);
+                    actions.extend((0..BOOKMARKS).map(|index|
+                    {
+                        MenuAction::Shortcut(Shortcut::Bookmark(index))
+                    }));
+
+                    actions.push(MenuAction::Help);
+                    actions.push(MenuAction::EditKeybinds);
+                }
+
+                (button, actions)
+            }).collect()
+        };
+
+        let current_tile_button;
+        {
+            let size = 64.0;
+            let margin = size * 0.1;
+
+            ui.push_anchored(
+                anchored(UiElementType::Panel, ui_texture("white")),
+                Layout::new(Anchor::TopLeft, pixels(size + margin, size + margin))
+            );
+
+            ui.push_anchored(
+                anchored(UiElementType::Panel, ui_texture("background")),
+                Layout::new(Anchor::TopLeft, pixels(size, size))
+            );
+
+            current_tile_button = ui.push_anchored(
+                anchored(UiElementType::Button, tile_texture_id(current_tile)),
+                Layout::new(Anchor::TopLeft, pixels(size, size))
+            );
+
+            ui.on_click(&current_tile_button, UiMessage::ToggleTiles);
+        }
+
+        // under the current tile
+        let tools = {
+            let buttons = Tool::ALL.iter().enumerate().map(|(index, tool)|
+            {
+                let margin = pixels(0.0, 76.0 + index as f32 * 30.0);
+
+                let button = ui.push_anchored(
+                    anchored(UiElementType::Button, ui_texture("background")),
+                    Layout::new(Anchor::TopLeft, pixels(64.0, 26.0)).with_margin(margin)
+                );
+
+                ui.push_child(&button, UiElement{
+                    kind: UiElementType::Label(Label{
+                        text: tool.name().to_owned(),
+                        font_size: None,
+                        color: SdlColor::WHITE,
+                        align: Align::Center
+                    }),
+                    pos: Point2::new(0.1, 0.2),
+                    size: Point2::new(0.8, 0.6),
+                    texture: ui_texture("white"),
+                    color: None,
+                    alpha: 255
+                });
+
+                button
+            }).collect();
+
+            ui.push_radio_group(buttons, 0, |index| UiMessage::Tool(Tool::ALL[index]))
+        };
+
+        // under the tools, colored like the tint
+        let tint_button = {
+            let margin = pixels(0.0, 76.0 + Tool::ALL.len() as f32 * 30.0);
+
+            let button = ui.push_anchored(
+                anchored(UiElementType::Button, ui_texture("white")),
+                Layout::new(Anchor::TopLeft, pixels(64.0, 26.0)).with_margin(margin)
+            );
+
+            ui.push_child(&button, UiElement{
+                kind: UiElementType::Label(Label{
+                    text: "tint".to_owned(),
+                    font_size: None,
+                    color: SdlColor::BLACK,
+                    align: Align::Center
+                }),
+                pos: Point2::new(0.1, 0.2),
+                size: Point2::new(0.8, 0.6),
+                texture: ui_texture("white"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.on_click(&button, UiMessage::OpenTints);
+
+            button
+        };
+
+        let (panel_pos, panel_size) = Self::tiles_panel_area(aspect);
+
+        let tiles_ui = Self::create_tiles_ui(
+            &renderer,
+            &assets,
+            panel_pos,
+            panel_size,
+            None,
+            None,
+            ""
+        );
+
+        let (tiles_window_animator_open, tiles_window_animator_close) =
+            Self::tiles_window_animators(panel_pos, panel_size);
+
+        let keybinds: Vec<(Keybind, _)> = vec![
+            (Keycode::W.into(), ControlName::Forward),
+            (Keycode::S.into(), ControlName::Back),
+            (Keycode::A.into(), ControlName::Left),
+            (Keycode::D.into(), ControlName::Right),
+            (Keycode::Space.into(), ControlName::ZoomOut),
+            (Keycode::LCtrl.into(), ControlName::ZoomIn),
+            (0.into(), ControlName::CreateTile),
+            (Keycode::Z.into(), ControlName::CreateTile),
+            (2.into(), ControlName::DeleteTile),
+            (Keycode::X.into(), ControlName::DeleteTile),
+            (Keycode::LShift.into(), ControlName::Select),
+            (Keycode::PageDown.into(), ControlName::NextScene),
+            (Keycode::PageUp.into(), ControlName::PreviousScene),
+            (Keycode::U.into(), ControlName::Undo),
+            (Keycode::Y.into(), ControlName::Redo),
+            (Keycode::B.into(), ControlName::PaintTool),
+            (Keycode::E.into(), ControlName::EraseTool),
+            (Keycode::F.into(), ControlName::FillTool),
+            (Keycode::R.into(), ControlName::SelectTool),
+        ];
+
+        let shortcuts: Vec<(Keycode, Shortcut)> = vec![
+            (Keycode::N, Shortcut::New),
+            (Keycode::O, Shortcut::Open),
+            (Keycode::S, Shortcut::Save),
+            (Keycode::R, Shortcut::RestoreBackup),
+            (Keycode::G, Shortcut::Export(ExportFormat::Godot)),
+            (Keycode::U, Shortcut::Export(ExportFormat::Rust)),
+            (Keycode::L, Shortcut::Export(ExportFormat::C)),
+            (Keycode::B, Shortcut::Export(ExportFormat::Binary)),
+            (Keycode::E, Shortcut::QuickExport),
+            (Keycode::K, Shortcut::ToggleCrop),
+            (Keycode::P, Shortcut::ExportPrefab),
+            (Keycode::I, Shortcut::ImportPrefab),
+            (Keycode::C, Shortcut::Copy),
+            (Keycode::V, Shortcut::Paste),
+            (Keycode::F, Shortcut::TogglePasteSnap),
+            (Keycode::D, Shortcut::Reroll),
+            (Keycode::H, Shortcut::MirrorX),
+            (Keycode::J, Shortcut::MirrorY),
+            (Keycode::T, Shortcut::Rotate),
+            (Keycode::Up, Shortcut::Translate(0, 1)),
+            (Keycode::Down, Shortcut::Translate(0, -1)),
+            (Keycode::Right, Shortcut::Translate(1, 0)),
+            (Keycode::Left, Shortcut::Translate(-1, 0)),
+            (Keycode::Z, Shortcut::Undo),
+            (Keycode::Y, Shortcut::Redo),
+            (Keycode::Quote, Shortcut::ToggleGrid),
+            (Keycode::Semicolon, Shortcut::ToggleRulers),
+            (Keycode::Backslash, Shortcut::ToggleSplit),
+            (Keycode::Period, Shortcut::ToggleZoomSnap),
+            (Keycode::Comma, Shortcut::ToggleLighting),
+            (Keycode::LeftBracket, Shortcut::ReferenceAlpha(-1)),
+            (Keycode::RightBracket, Shortcut::ReferenceAlpha(1)),
+            (Keycode::Q, Shortcut::ReferenceScale(-1)),
+            (Keycode::W, Shortcut::ReferenceScale(1)),
+            (Keycode::M, Shortcut::ToggleReferenceAbove),
+            (Keycode::Equals, Shortcut::ZoomIn),
+            (Keycode::Minus, Shortcut::ZoomOut),
+            (Keycode::Num1, Shortcut::Bookmark(0)),
+            (Keycode::Num2, Shortcut::Bookmark(1)),
+            (Keycode::Num3, Shortcut::Bookmark(2)),
+            (Keycode::Num4, Shortcut::Bookmark(3)),
+            (Keycode::Num0, Shortcut::FitScene),
+            (Keycode::Num7, Shortcut::PixelZoom(1)),
+            (Keycode::Num8, Shortcut::PixelZoom(2)),
+            (Keycode::Num9, Shortcut::PixelZoom(4)),
+        ];
+
+        let help_ui = Self::create_help_ui(&renderer, &assets, &keybinds, &shortcuts);
+
+        let toasts_ui = Ui::new(renderer.clone(), assets.clone());
+
+        let mut this = Self{
+            aspect,
+            window_size,
+            camera,
+            camera_flight: None,
+            bookmarks: Default::default(),
+            controls,
+            scenes,
+            current_scene: 0,
+            current_tile,
+            recent_tiles: Vec::new(),
+            scene_tabs,
+            scene_tabs_shown: None,
+            renaming_scene: None,
+            linking: None,
+            status_label,
+            window_title: String::new(),
+            menu_bar,
+            current_tile_button,
+            tool: Tool::Paint,
+            tools,
+            brush_tint: NO_TINT,
+            tint_button,
+            tiles_paged: false,
+            clamp_camera: false,
+            snap_zoom: false,
+            snapped_height: CAMERA_HEIGHT,
+            tiles_pages: HashMap::new(),
+            tiles_category: None,
+            tiles_search: String::new(),
+            tiles_scroll: 0.0,
+            keybinds,
+            shortcuts,
+            help_ui,
+            show_help: false,
+            show_profiler: false,
+            profiler: Profiler::new(),
+            context_menu: None,
+            toasts: VecDeque::new(),
+            toasts_ui,
+            confirm_dialog: None,
+            properties_dialog: None,
+            scene_diff: None,
+            new_scene_dialog: None,
+            task: None,
+            keybinds_ui: None,
+            rebinding: None,
+            keybinds_path: None,
+            mouse_pos: Point2::new(0, 0),
+            mouse_held: false,
+            mouse_press: None,
+            window,
+            renderer,
+            assets,
+            ui,
+            tiles_panel: tiles_ui.panel,
+            tiles_panel_pos: panel_pos,
+            tiles_panel_size: panel_size,
+            tiles_window_animator_open,
+            tiles_window_animator_close,
+            tiles_ui: tiles_ui.ui,
+            current_ui: UiVariant::Normal,
+            event_hook: None,
+            injected_events: Vec::new(),
+            save_path: PathBuf::from("map.json"),
+            tilesets: Vec::new(),
+            tile_pixels: None,
+            tile_fit: TileFit::default(),
+            directories: AssetDirectories::default(),
+            backups: Backups::default(),
+            restore_index: 0,
+            export_options: ExportOptions::default(),
+            last_export: None,
+            selection: None,
+            selecting: false,
+            painting: false,
+            history: History::default(),
+            show_grid: false,
+            show_rulers: false,
+            split: None,
+            reference: None,
+            scene_caches: Default::default(),
+            lighting: false,
+            ambient_light: AMBIENT_LIGHT,
+            light_caches: Default::default(),
+            view_override: None,
+            last_frame: Instant::now(),
+            fps: FPS,
+            idle_throttle: true,
+            last_activity: Instant::now(),
+            viewer: None,
+            watcher: None,
+            last_prefab: None,
+            notifiers: Vec::new(),
+            paste_snap: false,
+            paste_grid: None
+        };
+
+        this.ensure_current_tile();
+
+        this
+    }
+
+    pub fn run(mut self)
+    {
+        loop
+        {
+            if self.idle_throttle && self.last_activity.elapsed() > IDLE_DELAY
+            {
+                let event = self.window.borrow_mut().events.wait_event_timeout(IDLE_WAIT_MS);
+
+                self.injected_events.extend(event);
+
+                // the wait isnt time the camera should move for
+                self.last_frame = Instant::now();
+            }
+
+            let start = Instant::now();
+
+            if !self.single_frame()
+            {
+                return;
+            }
+
+            if self.window.borrow().vsync
+            {
+                continue;
+            }
+
+            let frame = Duration::from_secs(1) / self.fps;
+
+            // slow frames dont get an extra sleep on top
+            if let Some(left) = frame.checked_sub(start.elapsed())
+            {
+                thread::sleep(left);
+            }
+        }
+    }
+
+    // the hook sees every event (including injected ones) before the editor does
+    pub fn set_event_hook(&mut self, hook: impl FnMut(&Event) -> EventFlow + 'static)
+    {
+        self.event_hook = Some(Box::new(hook));
+    }
+
+    pub fn clear_event_hook(&mut self)
+    {
+        self.event_hook = None;
+    }
+
+    // processed at the start of the next frame
+    pub fn inject_event(&mut self, event: Event)
+    {
+        self.injected_events.push(event);
+    }
+
+    pub fn set_export_options(&mut self, options: ExportOptions)
+    {
+        self.export_options = options;
+    }
+
+    // pastes snap to multiples of this, or of the pasted thing's size if none
+    pub fn set_paste_grid(&mut self, grid: Option<Point2<i32>>)
+    {
+        assert!(grid.map(|grid| grid.x > 0 && grid.y > 0).unwrap_or(true));
+
+        self.paste_grid = grid;
+    }
+
+    // gets told about every save and export, so a running game can reload the map
+    pub fn add_notifier(&mut self, notifier: Notifier)
+    {
+        self.notifiers.push(notifier);
+    }
+
+    fn notify(&self, change: Change)
+    {
+        self.notifiers.iter().for_each(|notifier|
+        {
+            if let Err(err) = notifier.notify(&change)
+            {
+                eprintln!("cant notify {notifier:?}: {err}");
+            }
+        });
+    }
+
+    pub fn set_backups(&mut self, backups: Backups)
+    {
+        self.backups = backups;
+    }
+
+    // shows a binary map file without loading it, nothing can be edited while viewing
+    pub fn view(&mut self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        self.viewer = Some(MappedScene::open(path)?);
+
+        // the viewed map is all there is
+        self.ui.set_visible(&self.scene_tabs, false);
+
+        Ok(())
+    }
+
+    // becomes the save path even if it doesnt exist yet
+    pub fn open(&mut self, path: impl Into<PathBuf>)
+    {
+        self.save_path = path.into();
+        self.restore_index = 0;
+
+        if self.save_path.exists()
+        {
+            let path = self.save_path.clone();
+            self.load_scenes(&path);
+        }
+    }
+
+    pub fn save(&mut self)
+    {
+        let mut project = Project::from_scenes(&self.scenes);
+        project.tilesets = self.tilesets.clone();
+        project.tile_size = self.tile_pixels;
+        project.tile_fit = self.tile_fit;
+        project.directories = self.directories.clone();
+
+        {
+            let assets = self.assets.borrow();
+            project.name_tiles(|tile| assets.tile_name(tile).map(ToOwned::to_owned));
+        }
+
+        match self.backups.save_over(&self.save_path, |path| project.save(path))
+        {
+            Ok(()) =>
+            {
+                self.restore_index = 0;
+
+                println!("saved {}", self.save_path.display());
+                self.toast(format!("saved {}", self.save_path.display()));
+
+                self.notify(Change::Saved(self.save_path.clone()));
+            },
+            Err(err) =>
+            {
+                eprintln!("cant save {}: {err}", self.save_path.display());
+                self.toast(format!("cant save {}: {err}", self.save_path.display()));
+            }
+        }
+    }
+
+    // each call goes 1 backup further back
+    pub fn restore_backup(&mut self)
+    {
+        let backups = match self.backups.list(&self.save_path)
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant list backups: {err}");
+
+                return;
+            }
+        };
+
+        if let Some(path) = backups.get(self.restore_index)
+        {
+            self.load_scenes(path);
+
+            println!("restored backup {}", path.display());
+            self.toast(format!("restored backup {}", path.display()));
+
+            self.restore_index += 1;
+        } else
+        {
+            eprintln!("no more backups of {}", self.save_path.display());
+            self.toast(format!("no more backups of {}", self.save_path.display()));
+        }
+    }
+
+    // with the tile ids fixed up to match the loaded tiles
+    fn load_project(&mut self, path: &Path) -> io::Result<Project>
+    {
+        let mut project = Project::load(path)?;
+
+        let missing = {
+            let assets = self.assets.borrow();
+
+            project.remap_tiles(|name| assets.tile(name))
+        };
+
+        if !missing.is_empty()
+        {
+            let message = format!(
+                "{} has unknown tiles (they got erased): {}",
+                path.display(),
+                missing.join(", ")
+            );
+
+            eprintln!("{message}");
+            self.toast(message);
+        }
+
+        Ok(project)
+    }
+
+    fn load_scenes(&mut self, path: &Path)
+    {
+        match self.load_project(path).and_then(|project|
+        {
+            let tilesets = project.tilesets.clone();
+            let tile_pixels = project.tile_size;
+            let tile_fit = project.tile_fit;
+            let directories = project.directories.clone();
+
+            project.into_scenes().map(|scenes|
+            {
+                (tilesets, tile_pixels, tile_fit, directories, scenes)
+            })
+        })
+        {
+            Ok((tilesets, tile_pixels, tile_fit, directories, scenes)) =>
+            {
+                self.tilesets = tilesets;
+                self.tile_pixels = tile_pixels;
+                self.tile_fit = tile_fit;
+                self.directories = directories;
+                self.scenes = scenes;
+                self.current_scene = 0;
+
+                self.scene_diff = None;
+
+                self.clear_history();
+            },
+            Err(err) =>
+            {
+                eprintln!("cant load {}: {err}", path.display());
+                self.toast(format!("cant load {}: {err}", path.display()));
+            }
+        }
+    }
+
+    // generated from the bindings so it cant go out of date
+    fn help_lines(
+        keybinds: &[(Keybind, ControlName)],
+        shortcuts: &[(Keycode, Shortcut)]
+    ) -> Vec<Vec<String>>
+    {
+        let mut groups: Vec<(&str, Vec<String>)> = Vec::new();
+
+        let mut push_line = |category: &'static str, line: String|
+        {
+            if let Some((_, lines)) = groups.iter_mut().find(|(name, _)| *name == category)
+            {
+                lines.push(line);
+            } else
+            {
+                groups.push((category, vec![line]));
+            }
+        };
+
+        let mut controls: Vec<ControlName> = Vec::new();
+        keybinds.iter().for_each(|(_, control)|
+        {
+            if !controls.contains(control)
+            {
+                controls.push(*control);
+            }
+        });
+
+        controls.into_iter().for_each(|control|
+        {
+            let keys = keybinds.iter().filter(|(_, c)| *c == control)
+                .map(|(key, _)| key.name())
+                .collect::<Vec<_>>()
+                .join(" / ");
+
+            push_line(control.category(), format!("  {keys}: {}", control.description()));
+        });
+
+        shortcuts.iter().for_each(|(key, shortcut)|
+        {
+            push_line(
+                shortcut.category(),
+                format!("  ctrl+{}: {}", key.name(), shortcut.description())
+            );
+        });
+
+        push_line("help", "  F1: toggle this".to_owned());
+
+        groups.into_iter().map(|(name, lines)|
+        {
+            let mut group = vec![name.to_owned()];
+            group.extend(lines);
+
+            group
+        }).collect()
+    }
+
+    fn create_help_ui(
+        renderer: &Rc<RefCell<dyn Renderer>>,
+        assets: &Rc<RefCell<Assets>>,
+        keybinds: &[(Keybind, ControlName)],
+        shortcuts: &[(Keycode, Shortcut)]
+    ) -> Ui
+    {
+        let mut help_ui = Ui::new(renderer.clone(), assets.clone());
+
+        let margin = 0.05;
+        let panel = help_ui.push(UiElement{
+            kind: UiElementType::Panel,
+            pos: Point2::repeat(margin),
+            size: Point2::repeat(1.0 - margin * 2.0),
+            texture: assets.borrow().ui_texture("panel"),
+            color: None,
+            alpha: 255
+        });
+
+        let groups = Self::help_lines(keybinds, shortcuts);
+
+        // whole groups go in the first column until its about half of the lines
+        let total: usize = groups.iter().map(|group| group.len() + 1).sum();
+
+        let mut columns = vec![String::new(), String::new()];
+        let mut lines_so_far = 0;
+        groups.into_iter().for_each(|group|
+        {
+            let column = if lines_so_far * 2 < total { 0 } else { 1 };
+            lines_so_far += group.len() + 1;
+
+            let text = &mut columns[column];
+            if !text.is_empty()
+            {
+                text.push_str("\n\n");
+            }
+
+            text.push_str(&group.join("\n"));
+        });
+
+        let column_margin = 0.03;
+        let column_width = 0.5 - column_margin * 2.0;
+        columns.into_iter().enumerate().for_each(|(index, text)|
+        {
+            help_ui.push_child(&panel, UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align: Align::Left
+                }),
+                pos: Point2::new(column_margin + index as f32 * 0.5, column_margin),
+                size: Point2::new(column_width, 1.0 - column_margin * 2.0),
+                texture: assets.borrow().ui_texture("white"),
+                color: None,
+                alpha: 255
+            });
+        });
+
+        help_ui
+    }
+
+    // a square in the middle of the screen
+    fn tiles_panel_area(aspect: f32) -> (Point2<f32>, Point2<f32>)
+    {
+        let margin = 0.1;
+        let panel_size = 1.0 - margin * 2.0;
+
+        let panel_size = if aspect < 1.0
+        {
+            panel_size
+        } else
+        {
+            panel_size / aspect
+        };
+
+        let panel_size = Point2::new(panel_size, panel_size * aspect);
+        let panel_pos = (-panel_size + 1.0) * 0.5;
+
+        (panel_pos, panel_size)
+    }
+
+    fn tiles_window_animators(
+        panel_pos: Point2<f32>,
+        panel_size: Point2<f32>
+    ) -> (Animator<UiAnimatableId>, Animator<UiAnimatableId>)
+    {
+        let thin_line = panel_size.y * 0.02;
+
+        let x_curve = ValueAnimation::EaseIn(0.7);
+        let y_curve = ValueAnimation::EaseIn(0.9);
+
+        let y_scale_start = 0.2;
+        let x_scale_end = 0.4;
+
+        let open = Animator::new(vec![
+            AnimatedValue{
+                id: UiAnimatableId::ScaleY,
+                range: thin_line..=panel_size.y,
+                curve: y_curve.clone(),
+                duration: y_scale_start..=1.0
+            },
+            AnimatedValue{
+                id: UiAnimatableId::PositionY,
+                range: (panel_size.y / 2.0 + panel_pos.y)..=panel_pos.y,
+                curve: y_curve,
+                duration: y_scale_start..=1.0
+            },
+            AnimatedValue{
+                id: UiAnimatableId::ScaleX,
+                range: 0.0..=panel_size.x,
+                curve: x_curve.clone(),
+                duration: 0.0..=x_scale_end
+            },
+            AnimatedValue{
+                id: UiAnimatableId::PositionX,
+                range: (panel_size.x / 2.0 + panel_pos.x)..=panel_pos.x,
+                curve: x_curve,
+                duration: 0.0..=x_scale_end
+            },
+            AnimatedValue{
+                id: UiAnimatableId::Alpha,
+                range: 0.0..=255.0,
+                curve: ValueAnimation::Linear,
+                duration: 0.0..=1.0
+            }
+        ], Duration::from_millis(200));
+
+        let close = open.reversed();
+
+        (open, close)
+    }
+
+    fn create_tiles_ui(
+        renderer: &Rc<RefCell<dyn Renderer>>,
+        assets: &Rc<RefCell<Assets>>,
+        panel_pos: Point2<f32>,
+        panel_size: Point2<f32>,
+        page: Option<usize>,
+        category: Option<&str>,
+        search: &str
+    ) -> TilesUi
+    {
+        let mut tiles_ui = Ui::new(renderer.clone(), assets.clone());
+
+        let tiles: Vec<Tile> = Self::search_tiles(&assets.borrow(), category, search);
+        let categories = assets.borrow().categories();
+
+        let tiles_panel = tiles_ui.push(UiElement{
+            kind: UiElementType::ScrollPanel{
+                scrollbar: assets.borrow().ui_texture("white")
+            },
+            pos: panel_pos,
+            size: panel_size,
+            texture: assets.borrow().ui_texture("panel"),
+            color: None,
+            alpha: 255
+        });
+
+        // scrolled out tiles would go over the rest of the editor
+        tiles_ui.set_clip_children(&tiles_panel, true);
+
+        // pages always use the smallest size and leave the last row for the page buttons
+        let (items_row, page_tiles) = if let Some(page) = page
+        {
+            (MAX_TILES_ROW, tiles.chunks(TILES_PER_PAGE).nth(page).unwrap_or_default())
+        } else
+        {
+            let items_row = (tiles.len() as f32).sqrt().ceil() as usize;
+
+            (items_row.clamp(1, MAX_TILES_ROW), &tiles[..])
+        };
+
+        let margin = 0.045;
+
+        let container = |kind|
+        {
+            UiElement{
+                kind,
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(1.0, 1.0),
+                texture: assets.borrow().ui_texture("white"),
+                color: None,
+                alpha: 255
+            }
+        };
+
+        // the tabs, search box and tiles go under each other
+        let content = tiles_ui.push_child(&tiles_panel, UiElement{
+            pos: Point2::repeat(margin),
+            size: Point2::repeat(1.0 - margin * 2.0),
+            ..container(UiElementType::Flow{spacing: Length::Relative(0.02)})
+        });
+
+        let row_height = 0.07 / (1.0 - margin * 2.0);
+
+        // theres no point in tabs if everythings in the same place
+        if !categories.is_empty()
+        {
+            let tabs: Vec<Option<String>> = iter::once(None)
+                .chain(categories.into_iter().map(Some))
+                .collect();
+
+            let gap = 0.01;
+            let tabs_amount = tabs.len() as f32;
+
+            // too many tabs go into more rows instead of getting tiny
+            let tab_width = ((1.0 - gap * (tabs_amount - 1.0)) / tabs_amount).max(0.2);
+
+            let tabs_row = tiles_ui.push_child(&content, UiElement{
+                size: Point2::new(1.0, row_height),
+                ..container(UiElementType::Flow{spacing: Length::Relative(gap)})
+            });
+
+            tabs.into_iter().for_each(|tab|
+            {
+                let selected = tab.as_deref() == category;
+
+                let texture = if selected { "white" } else { "background" };
+                let tab_button = tiles_ui.push_child(&tabs_row, UiElement{
+                    kind: UiElementType::Button,
+                    pos: Point2::new(0.0, 0.0),
+                    size: Point2::new(tab_width, 1.0),
+                    texture: assets.borrow().ui_texture(texture),
+                    color: None,
+                    alpha: 255
+                });
+
+                tiles_ui.push_child(&tab_button, UiElement{
+                    kind: UiElementType::Label(Label{
+                        text: tab.clone().unwrap_or_else(|| "all".to_owned()),
+                        font_size: None,
+                        color: if selected { SdlColor::BLACK } else { SdlColor::WHITE },
+                        align: Align::Center
+                    }),
+                    pos: Point2::new(0.1, 0.2),
+                    size: Point2::new(0.8, 0.6),
+                    texture: assets.borrow().ui_texture("white"),
+                    color: None,
+                    alpha: 255
+                });
+
+                tiles_ui.on_click(&tab_button, TilesMessage::Tab(tab));
+            });
+        }
+
+        let search_text = if search.is_empty()
+        {
+            "type to search".to_owned()
+        } else
+        {
+            format!("search: {search}")
+        };
+
+        let label = |text: String, width: f32, align|
+        {
+            UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align
+                }),
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(width, row_height),
+                texture: assets.borrow().ui_texture("white"),
+                color: None,
+                alpha: 255
+            }
+        };
+
+        let search_width = if page.is_some() { 0.65 } else { 1.0 };
+        tiles_ui.push_child(&content, label(search_text, search_width, Align::Left));
+
+        let pages = Self::tile_pages(tiles.len());
+
+        // fits next to the search box
+        if let Some(page) = page
+        {
+            let text = format!("page {}/{pages}", page + 1);
+
+            tiles_ui.push_child(&content, label(text, 0.3, Align::Right));
+        }
+
+        // a tenth of a tile between tiles
+        let spacing = 0.1 / (items_row as f32 + (items_row - 1) as f32 * 0.1);
+
+        let grid = tiles_ui.push_child(&content, container(UiElementType::Grid{
+            columns: items_row,
+            spacing: Length::Relative(spacing)
+        }));
+
+        page_tiles.iter().for_each(|&tile|
+        {
+            let tile_element_id = tiles_ui.push_child(&grid, UiElement{
+                kind: UiElementType::Button,
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(0.0, 0.0),
+                texture: assets.borrow().tile_texture_id(tile),
+                color: None,
+                alpha: 255
+            });
+
+            tiles_ui.on_click(&tile_element_id, TilesMessage::Tile(tile));
+        });
+
+        if page.is_some()
+        {
+            // empty cells keep the page buttons in the last row
+            let mut push_cell = |name, visible|
+            {
+                let cell = tiles_ui.push_child(&grid, UiElement{
+                    kind: UiElementType::Button,
+                    pos: Point2::new(0.0, 0.0),
+                    size: Point2::new(0.0, 0.0),
+                    texture: assets.borrow().ui_texture(name),
+                    color: None,
+                    alpha: 255
+                });
+
+                tiles_ui.set_visible(&cell, visible);
+
+                cell
+            };
+
+            (page_tiles.len()..TILES_PER_PAGE).for_each(|_| { push_cell("white", false); });
+
+            let previous = push_cell("minus", true);
+            (0..MAX_TILES_ROW - 2).for_each(|_| { push_cell("white", false); });
+            let next = push_cell("plus", true);
+
+            tiles_ui.on_click(&previous, TilesMessage::PreviousPage);
+            tiles_ui.on_click(&next, TilesMessage::NextPage);
+
+            // theres nowhere to go
+            if pages <= 1
+            {
+                tiles_ui.set_enabled(&previous, false);
+                tiles_ui.set_enabled(&next, false);
+            }
+        }
+
+        // places everything inside the containers
+        tiles_ui.resize();
+
+        TilesUi{ui: tiles_ui, panel: tiles_panel}
+    }
+
+    // matches the tile name, display name or the name of its image file, ignoring case
+    fn search_tiles(assets: &Assets, category: Option<&str>, search: &str) -> Vec<Tile>
+    {
+        let search = search.to_lowercase();
+
+        assets.tiles().filter(|tile|
+        {
+            if category.is_some() && assets.tile_category(*tile) != category
+            {
+                return false;
+            }
+
+            let name = assets.tile_name(*tile).unwrap_or_default().to_lowercase();
+            let display_name = assets.tile_display_name(*tile).unwrap_or_default().to_lowercase();
+
+            let file_name = assets.tile_source(*tile).path.file_name()
+                .map(|x| x.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+
+            name.contains(&search) || display_name.contains(&search) || file_name.contains(&search)
+        }).collect()
+    }
+
+    fn tile_pages(tiles: usize) -> usize
+    {
+        tiles.div_ceil(TILES_PER_PAGE).max(1)
+    }
+
+    fn search_tiles_input(&mut self, change: impl FnOnce(&mut String))
+    {
+        change(&mut self.tiles_search);
+        self.tiles_scroll = 0.0;
+
+        if self.tiles_paged
+        {
+            self.tiles_pages.insert(self.tiles_category.clone(), 0);
+        }
+
+        self.rebuild_tiles_ui();
+    }
+
+    // every category remembers its own page
+    fn tiles_page(&self) -> Option<usize>
+    {
+        self.tiles_paged.then(|| self.tiles_pages.get(&self.tiles_category).copied().unwrap_or(0))
+    }
+
+    fn picker_tiles_amount(&self) -> usize
+    {
+        let category = self.tiles_category.as_deref();
+
+        Self::search_tiles(&self.assets.borrow(), category, &self.tiles_search).len()
+    }
+
+    // if not every tile is shown at once and it scrolls when they dont fit
+    pub fn set_tile_pages(&mut self, paged: bool)
+    {
+        self.tiles_paged = paged;
+
+        self.rebuild_tiles_ui();
+    }
+
+    pub fn set_fps(&mut self, fps: u32)
+    {
+        self.fps = fps.max(1);
+    }
+
+    // stops redrawing every frame while nothing is happening so it doesnt hog a core
+    pub fn set_idle_throttle(&mut self, throttle: bool)
+    {
+        self.idle_throttle = throttle;
+    }
+
+    // keeps the camera near the tiles of the current scene so it cant get lost in empty space
+    pub fn set_clamp_camera(&mut self, clamp: bool)
+    {
+        self.clamp_camera = clamp;
+    }
+
+    fn toggle_split(&mut self)
+    {
+        self.split = if self.split.is_some()
+        {
+            None
+        } else
+        {
+            // both halves start out looking at the same place
+            let right = self.mouse_pos.x >= (self.window_size.x / 2) as i32;
+
+            Some(SplitView{camera: self.camera, scene: self.current_scene, active_right: right})
+        };
+    }
+
+    // swaps the camera and scene with the other half of the split view
+    fn swap_split(&mut self)
+    {
+        let last_scene = self.scenes.len().saturating_sub(1);
+
+        if let Some(split) = self.split.as_mut()
+        {
+            // scenes might have been deleted since
+            split.scene = split.scene.min(last_scene);
+
+            mem::swap(&mut self.camera, &mut split.camera);
+            mem::swap(&mut self.current_scene, &mut split.scene);
+
+            split.active_right = !split.active_right;
+        }
+    }
+
+    // whichever half the mouse is over gets edited, but not in the middle of a stroke
+    fn update_split_side(&mut self)
+    {
+        let Some(split) = self.split.as_ref()
+        else
+        {
+            return;
+        };
+
+        let right = self.mouse_pos.x >= (self.window_size.x / 2) as i32;
+
+        let busy = self.painting || self.selecting || self.mouse_held;
+        if right != split.active_right && !busy
+        {
+            self.swap_split();
+
+            self.camera_flight = None;
+        }
+    }
+
+    fn save_bookmark(&mut self, index: usize)
+    {
+        let center = self.camera_to_tile(self.camera.pos).map(|x| x.floor() as i32);
+
+        let name = format!("bookmark {} at {} {}", index + 1, center.x, center.y);
+
+        self.toast(format!("saved {name}"));
+
+        self.bookmarks[index] = Some(Bookmark{name, camera: self.camera});
+    }
+
+    fn go_to_bookmark(&mut self, index: usize)
+    {
+        let Some(bookmark) = self.bookmarks[index].as_ref()
+        else
+        {
+            self.toast(format!("bookmark {} is empty", index + 1));
+
+            return;
+        };
+
+        self.fly_to(bookmark.camera);
+    }
+
+    fn fit_scene(&mut self)
+    {
+        let Some((min, max)) = self.scenes[self.current_scene].bounds()
+        else
+        {
+            self.toast("scene is empty");
+
+            return;
+        };
+
+        let size = (max - min).map(|x| (x + 1) as f32);
+        let center = (min.map(|x| x as f32) + (max + 1).map(|x| x as f32)) / 2.0;
+
+        // a tile of space around it
+        let width = size.x * self.tile_aspect() / self.view_aspect();
+        let height = (size.y.max(width) + 2.0).max(1.0);
+
+        self.fly_to(Camera{pos: self.tile_to_camera(center), height});
+    }
+
+    fn pixel_zoom(&mut self, scale: u32)
+    {
+        let Some(tile_size) = self.tile_pixel_size()
+        else
+        {
+            self.toast("no tiles to get the pixel size from");
+
+            return;
+        };
+
+        let tile_pixels = (tile_size.y as u32 * scale).max(1);
+        let height = self.window_size.y as f32 / tile_pixels as f32;
+
+        self.fly_to(Camera{height, ..self.camera});
+    }
+
+    fn fly_to(&mut self, camera: Camera)
+    {
+        let value = |id, from: f32, to: f32|
+        {
+            AnimatedValue{
+                id,
+                range: from..=to,
+                curve: ValueAnimation::EaseOut(3.0),
+                duration: 0.0..=1.0
+            }
+        };
+
+        let (from, to) = (self.camera, camera);
+
+        let mut flight = Animator::new(vec![
+            value(CameraValue::X, from.pos.x, to.pos.x),
+            value(CameraValue::Y, from.pos.y, to.pos.y),
+            value(CameraValue::Height, from.height, to.height)
+        ], Duration::from_millis(600));
+
+        flight.reset();
+
+        self.camera_flight = Some(flight);
+    }
+
+    // shown under the tiles, its top left corner starts at the origin
+    pub fn set_reference(&mut self, path: impl AsRef<Path>) -> Result<(), image::ImageError>
+    {
+        let texture = self.assets.borrow_mut().try_add_texture(path.as_ref())?;
+
+        // starts out with its pixels the same size as the tiles ones
+        let scale = self.tile_pixel_size()
+            .map(|size| size.y as f32)
+            .unwrap_or(16.0);
+
+        if let Some(old) = self.reference.take()
+        {
+            let mut assets = self.assets.borrow_mut();
+
+            assets.release_texture(old.texture);
+            assets.unload_unused();
+        }
+
+        self.reference = Some(Reference{
+            texture,
+            pos: Point2::new(0.0, 0.0),
+            scale,
+            alpha: 128,
+            above: false
+        });
+
+        Ok(())
+    }
+
+    // tiles always take up a whole number of screen pixels so they dont shimmer
+    pub fn set_snap_zoom(&mut self, snap: bool)
+    {
+        self.snap_zoom = snap;
+    }
+
+    // darkens everything except around tiles that give off light
+    pub fn set_lighting(&mut self, enabled: bool)
+    {
+        self.lighting = enabled;
+
+        self.clear_scene_caches();
+    }
+
+    pub fn set_ambient_light(&mut self, ambient: f32)
+    {
+        self.ambient_light = ambient.clamp(0.0, 1.0);
+
+        self.clear_scene_caches();
+    }
+
+    pub fn set_tile_filter(&mut self, filter: Filter)
+    {
+        self.assets.borrow_mut().set_tile_filter(filter);
+
+        self.clear_scene_caches();
+    }
+
+    pub fn set_mipmaps(&mut self, enabled: bool)
+    {
+        self.assets.borrow_mut().set_mipmaps(enabled);
+
+        self.clear_scene_caches();
+    }
+
+    // zooming stays smooth and lands on whole pixels once it stops
+    fn snap_camera_height(&mut self)
+    {
+        let view_height = self.view_area().1.y as f32;
+        let pixels = view_height / self.camera.height;
+
+        // tiles smaller than a pixel cant line up anyway
+        if pixels < 1.0
+        {
+            return;
+        }
+
+        // rounding to the nearest would undo small zoom steps
+        let pixels = if self.camera.height > self.snapped_height
+        {
+            pixels.floor()
+        } else if self.camera.height < self.snapped_height
+        {
+            pixels.ceil()
+        } else
+        {
+            pixels.round()
+        };
+
+        self.camera.height = view_height / pixels.max(1.0);
+        self.snapped_height = self.camera.height;
+    }
+
+    fn clamp_camera_to_scene(&mut self)
+    {
+        if self.viewer.is_some()
+        {
+            return;
+        }
+
+        let (min, max) = self.scenes[self.current_scene].bounds()
+            .unwrap_or((Point2::new(0, 0), Point2::new(0, 0)));
+
+        // the tile in the middle of the screen
+        let center = self.camera_to_tile(self.camera.pos);
+
+        let center = center.zip(min.zip(max)).map(|(x, (min, max))|
+        {
+            x.clamp(min as f32 - CAMERA_MARGIN, (max + 1) as f32 + CAMERA_MARGIN)
+        });
+
+        self.camera.pos = self.tile_to_camera(center);
+    }
+
+    fn turn_tiles_page(&mut self, amount: i32)
+    {
+        if let Some(page) = self.tiles_page()
+        {
+            let pages = Self::tile_pages(self.picker_tiles_amount());
+
+            let page = (page as i32 + amount).rem_euclid(pages as i32) as usize;
+            self.tiles_pages.insert(self.tiles_category.clone(), page);
+
+            self.rebuild_tiles_ui();
+        }
+    }
+
+    fn rebuild_tiles_ui(&mut self)
+    {
+        // the category might be gone if its tiles got deleted
+        if let Some(category) = self.tiles_category.as_ref()
+        {
+            if !self.assets.borrow().categories().contains(category)
+            {
+                self.tiles_category = None;
+            }
+        }
+
+        // the page stays the same unless it doesnt exist anymore
+        if let Some(page) = self.tiles_page()
+        {
+            let last_page = Self::tile_pages(self.picker_tiles_amount()) - 1;
+
+            self.tiles_pages.insert(self.tiles_category.clone(), page.min(last_page));
+        }
+
+        let tiles_ui = Self::create_tiles_ui(
+            &self.renderer,
+            &self.assets,
+            self.tiles_panel_pos,
+            self.tiles_panel_size,
+            self.tiles_page(),
+            self.tiles_category.as_deref(),
+            &self.tiles_search
+        );
+
+        self.tiles_ui = tiles_ui.ui;
+        self.tiles_panel = tiles_ui.panel;
+
+        self.tiles_ui.on_scroll(&self.tiles_panel, TilesMessage::Scrolled);
+        self.tiles_ui.get(&self.tiles_panel).borrow_mut().set_scroll(self.tiles_scroll);
+
+        // deleted tiles cant be picked anymore
+        {
+            let assets = self.assets.borrow();
+            self.recent_tiles.retain(|tile| assets.try_tile_texture_id(*tile).is_some());
+        }
+
+        if self.assets.borrow().try_tile_texture_id(self.current_tile).is_none()
+        {
+            if let Some(tile) = self.assets.borrow().tiles().next()
+            {
+                self.current_tile = tile;
+            }
+        }
+
+        self.ensure_current_tile();
+    }
+
+    pub fn watch(&mut self, tiles_directory: impl Into<PathBuf>, ui_directory: impl Into<PathBuf>)
+    {
+        let tiles_directory = tiles_directory.into();
+
+        self.watcher = Some((
+            DirectoryWatcher::new(vec![tiles_directory.clone(), ui_directory.into()], &["png"]),
+            tiles_directory
+        ));
+    }
+
+    fn check_watcher(&mut self)
+    {
+        let watcher = self.watcher.as_mut();
+        let (changes, tiles_directory) = if let Some((watcher, tiles_directory)) = watcher
+        {
+            (watcher.poll(), tiles_directory.clone())
+        } else
+        {
+            return;
+        };
+
+        let changed = !changes.is_empty();
+
+        let mut rebuild = false;
+        changes.into_iter().for_each(|change|
+        {
+            let is_tile = change.path().starts_with(&tiles_directory);
+
+            let mut assets = self.assets.borrow_mut();
+            match change
+            {
+                FileChange::Changed(path) => assets.reload_texture(&path),
+                FileChange::Added(path) if is_tile =>
+                {
+                    match assets.add_tile_in(&tiles_directory, &path)
+                    {
+                        Ok(_) => rebuild = true,
+                        Err(err) => eprintln!("cant add tile {}: {err}", path.display())
+                    }
+                },
+                FileChange::Added(path) =>
+                {
+                    assets.add_texture(path);
+                },
+                FileChange::Removed(path) if is_tile =>
+                {
+                    rebuild |= assets.remove_tiles_with(&path);
+                },
+                FileChange::Removed(_) => ()
+            }
+        });
+
+        if changed
+        {
+            // removed tiles leave their textures behind
+            let freed = self.assets.borrow_mut().unload_unused();
+            if freed > 0
+            {
+                println!("unloaded {freed} bytes of unused textures");
+            }
+
+            self.assets.borrow_mut().build_atlas();
+
+            self.clear_scene_caches();
+        }
+
+        if rebuild
+        {
+            self.rebuild_tiles_ui();
+        }
+    }
+
+    // returns if textures r still loading, the scenes have spinners in them until theyre done
+    fn check_loading(&mut self) -> bool
+    {
+        let loading = {
+            let mut assets = self.assets.borrow_mut();
+
+            let loaded = assets.poll_loading();
+
+            // the ones that loaded in the background get into the atlas
+            if loaded && !assets.is_loading()
+            {
+                assets.build_atlas();
+            }
+
+            loaded || assets.is_loading()
+        };
+
+        if loading
+        {
+            self.clear_scene_caches();
+        }
+
+        loading
+    }
+
+    fn set_current_tile(&mut self, tile: Tile)
+    {
+        self.current_tile = tile;
+
+        if !self.recent_tiles.contains(&tile)
+        {
+            self.recent_tiles.insert(0, tile);
+            self.recent_tiles.truncate(RECENT_TILES);
+        }
+
+        self.ensure_current_tile();
+    }
+
+    // goes around the recent tiles, wrapping at the ends
+    fn cycle_recent_tile(&mut self, amount: i32)
+    {
+        if self.recent_tiles.is_empty()
+        {
+            return;
+        }
+
+        let current = self.recent_tiles.iter().position(|tile| *tile == self.current_tile)
+            .unwrap_or(0);
+
+        let index = (current as i32 + amount).rem_euclid(self.recent_tiles.len() as i32);
+
+        self.set_current_tile(self.recent_tiles[index as usize]);
+    }
+
+    fn ensure_current_tile(&mut self)
+    {
+        let texture = self.assets.borrow().tile_texture_id(self.current_tile);
+
+        *self.ui.get(&self.current_tile_button).borrow_mut().texture() = texture;
+    }
+
+    fn set_brush_tint(&mut self, tint: [u8; 3])
+    {
+        self.brush_tint = tint;
+
+        let [r, g, b] = tint;
+        *self.ui.get(&self.tint_button).borrow_mut().color() = Some(SdlColor::RGB(r, g, b));
+    }
+
+    fn ensure_current_scene(&mut self)
+    {
+        while self.scenes.len() <= self.current_scene
+        {
+            let size = Point2::new(0, 0);
+            let offset = Point2::new(0, 0);
+
+            self.scenes.push(Scene::new(size, offset));
+        }
+    }
+
+    // fills a new scene with random tiles and prints how long editing and drawing it takes,
+    // the same seed every time so runs can be compared
+    pub fn benchmark(&mut self, size: usize)
+    {
+        // spinners would make drawing look faster than it is
+        if self.assets.borrow().is_loading()
+        {
+            let mut assets = self.assets.borrow_mut();
+
+            assets.finish_loading();
+            assets.build_atlas();
+        }
+
+        let tiles: Vec<Tile> = self.assets.borrow().tiles().collect();
+
+        if tiles.is_empty()
+        {
+            eprintln!("theres no tiles to fill the scene with");
+
+            return;
+        }
+
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        let mut random = move ||
+        {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            state
+        };
+
+        let random_tile = |random: &mut dyn FnMut() -> u64|
+        {
+            tiles[(random() % tiles.len() as u64) as usize]
+        };
+
+        let timed = |name: &str, f: &mut dyn FnMut()|
+        {
+            let start = Instant::now();
+
+            f();
+
+            println!("{name}: {:.2}ms", start.elapsed().as_secs_f64() * 1000.0);
+        };
+
+        let mut scene = Scene::new(Point2::new(0, 0), Point2::new(0, 0));
+
+        timed(&format!("generating {size}x{size}"), &mut ||
+        {
+            for y in 0..size as i32
+            {
+                for x in 0..size as i32
+                {
+                    scene[Point2::new(x, y)] = random_tile(&mut random);
+                }
+            }
+        });
+
+        let edits = 10_000;
+        timed(&format!("painting {edits} random tiles"), &mut ||
+        {
+            for _ in 0..edits
+            {
+                let pos = Point2::new(random(), random()).map(|x| (x % size as u64) as i32);
+
+                scene[pos] = random_tile(&mut random);
+            }
+        });
+
+        timed("undo snapshot", &mut ||
+        {
+            let _ = scene.clone();
+        });
+
+        timed("mirroring", &mut ||
+        {
+            let _ = scene.mirror_x();
+        });
+
+        self.scenes.push(scene);
+        self.current_scene = self.scenes.len() - 1;
+
+        let middle = size as f32 / 2.0;
+        let frames = 60;
+
+        for height in [10.0, 100.0, 500.0, size as f32]
+        {
+            self.camera = Camera{pos: self.tile_to_camera(Point2::repeat(middle)), height};
+
+            // a moving camera keeps leaving the cached area so every frame gets drawn again
+            for moving in [false, true]
+            {
+                let start = Instant::now();
+
+                for i in 0..frames
+                {
+                    if moving
+                    {
+                        let offset = (i % 2) as f32 * height;
+                        self.camera.pos = self.tile_to_camera(Point2::new(middle + offset, middle));
+                    }
+
+                    if !self.single_frame()
+                    {
+                        return;
+                    }
+                }
+
+                let average = start.elapsed().as_secs_f64() * 1000.0 / frames as f64;
+                let state = if moving { "moving" } else { "still" };
+
+                println!("drawing {height} tiles high ({state}): {average:.2}ms per frame");
+            }
+        }
+    }
+
+    pub fn single_frame(&mut self) -> bool
+    {
+        self.profiler.begin_frame();
+
+        let mut events = mem::take(&mut self.injected_events);
+        events.extend(self.window.borrow_mut().events.poll_iter());
+
+        let mut active = !events.is_empty();
+
+        for event in events
+        {
+            if let Some(hook) = self.event_hook.as_mut()
+            {
+                if let EventFlow::Consume = hook(&event)
+                {
+                    continue;
+                }
+            }
+
+            if !self.on_event(event)
+            {
+                return false;
+            }
+        }
+
+        self.tiles_ui.take_messages().into_iter().for_each(|message|
+        {
+            self.on_tiles_message(message);
+        });
+
+        self.ensure_current_scene();
+
+        self.check_watcher();
+        self.check_task();
+        active |= self.check_loading();
+
+        self.profiler.end_phase(Phase::Events);
+
+        // in milliseconds, a long hitch shouldnt throw the camera across the map
+        let dt = {
+            let now = Instant::now();
+            let dt = now.duration_since(self.last_frame).as_secs_f32() * 1000.0;
+
+            self.last_frame = now;
+
+            dt.min(100.0)
+        };
+
+        let speed = 0.002 * self.camera.height.sqrt() * dt;
+
+        let moving = [
+            ControlName::Forward,
+            ControlName::Back,
+            ControlName::Right,
+            ControlName::Left,
+            ControlName::ZoomOut,
+            ControlName::ZoomIn
+        ].into_iter().any(|control| self.pressed(control));
+
+        if moving
+        {
+            self.camera_flight = None;
+        }
+
+        active |= self.controls.iter().any(|pressed| *pressed)
+            || self.camera_flight.is_some()
+            || self.task.is_some()
+            || !self.toasts.is_empty();
+
+        if let Some(flight) = self.camera_flight.as_mut()
+        {
+            if let AnimationState::Over = flight.animate(&mut self.camera)
+            {
+                self.camera_flight = None;
+            }
+        }
+
+        if self.pressed(ControlName::Forward)
+        {
+            self.camera.pos.y += speed;
+        } else if self.pressed(ControlName::Back)
+        {
+            self.camera.pos.y -= speed;
+        }
+
+        if self.pressed(ControlName::Right)
+        {
+            self.camera.pos.x += speed;
+        } else if self.pressed(ControlName::Left)
+        {
+            self.camera.pos.x -= speed;
+        }
+
+        if self.clamp_camera
+        {
+            self.clamp_camera_to_scene();
+        }
+
+        let zoom_scale = 0.9_f32.powf(0.05 * dt);
+
+        if self.pressed(ControlName::ZoomOut)
+        {
+            self.camera.height /= zoom_scale;
+        } else if self.pressed(ControlName::ZoomIn)
+        {
+            self.camera.height *= zoom_scale;
+        }
+
+        let zooming = self.pressed(ControlName::ZoomOut) || self.pressed(ControlName::ZoomIn);
+        if self.snap_zoom && !zooming && self.camera_flight.is_none()
+        {
+            self.snap_camera_height();
+        }
+
+        {
+            let editable = self.viewer.is_none();
+
+            // holding select or removing works the same with any tool
+            let tool = if editable && self.pressed(ControlName::CreateTile)
+            {
+                Some(if self.pressed(ControlName::Select) { Tool::Select } else { self.tool })
+            } else if editable && self.pressed(ControlName::DeleteTile)
+            {
+                Some(Tool::Erase)
+            } else
+            {
+                None
+            };
+
+            let tile_pos = self.screen_to_pos(self.mouse_pos);
+
+            if let Some(Tool::Paint | Tool::Erase | Tool::Fill) = tool
+            {
+                // a whole stroke gets undone at once
+                if !self.painting
+                {
+                    self.remember_scene();
+                }
+            }
+
+            let scene = &mut self.scenes[self.current_scene];
+            match tool
+            {
+                // the connected tiles stay selected until its let go
+                Some(Tool::Select) if self.mouse_press.is_some_and(|press| press.is_double()) => (),
+                Some(Tool::Select) =>
+                {
+                    match self.selection.as_mut()
+                    {
+                        Some(selection) if self.selecting => selection.end = tile_pos,
+                        _ => self.selection = Some(Selection{start: tile_pos, end: tile_pos})
+                    }
+                },
+                Some(Tool::Paint) =>
+                {
+                    scene[tile_pos] = self.current_tile;
+                    scene.set_tint(tile_pos, self.brush_tint);
+                },
+                Some(Tool::Erase) =>
+                {
+                    scene[tile_pos] = Tile::none();
+                    scene.set_tint(tile_pos, NO_TINT);
+                },
+                // only once per click
+                Some(Tool::Fill) if !self.painting =>
+                {
+                    scene.fill_tinted(tile_pos, self.current_tile, self.brush_tint);
+                },
+                Some(Tool::Fill) | None => ()
+            }
+
+            self.selecting = tool == Some(Tool::Select);
+            self.painting = matches!(tool, Some(Tool::Paint | Tool::Erase | Tool::Fill));
+        }
+
+        self.profiler.begin_phase();
+
+        {
+            let mut renderer = self.renderer.borrow_mut();
+
+            renderer.set_color(SdlColor::RGB(0, 0, 0));
+            renderer.clear();
+        }
+
+        let mouse = self.screen_to_local(self.mouse_pos);
+
+        // same rules as clicking on the scene
+        let hovering_scene = self.viewer.is_none()
+            && matches!(self.current_ui, UiVariant::Normal)
+            && self.context_menu.is_none()
+            && self.keybinds_ui.is_none()
+            && self.properties_dialog.is_none()
+            && self.new_scene_dialog.is_none()
+            && self.confirm_dialog.is_none()
+            && self.ui.click(mouse).is_none();
+
+        self.update_scene_diff();
+
+        // the other camera gets drawn with its own scene in its own half
+        if self.split.is_some()
+        {
+            self.swap_split();
+            self.update_scene_cache();
+            self.update_light_cache();
+            active |= self.draw_view(None, None);
+            self.swap_split();
+
+            let half = (self.window_size.x / 2) as i32;
+
+            let mut renderer = self.renderer.borrow_mut();
+
+            renderer.set_color(SdlColor::RGB(255, 255, 255));
+            renderer.draw_line(Point2::new(half, 0), Point2::new(half, self.window_size.y as i32));
+        }
+
+        let hover = hovering_scene.then(|| self.screen_to_pos(self.mouse_pos));
+
+        self.update_scene_cache();
+        self.update_light_cache();
+        active |= self.draw_view(hover, self.selection);
+
+        self.profiler.end_phase(Phase::Scene);
+        self.profiler.begin_phase();
+
+        self.ui.set_mouse(mouse, self.mouse_held);
+        self.tiles_ui.set_mouse(mouse, self.mouse_held);
+
+        self.update_status_label();
+        self.update_window_title();
+        self.update_scene_tabs();
+
+        self.ui.draw();
+
+        // stays on screen while its closing
+        let draw_tiles_ui = match self.current_ui
+        {
+            UiVariant::Tiles => true,
+            UiVariant::Normal => self.tiles_ui.is_animating(&self.tiles_panel)
+        };
+
+        if draw_tiles_ui
+        {
+            self.tiles_ui.draw();
+        }
+
+        self.update_toasts();
+        self.toasts_ui.draw();
+
+        if self.show_help
+        {
+            self.help_ui.draw();
+        }
+
+        if let Some(keybinds_ui) = self.keybinds_ui.as_mut()
+        {
+            keybinds_ui.set_mouse(mouse, self.mouse_held);
+            keybinds_ui.draw();
+        }
+
+        if let Some(dialog) = self.properties_dialog.as_mut()
+        {
+            dialog.ui.set_mouse(mouse, self.mouse_held);
+            dialog.ui.draw();
+        }
+
+        if let Some(dialog) = self.new_scene_dialog.as_mut()
+        {
+            dialog.ui.set_mouse(mouse, self.mouse_held);
+            dialog.ui.draw();
+        }
+
+        // transient stuff goes above everything else
+        if let Some(menu) = self.context_menu.as_mut()
+        {
+            menu.ui.set_mouse(mouse, self.mouse_held);
+            menu.ui.draw();
+        }
+
+        if let Some(running) = self.task.as_mut()
+        {
+            if let Some(progress) = running.ui.get(&running.bar).borrow_mut().progress()
+            {
+                *progress = running.task.progress();
+            }
+
+            running.ui.draw();
+        }
+
+        if let Some(dialog) = self.confirm_dialog.as_mut()
+        {
+            dialog.ui.set_mouse(mouse, self.mouse_held);
+            dialog.ui.draw();
+        }
+
+        active |= self.ui.is_animating_any()
+            || self.tiles_ui.is_animating_any()
+            || self.toasts_ui.is_animating_any();
+
+        if active
+        {
+            self.last_activity = Instant::now();
+        }
+
+        self.profiler.end_phase(Phase::Ui);
+
+        {
+            let mut renderer = self.renderer.borrow_mut();
+
+            let draw_calls = renderer.take_draw_calls();
+            self.profiler.end_frame(draw_calls);
+
+            if self.show_profiler
+            {
+                let pos = Point2::new(10, self.window_size.y as i32 - 160);
+                let budget = Duration::from_secs(1) / self.fps.max(1);
+
+                self.profiler.draw(&mut *renderer, &self.assets.borrow(), pos, budget);
+
+                // the overlay itself shouldnt show up in the next frame
+                renderer.take_draw_calls();
+            }
+
+            renderer.present();
+        }
+
+        true
+    }
+
+    fn confirm(
+        &mut self,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        on_yes: impl FnOnce(&mut Self) + 'static
+    )
+    {
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        let label = |text: String, (x, y): (f32, f32), (width, height): (f32, f32)|
+        {
+            UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align: Align::Center
+                }),
+                pos: Point2::new(x, y),
+                size: Point2::new(width, height),
+                texture: texture("white"),
+                color: None,
+                alpha: 255
+            }
+        };
+
+        let size = Point2::new(Length::Absolute(360.0), Length::Absolute(150.0));
+        let panel = ui.push_anchored(
+            UiElement{
+                kind: UiElementType::Panel,
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(0.0, 0.0),
+                texture: texture("panel"),
+                color: None,
+                alpha: 255
+            },
+            Layout::new(Anchor::Center, size)
+        );
+
+        ui.push_child(&panel, label(title.into(), (0.1, 0.72), (0.8, 0.16)));
+        ui.push_child(&panel, label(message.into(), (0.05, 0.45), (0.9, 0.14)));
+
+        [("yes", 0.1, true), ("no", 0.55, false)].into_iter().for_each(|(text, x, answer)|
+        {
+            let button = ui.push_child(&panel, UiElement{
+                kind: UiElementType::Button,
+                pos: Point2::new(x, 0.1),
+                size: Point2::new(0.35, 0.25),
+                texture: texture("background"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.push_child(&button, label(text.to_owned(), (0.2, 0.2), (0.6, 0.6)));
+
+            ui.on_click(&button, answer);
+        });
+
+        // enter right away shouldnt throw anything away
+        ui.move_focus(false);
+
+        self.context_menu = None;
+        self.confirm_dialog = Some(ConfirmDialog{ui, on_yes: Box::new(on_yes)});
+    }
+
+    fn answer_confirm(&mut self, answer: bool)
+    {
+        if let Some(dialog) = self.confirm_dialog.take()
+        {
+            if answer
+            {
+                (dialog.on_yes)(self);
+            }
+        }
+    }
+
+    // every keybind gets a button, the one being rebound asks for a key instead
+    fn create_keybinds_ui(&self) -> Ui<usize>
+    {
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        let margin = 0.1;
+        let panel = ui.push(UiElement{
+            kind: UiElementType::ScrollPanel{scrollbar: texture("white")},
+            pos: Point2::repeat(margin),
+            size: Point2::repeat(1.0 - margin * 2.0),
+            texture: texture("panel"),
+            color: None,
+            alpha: 255
+        });
+
+        ui.set_clip_children(&panel, true);
+
+        let row_height = 0.08;
+        let row_margin = 0.03;
+
+        ui.push_child(&panel, UiElement{
+            kind: UiElementType::Label(Label{
+                text: "click a keybind to change it, escape to close".to_owned(),
+                font_size: None,
+                color: SdlColor::WHITE,
+                align: Align::Center
+            }),
+            pos: Point2::new(row_margin, 1.0 - row_margin - row_height),
+            size: Point2::new(1.0 - row_margin * 2.0, row_height * 0.6),
+            texture: texture("white"),
+            color: None,
+            alpha: 255
+        });
+
+        self.keybinds.iter().enumerate().for_each(|(index, (keybind, control))|
+        {
+            let text = if self.rebinding == Some(index)
+            {
+                format!("{}: press a key", control.description())
+            } else
+            {
+                format!("{}: {}", control.description(), keybind.name())
+            };
+
+            let row = (index + 2) as f32;
+            let button = ui.push_child(&panel, UiElement{
+                kind: UiElementType::Button,
+                pos: Point2::new(row_margin, 1.0 - row_margin - row_height * row),
+                size: Point2::new(1.0 - row_margin * 2.0, row_height * 0.9),
+                texture: texture("background"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.push_child(&button, UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align: Align::Left
+                }),
+                pos: Point2::new(0.03, 0.2),
+                size: Point2::new(0.94, 0.6),
+                texture: texture("white"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.on_click(&button, index);
+        });
+
+        ui
+    }
+
+    fn create_properties_ui(&self, scene: usize, input: &str) -> Ui<usize>
+    {
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        let label = |text: String, pos: Point2<f32>, size: Point2<f32>, align|
+        {
+            UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align
+                }),
+                pos,
+                size,
+                texture: texture("white"),
+                color: None,
+                alpha: 255
+            }
+        };
+
+        let margin = 0.1;
+        let panel = ui.push(UiElement{
+            kind: UiElementType::ScrollPanel{scrollbar: texture("white")},
+            pos: Point2::repeat(margin),
+            size: Point2::repeat(1.0 - margin * 2.0),
+            texture: texture("panel"),
+            color: None,
+            alpha: 255
+        });
+
+        ui.set_clip_children(&panel, true);
+
+        let row_height = 0.08;
+        let row_margin = 0.03;
+        let row_pos = |row: usize| Point2::new(
+            row_margin,
+            1.0 - row_margin - row_height * (row + 1) as f32
+        );
+
+        let row_size = Point2::new(1.0 - row_margin * 2.0, row_height * 0.9);
+
+        ui.push_child(&panel, label(
+            format!("{} properties, type key = value and press enter", self.scene_name(scene)),
+            row_pos(0),
+            Point2::new(row_size.x, row_height * 0.6),
+            Align::Center
+        ));
+
+        ui.push_child(&panel, label(
+            "an empty value removes it, escape to close".to_owned(),
+            row_pos(1) + Point2::new(0.0, row_height * 0.3),
+            Point2::new(row_size.x, row_height * 0.6),
+            Align::Center
+        ));
+
+        let line = ui.push_child(&panel, UiElement{
+            kind: UiElementType::Panel,
+            pos: row_pos(2),
+            size: row_size,
+            texture: texture("background"),
+            color: None,
+            alpha: 255
+        });
+
+        let row_label = |text: String|
+        {
+            label(text, Point2::new(0.03, 0.2), Point2::new(0.94, 0.6), Align::Left)
+        };
+
+        ui.push_child(&line, row_label(format!("{input}_")));
+
+        let properties = self.scenes.get(scene).into_iter().flat_map(|scene| scene.properties());
+        properties.enumerate().for_each(|(index, (key, value))|
+        {
+            let button = ui.push_child(&panel, UiElement{
+                kind: UiElementType::Button,
+                pos: row_pos(index + 3),
+                size: row_size,
+                texture: texture("background"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.push_child(&button, row_label(format!("{key} = {value}")));
+
+            ui.on_click(&button, index);
+        });
+
+        ui
+    }
+
+    fn create_new_scene_ui(&self, templates: &[PathBuf]) -> Ui<usize>
+    {
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        let label = |text: String, pos: Point2<f32>, size: Point2<f32>|
+        {
+            UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align: Align::Center
+                }),
+                pos,
+                size,
+                texture: texture("white"),
+                color: None,
+                alpha: 255
+            }
+        };
+
+        let margin = 0.2;
+        let panel = ui.push(UiElement{
+            kind: UiElementType::ScrollPanel{scrollbar: texture("white")},
+            pos: Point2::repeat(margin),
+            size: Point2::repeat(1.0 - margin * 2.0),
+            texture: texture("panel"),
+            color: None,
+            alpha: 255
+        });
+
+        ui.set_clip_children(&panel, true);
+
+        let row_height = 0.1;
+        let row_margin = 0.04;
+        let row_pos = |row: usize| Point2::new(
+            row_margin,
+            1.0 - row_margin - row_height * (row + 1) as f32
+        );
+
+        let row_size = Point2::new(1.0 - row_margin * 2.0, row_height * 0.9);
+
+        ui.push_child(&panel, label(
+            "new scene from, escape cancels".to_owned(),
+            row_pos(0),
+            Point2::new(row_size.x, row_height * 0.6)
+        ));
+
+        let names = iter::once("empty".to_owned()).chain(templates.iter().map(|path|
+        {
+            path.file_stem().map(|x| x.to_string_lossy().into_owned()).unwrap_or_default()
+        }));
+
+        names.enumerate().for_each(|(index, name)|
+        {
+            let button = ui.push_child(&panel, UiElement{
+                kind: UiElementType::Button,
+                pos: row_pos(index + 1),
+                size: row_size,
+                texture: texture("background"),
+                color: None,
+                alpha: 255
+            });
+
+            ui.push_child(&button, label(name, Point2::new(0.03, 0.2), Point2::new(0.94, 0.6)));
+
+            ui.on_click(&button, index);
+        });
+
+        ui
+    }
+
+    fn new_scene(&mut self, template: Option<&Path>)
+    {
+        let Some(path) = template
+        else
+        {
+            self.current_scene = self.scenes.len();
+            self.ensure_current_scene();
+
+            return;
+        };
+
+        let template = match Template::load(path)
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant load template {}: {err}", path.display());
+                self.toast(format!("cant load template {}: {err}", path.display()));
+
+                return;
+            }
+        };
+
+        let (scene, missing) = {
+            let assets = self.assets.borrow();
+
+            template.to_scene(|name| assets.tile(name))
+        };
+
+        if !missing.is_empty()
+        {
+            eprintln!("template has unknown tiles: {}", missing.join(", "));
+            self.toast(format!("template has unknown tiles: {}", missing.join(", ")));
+        }
+
+        self.scenes.push(scene);
+        self.current_scene = self.scenes.len() - 1;
+    }
+
+    fn save_template(&mut self, index: usize)
+    {
+        let Some(scene) = self.scenes.get(index)
+        else
+        {
+            return;
+        };
+
+        let template = {
+            let assets = self.assets.borrow();
+
+            Template::from_scene(scene, |tile| assets.tile_name(tile).map(|x| x.to_owned()))
+        };
+
+        // scene names can have anything in them
+        let name: String = self.scene_name(index).chars().map(|c|
+        {
+            if c.is_alphanumeric() || c == '-' { c } else { '_' }
+        }).collect();
+
+        let directory = PathBuf::from(TEMPLATES_DIRECTORY);
+        let path = iter::once(directory.join(format!("{name}.json")))
+            .chain((1..).map(|index| directory.join(format!("{name}{index}.json"))))
+            .find(|path| !path.exists())
+            .unwrap();
+
+        match fs::create_dir_all(&directory).and_then(|_| template.save(&path))
+        {
+            Ok(()) =>
+            {
+                println!("saved template {}", path.display());
+                self.toast(format!("saved template {}", path.display()));
+            },
+            Err(err) =>
+            {
+                eprintln!("cant save template: {err}");
+                self.toast(format!("cant save template: {err}"));
+            }
+        }
+    }
+
+    fn open_scene_properties(&mut self, scene: usize)
+    {
+        if scene >= self.scenes.len()
+        {
+            return;
+        }
+
+        let ui = self.create_properties_ui(scene, "");
+
+        self.properties_dialog = Some(PropertiesDialog{ui, scene, input: String::new()});
+    }
+
+    fn refresh_properties_ui(&mut self)
+    {
+        if let Some(dialog) = self.properties_dialog.as_ref()
+        {
+            let ui = self.create_properties_ui(dialog.scene, &dialog.input);
+
+            if let Some(dialog) = self.properties_dialog.as_mut()
+            {
+                dialog.ui = ui;
+            }
+        }
+    }
+
+    // returns if the key went to the dialog, which is every key while its open
+    fn properties_input(&mut self, key: Keycode) -> bool
+    {
+        let Some(dialog) = self.properties_dialog.as_mut()
+        else
+        {
+            return false;
+        };
+
+        match key
+        {
+            Keycode::Escape => self.properties_dialog = None,
+            Keycode::Backspace =>
+            {
+                dialog.input.pop();
+
+                self.refresh_properties_ui();
+            },
+            Keycode::Return | Keycode::KpEnter =>
+            {
+                let property = dialog.input.split_once('=')
+                    .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+                    .filter(|(key, _)| !key.is_empty());
+
+                if let Some((key, value)) = property
+                {
+                    dialog.input.clear();
+
+                    self.scenes[dialog.scene].set_property(key, value);
+                } else
+                {
+                    self.toast("properties r typed like key = value");
+                }
+
+                self.refresh_properties_ui();
+            },
+            _ => ()
+        }
+
+        true
+    }
+
+    fn rebind(&mut self, index: usize, keybind: Keybind)
+    {
+        self.keybinds[index].0 = keybind;
+        self.rebinding = None;
+
+        // whatever was held might not be bound to the same thing anymore
+        self.controls = [false; ControlName::LAST as usize];
+
+        self.help_ui = Self::create_help_ui(
+            &self.renderer,
+            &self.assets,
+            &self.keybinds,
+            &self.shortcuts
+        );
+
+        self.keybinds_ui = Some(self.create_keybinds_ui());
+
+        self.save_keybinds();
+    }
+
+    // the defaults stay if the file doesnt exist, changes get saved to it
+    pub fn load_keybinds(&mut self, path: impl Into<PathBuf>)
+    {
+        let path = path.into();
+
+        if path.exists()
+        {
+            let config = fs::read_to_string(&path).map_err(|err| err.to_string())
+                .and_then(|text|
+                {
+                    toml::from_str::<KeybindsConfig>(&text).map_err(|err| err.to_string())
+                });
+
+            match config
+            {
+                Ok(config) =>
+                {
+                    let keybinds: Vec<_> = config.keybinds.iter().filter_map(|entry|
+                    {
+                        let keybind = Keybind::from_config(&entry.key);
+                        let control = ControlName::from_id(&entry.control);
+
+                        if keybind.is_none() || control.is_none()
+                        {
+                            eprintln!("unknown keybind {} for {}", entry.key, entry.control);
+                        }
+
+                        keybind.zip(control)
+                    }).collect();
+
+                    self.keybinds = keybinds;
+
+                    config.shortcuts.iter().for_each(|entry|
+                    {
+                        let key = Keycode::from_name(&entry.key);
+                        let shortcut = self.shortcuts.iter_mut().find(|(_, shortcut)|
+                        {
+                            shortcut.id() == entry.shortcut
+                        });
+
+                        match (key, shortcut)
+                        {
+                            (Some(key), Some((old_key, _))) => *old_key = key,
+                            _ => eprintln!("unknown shortcut {} for {}", entry.key, entry.shortcut)
+                        }
+                    });
+
+                    self.help_ui = Self::create_help_ui(
+                        &self.renderer,
+                        &self.assets,
+                        &self.keybinds,
+                        &self.shortcuts
+                    );
+                },
+                Err(err) => eprintln!("cant load keybinds from {}: {err}", path.display())
+            }
+        }
+
+        self.keybinds_path = Some(path);
+    }
+
+    fn save_keybinds(&self)
+    {
+        let Some(path) = self.keybinds_path.as_ref()
+        else
+        {
+            return;
+        };
+
+        let config = KeybindsConfig{
+            keybinds: self.keybinds.iter().map(|(keybind, control)|
+            {
+                KeybindEntry{control: control.id().to_owned(), key: keybind.to_config()}
+            }).collect(),
+            shortcuts: self.shortcuts.iter().map(|(key, shortcut)|
+            {
+                ShortcutEntry{shortcut: shortcut.id(), key: key.name()}
+            }).collect()
+        };
+
+        let result = toml::to_string(&config).map_err(|err| err.to_string())
+            .and_then(|text| fs::write(path, text).map_err(|err| err.to_string()));
+
+        if let Err(err) = result
+        {
+            eprintln!("cant save keybinds to {}: {err}", path.display());
+        }
+    }
+
+    // held controls stay on until theyre let go, actions happen once when pressed
+    fn set_control(&mut self, control: Keybind, state: bool)
+    {
+        let Some(&(_, control)) = self.keybinds.iter().find(|(k, _)| *k == control)
+        else
+        {
+            return;
+        };
+
+        if control.is_action()
+        {
+            if state
+            {
+                self.on_action(control);
+            }
+
+            return;
+        }
+
+        self.controls[control as usize] = state;
+    }
+
+    // ctrl zooms by default, once its used for a shortcut or a click it shouldnt keep doing that
+    fn release_ctrl_controls(&mut self)
+    {
+        self.set_control(Keybind::Keyboard(Keycode::LCtrl), false);
+        self.set_control(Keybind::Keyboard(Keycode::RCtrl), false);
+    }
+
+    fn on_action(&mut self, action: ControlName)
+    {
+        if self.viewer.is_some()
+        {
+            return;
+        }
+
+        let scenes = self.scenes.len().max(1);
+
+        match action
+        {
+            ControlName::NextScene =>
+            {
+                self.on_ui_message(UiMessage::Scene((self.current_scene + 1) % scenes));
+            },
+            ControlName::PreviousScene =>
+            {
+                self.on_ui_message(UiMessage::Scene((self.current_scene + scenes - 1) % scenes));
+            },
+            ControlName::Undo => self.run_shortcut(Shortcut::Undo),
+            ControlName::Redo => self.run_shortcut(Shortcut::Redo),
+            ControlName::PaintTool => self.on_ui_message(UiMessage::Tool(Tool::Paint)),
+            ControlName::EraseTool => self.on_ui_message(UiMessage::Tool(Tool::Erase)),
+            ControlName::FillTool => self.on_ui_message(UiMessage::Tool(Tool::Fill)),
+            ControlName::SelectTool => self.on_ui_message(UiMessage::Tool(Tool::Select)),
+            _ => ()
+        }
+    }
+
+    fn export(&mut self, kind: ExportFormat)
+    {
+        let name = format!("scene{}", self.current_scene);
+
+        let path = PathBuf::from("export").join(format!("{name}.{}", kind.extension()));
+
+        let settings = ExportSettings{
+            kind,
+            scene: self.current_scene,
+            path,
+            options: self.export_options.clone()
+        };
+
+        // quick export doesnt ask, its meant for doing the same export again
+        if settings.path.exists()
+        {
+            let message = format!("{} already exists", settings.path.display());
+
+            self.confirm("overwrite?", message, |game| game.run_export(settings));
+        } else
+        {
+            self.run_export(settings);
+        }
+    }
+
+    fn quick_export(&mut self)
+    {
+        if let Some(settings) = self.last_export.clone()
+        {
+            self.run_export(settings);
+        } else
+        {
+            eprintln!("nothing exported yet");
+            self.toast("nothing exported yet");
+        }
+    }
+
+    // the export itself happens on another thread, big maps can take a while
+    fn run_export(&mut self, settings: ExportSettings)
+    {
+        if self.task.is_some()
+        {
+            self.toast("wait for the current task to finish");
+
+            return;
+        }
+
+        let scene = if let Some(x) = self.scenes.get(settings.scene)
+        {
+            x
+        } else
+        {
+            eprintln!("scene {} doesnt exist anymore", settings.scene);
+            self.toast(format!("scene {} doesnt exist anymore", settings.scene));
+
+            return;
+        };
+
+        if let Some(parent) = settings.path.parent()
+        {
+            if let Err(err) = fs::create_dir_all(parent)
+            {
+                eprintln!("cant create export directory: {err}");
+                self.toast(format!("export failed: {err}"));
+
+                return;
+            }
+        }
+
+        let scene = settings.options.apply(scene).into_owned();
+
+        let name = settings.path.file_stem().map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        // assets cant go to another thread so everything the export needs gets looked up now
+        let (sources, names) = {
+            let assets = self.assets.borrow();
+
+            let tiles: HashSet<Tile> = scene.iter().map(|(_, tile)| *tile)
+                .filter(|tile| !tile.is_none())
+                .collect();
+
+            let sources: HashMap<_, _> = tiles.iter()
+                .map(|tile| (*tile, assets.tile_source(*tile)))
+                .collect();
+
+            let names: HashMap<_, _> = tiles.iter()
+                .map(|tile| (*tile, assets.tile_name(*tile).map(|x| x.to_owned())))
+                .collect();
+
+            (sources, names)
+        };
+
+        let tile_pixels = self.tile_pixels;
+
+        let task_name = format!("exporting {}", settings.path.display());
+        self.start_task(task_name, move |progress|
+        {
+            let bytes = settings.kind.to_bytes(
+                &name,
+                &scene,
+                tile_pixels,
+                |tile| sources[&tile].clone(),
+                |tile| names.get(&tile).cloned().flatten()
+            );
+
+            // making the bytes counts as the first half
+            progress.set(0.5);
+
+            let result = write_with_progress(&settings.path, &bytes, |fraction|
+            {
+                progress.set(0.5 + fraction * 0.5);
+            });
+
+            Box::new(move |game: &mut Game|
+            {
+                match result
+                {
+                    Ok(()) =>
+                    {
+                        println!("exported to {}", settings.path.display());
+                        game.toast(format!("exported to {}", settings.path.display()));
+
+                        game.notify(Change::Exported(settings.kind, settings.path.clone()));
+
+                        game.last_export = Some(settings);
+                    },
+                    Err(err) =>
+                    {
+                        eprintln!("export failed: {err}");
+                        game.toast(format!("export failed: {err}"));
+                    }
+                }
+            })
+        });
+    }
+
+    fn start_task(
+        &mut self,
+        name: String,
+        f: impl FnOnce(&Progress) -> TaskDone + Send + 'static
+    )
+    {
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        let panel = ui.push_anchored(
+            UiElement{
+                kind: UiElementType::Panel,
+                pos: Point2::new(0.0, 0.0),
+                size: Point2::new(0.0, 0.0),
+                texture: texture("panel"),
+                color: None,
+                alpha: 255
+            },
+            Layout::new(
+                Anchor::Center,
+                Point2::new(Length::Absolute(360.0), Length::Absolute(90.0))
+            )
+        );
+
+        ui.push_child(&panel, UiElement{
+            kind: UiElementType::Label(Label{
+                text: name.clone(),
+                font_size: None,
+                color: SdlColor::WHITE,
+                align: Align::Center
+            }),
+            pos: Point2::new(0.05, 0.6),
+            size: Point2::new(0.9, 0.25),
+            texture: texture("white"),
+            color: None,
+            alpha: 255
+        });
+
+        let bar = ui.push_child(&panel, UiElement{
+            kind: UiElementType::ProgressBar{fill: texture("white"), progress: 0.0},
+            pos: Point2::new(0.05, 0.15),
+            size: Point2::new(0.9, 0.3),
+            texture: texture("background"),
+            color: None,
+            alpha: 255
+        });
+
+        self.task = Some(RunningTask{task: Task::spawn(name, f), ui, bar});
+    }
+
+    fn check_task(&mut self)
+    {
+        let done = self.task.as_mut().and_then(|running| running.task.poll());
+
+        match done
+        {
+            Some(Ok(done)) =>
+            {
+                self.task = None;
+
+                done(self);
+            },
+            Some(Err(err)) =>
+            {
+                self.task = None;
+
+                eprintln!("{err}");
+                self.toast(err.to_string());
+            },
+            None => ()
+        }
+    }
+
+    fn selection_prefab(&self) -> Option<Prefab>
+    {
+        let selection = if let Some(x) = self.selection
+        {
+            x
+        } else
+        {
+            eprintln!("nothing selected");
+
+            return None;
+        };
+
+        let (min, max) = selection.bounds();
+
+        let assets = self.assets.borrow();
+
+        Some(Prefab::from_region(
+            &self.scenes[self.current_scene],
+            min,
+            max,
+            |tile| assets.tile_name(tile).map(|x| x.to_owned())
+        ))
+    }
+
+    fn export_prefab(&mut self)
+    {
+        let prefab = if let Some(x) = self.selection_prefab() { x } else { return };
+
+        let directory = PathBuf::from("prefabs");
+        let path = (0..).map(|index| directory.join(format!("prefab{index}.json")))
+            .find(|path| !path.exists())
+            .unwrap();
+
+        match fs::create_dir_all(&directory).and_then(|_| prefab.save(&path))
+        {
+            Ok(()) =>
+            {
+                println!("exported prefab to {}", path.display());
+
+                self.last_prefab = Some(path);
+            },
+            Err(err) => eprintln!("cant export prefab: {err}")
+        }
+    }
+
+    fn open_context_menu(&mut self, screen_pos: Point2<i32>)
+    {
+        let mut actions = vec![MenuAction::Fill, MenuAction::PickTile, MenuAction::ClearRegion];
+
+        if self.reference.is_some()
+        {
+            actions.push(MenuAction::MoveReference);
+        }
+
+        let pos = self.screen_to_pos(screen_pos);
+
+        actions.push(MenuAction::StartLink);
+
+        if self.scenes.get(self.current_scene).and_then(|scene| scene.link(pos)).is_some()
+        {
+            actions.extend([MenuAction::FollowLink, MenuAction::RemoveLink]);
+        }
+
+        self.open_menu(&actions, self.screen_to_local(screen_pos), pos);
+    }
+
+    fn menu_action_name(&self, action: MenuAction) -> String
+    {
+        match action
+        {
+            MenuAction::Shortcut(Shortcut::Bookmark(index)) =>
+            {
+                self.bookmarks[index].as_ref().map(|bookmark| format!("go to {}", bookmark.name))
+                    .unwrap_or_else(|| format!("bookmark {} (empty)", index + 1))
+            },
+            _ => action.name().to_owned()
+        }
+    }
+
+    fn menu_accelerator(&self, action: MenuAction) -> Option<String>
+    {
+        match action
+        {
+            MenuAction::Help => Some("F1".to_owned()),
+            MenuAction::Shortcut(shortcut) =>
+            {
+                self.shortcuts.iter().find(|(_, x)| *x == shortcut)
+                    .map(|(key, _)| format!("ctrl+{}", key.name()))
+            },
+            _ => None
+        }
+    }
+
+    // whatever is on top gets the keyboard
+    fn move_focus(&mut self, forward: bool)
+    {
+        if let Some(dialog) = self.confirm_dialog.as_mut()
+        {
+            dialog.ui.move_focus(forward);
+        } else if let Some(menu) = self.context_menu.as_mut()
+        {
+            menu.ui.move_focus(forward);
+        } else if let Some(keybinds_ui) = self.keybinds_ui.as_mut()
+        {
+            keybinds_ui.move_focus(forward);
+        } else
+        {
+            match self.current_ui
+            {
+                UiVariant::Normal => self.ui.move_focus(forward),
+                UiVariant::Tiles => self.tiles_ui.move_focus(forward)
+            }
+        }
+    }
+
+    // returns if anything was focused
+    fn activate_focused(&mut self) -> bool
+    {
+        if let Some(dialog) = self.confirm_dialog.as_ref()
+        {
+            if let Some(answer) = dialog.ui.activate()
+            {
+                self.answer_confirm(answer);
+
+                return true;
+            }
+
+            return false;
+        }
+
+        if let Some(menu) = self.context_menu.as_ref()
+        {
+            if let Some(action) = menu.ui.activate()
+            {
+                let pos = menu.pos;
+                self.context_menu = None;
+
+                self.on_menu_action(action, pos);
+
+                return true;
+            }
+
+            return false;
+        }
+
+        if let Some(keybinds_ui) = self.keybinds_ui.as_ref()
+        {
+            if let Some(index) = keybinds_ui.activate()
+            {
+                self.rebinding = Some(index);
+                self.keybinds_ui = Some(self.create_keybinds_ui());
+
+                return true;
+            }
+
+            return false;
+        }
+
+        match self.current_ui
+        {
+            UiVariant::Normal =>
+            {
+                self.ui.activate().map(|message| self.on_ui_message(message)).is_some()
+            },
+            UiVariant::Tiles =>
+            {
+                self.tiles_ui.activate().map(|message| self.on_tiles_message(message)).is_some()
+            }
+        }
+    }
+
+    fn on_ui_message(&mut self, message: UiMessage)
+    {
+        match message
+        {
+            UiMessage::Scene(index) => self.current_scene = index,
+            UiMessage::Tool(tool) =>
+            {
+                self.ui.select(self.tools, tool as usize);
+
+                self.tool = tool;
+            },
+            UiMessage::NewScene =>
+            {
+                let templates = Template::list(TEMPLATES_DIRECTORY);
+
+                // without any templates theres nothing to pick from
+                if templates.is_empty()
+                {
+                    self.current_scene = self.scenes.len();
+                    self.ensure_current_scene();
+                } else
+                {
+                    let ui = self.create_new_scene_ui(&templates);
+
+                    self.new_scene_dialog = Some(NewSceneDialog{ui, templates});
+                }
+            },
+            UiMessage::ToggleTiles =>
+            {
+                self.current_ui = match self.current_ui
+                {
+                    UiVariant::Normal =>
+                    {
+                        self.tiles_ui.animate(
+                            &self.tiles_panel,
+                            self.tiles_window_animator_open.clone()
+                        );
+
+                        UiVariant::Tiles
+                    },
+                    UiVariant::Tiles =>
+                    {
+                        self.tiles_ui.animate(
+                            &self.tiles_panel,
+                            self.tiles_window_animator_close.clone()
+                        );
+
+                        UiVariant::Normal
+                    }
+                };
+            },
+            UiMessage::OpenMenu(index) =>
+            {
+                let (button, actions) = self.menu_bar[index].clone();
+
+                // opens right under the button
+                let (pos, _) = self.ui.get(&button).borrow().area();
+
+                self.open_menu(&actions, pos, Point2::new(0, 0));
+            },
+            UiMessage::OpenTints =>
+            {
+                let actions: Vec<MenuAction> = (0..TINTS.len()).map(MenuAction::Tint).collect();
+
+                let (pos, _) = self.ui.get(&self.tint_button).borrow().area();
+
+                self.open_menu(&actions, pos, Point2::new(0, 0));
+            }
+        }
+    }
+
+    fn on_tiles_message(&mut self, message: TilesMessage)
+    {
+        match message
+        {
+            TilesMessage::Tab(tab) =>
+            {
+                self.tiles_category = tab;
+                self.tiles_scroll = 0.0;
+
+                self.rebuild_tiles_ui();
+            },
+            TilesMessage::PreviousPage => self.turn_tiles_page(-1),
+            TilesMessage::NextPage => self.turn_tiles_page(1),
+            TilesMessage::Scrolled(scroll) => self.tiles_scroll = scroll,
+            TilesMessage::Tile(tile) => self.set_current_tile(tile)
+        }
+    }
+
+    // pos is the tile the actions apply to
+    fn open_menu(&mut self, actions: &[MenuAction], top_left: Point2<f32>, pos: Point2<i32>)
+    {
+        self.ensure_current_scene();
+
+        let mut ui = Ui::new(self.renderer.clone(), self.assets.clone());
+
+        let item_size = Point2::new(0.4, 0.05 * self.aspect);
+
+        // stays on screen even when opened near the edges
+        let menu_height = item_size.y * actions.len() as f32;
+
+        let left = top_left.x.min(1.0 - item_size.x);
+        let top = top_left.y.max(menu_height);
+
+        let texture = self.assets.borrow().ui_texture("background");
+        let white = self.assets.borrow().ui_texture("white");
+
+        actions.iter().copied().enumerate().for_each(|(index, action)|
+        {
+            let name = self.menu_action_name(action);
+
+            let text = if let Some(accelerator) = self.menu_accelerator(action)
+            {
+                format!("{name} ({accelerator})")
+            } else
+            {
+                name
+            };
+
+            // tints show what they look like
+            let color = if let MenuAction::Tint(index) = action
+            {
+                let [r, g, b] = TINTS[index].1;
+
+                Some(SdlColor::RGB(r, g, b))
+            } else
+            {
+                None
+            };
+
+            let item = ui.push(UiElement{
+                kind: UiElementType::Button,
+                pos: Point2::new(left, top - item_size.y * (index + 1) as f32),
+                size: item_size,
+                texture,
+                color,
+                alpha: 255
+            });
+
+            ui.push_child(&item, UiElement{
+                kind: UiElementType::Label(Label{
+                    text,
+                    font_size: None,
+                    color: SdlColor::WHITE,
+                    align: Align::Left
+                }),
+                pos: Point2::new(0.05, 0.2),
+                size: Point2::new(0.9, 0.6),
+                texture: white,
+                color: None,
+                alpha: 255
+            });
+
+            ui.on_click(&item, action);
+        });
+
+        self.context_menu = Some(ContextMenu{ui, pos});
+    }
+
+    fn on_menu_action(&mut self, action: MenuAction, pos: Point2<i32>)
+    {
+        match action
+        {
+            MenuAction::Help =>
+            {
+                self.show_help = !self.show_help;
+
+                return;
+            },
+            MenuAction::Shortcut(shortcut) =>
+            {
+                self.run_shortcut(shortcut);
+
+                return;
+            },
+            MenuAction::EditKeybinds =>
+            {
+                self.keybinds_ui = Some(self.create_keybinds_ui());
+
+                return;
+            },
+            MenuAction::RenameScene(index) =>
+            {
+                if let Some(scene) = self.scenes.get(index)
+                {
+                    self.renaming_scene = Some((index, scene.name().map(ToOwned::to_owned)));
+                }
+
+                return;
+            },
+            MenuAction::DeleteScene(index) =>
+            {
+                let message = format!("{} and its history will be gone", self.scene_name(index));
+
+                self.confirm("delete scene?", message, move |game| game.delete_scene(index));
+
+                return;
+            },
+            MenuAction::SceneProperties(index) =>
+            {
+                self.open_scene_properties(index);
+
+                return;
+            },
+            MenuAction::StartLink =>
+            {
+                self.linking = Some((self.current_scene, pos));
+
+                self.toast("click where the link should lead to, escape cancels");
+
+                return;
+            },
+            MenuAction::FollowLink =>
+            {
+                if let Some(link) = self.scenes[self.current_scene].link(pos)
+                {
+                    self.follow_link(link);
+                }
+
+                return;
+            },
+            MenuAction::RemoveLink =>
+            {
+                self.remember_scene();
+                self.scenes[self.current_scene].set_link(pos, None);
+
+                return;
+            },
+            MenuAction::MoveSceneLeft(index) =>
+            {
+                if index > 0
+                {
+                    self.swap_scenes(index, index - 1);
+                }
+
+                return;
+            },
+            MenuAction::MoveSceneRight(index) =>
+            {
+                self.swap_scenes(index, index + 1);
+
+                return;
+            },
+            MenuAction::SaveTemplate(index) =>
+            {
+                self.save_template(index);
+
+                return;
+            },
+            MenuAction::CompareSaved(index) =>
+            {
+                self.compare_with_saved(index);
+
+                return;
+            },
+            MenuAction::CompareWith(index) =>
+            {
+                let name = self.scene_name(index);
+                self.compare_scene(self.current_scene, DiffTarget::Scene(index), &name);
+
+                return;
+            },
+            MenuAction::StopComparing =>
+            {
+                self.scene_diff = None;
+
+                return;
+            },
+            MenuAction::Tint(index) =>
+            {
+                self.set_brush_tint(TINTS[index].1);
+
+                return;
+            },
+            MenuAction::MoveReference =>
+            {
+                if let Some(reference) = self.reference.as_mut()
+                {
+                    reference.pos = Point2::new(pos.x as f32, (pos.y + 1) as f32);
+                }
+
+                return;
+            },
+            MenuAction::SaveBookmark =>
+            {
+                // the first free one, or the last one once theyre all taken
+                let index = self.bookmarks.iter().position(Option::is_none)
+                    .unwrap_or(BOOKMARKS - 1);
+
+                self.save_bookmark(index);
+
+                return;
+            },
+            MenuAction::ClearRegion =>
+            {
+                self.confirm("clear region?", "all the tiles in it get removed", move |game|
+                {
+                    game.remember_scene();
+                    game.clear_region(pos);
+                });
+
+                return;
+            },
+            MenuAction::Fill => self.remember_scene(),
+            MenuAction::PickTile => ()
+        }
+
+        let scene = &mut self.scenes[self.current_scene];
+
+        match action
+        {
+            MenuAction::Fill => scene.fill_tinted(pos, self.current_tile, self.brush_tint),
+            MenuAction::PickTile =>
+            {
+                if let Some(&tile) = scene.get(pos).filter(|tile| !tile.is_none())
+                {
+                    let tint = scene.tint(pos);
+
+                    self.set_current_tile(tile);
+                    self.set_brush_tint(tint);
+                }
+            },
+            MenuAction::Help
+                | MenuAction::ClearRegion
+                | MenuAction::EditKeybinds
+                | MenuAction::RenameScene(_)
+                | MenuAction::DeleteScene(_)
+                | MenuAction::SceneProperties(_)
+                | MenuAction::MoveSceneLeft(_)
+                | MenuAction::MoveSceneRight(_)
+                | MenuAction::SaveTemplate(_)
+                | MenuAction::CompareSaved(_)
+                | MenuAction::CompareWith(_)
+                | MenuAction::StopComparing
+                | MenuAction::SaveBookmark
+                | MenuAction::MoveReference
+                | MenuAction::StartLink
+                | MenuAction::FollowLink
+                | MenuAction::RemoveLink
+                | MenuAction::Tint(_)
+                | MenuAction::Shortcut(_) => ()
+        }
+    }
+
+    // the selection if its under the cursor, otherwise the same tiles around it
+    fn clear_region(&mut self, pos: Point2<i32>)
+    {
+        let scene = &mut self.scenes[self.current_scene];
+
+        let selected = self.selection.map(|selection|
+        {
+            let (min, max) = selection.bounds();
+
+            pos.x >= min.x && pos.x <= max.x && pos.y >= min.y && pos.y <= max.y
+        }).unwrap_or(false);
+
+        if let (true, Some(selection)) = (selected, self.selection)
+        {
+            let (min, max) = selection.bounds();
+
+            for y in min.y..=max.y
+            {
+                for x in min.x..=max.x
+                {
+                    let pos = Point2::new(x, y);
+
+                    if scene.get(pos).is_some()
+                    {
+                        scene[pos] = Tile::none();
+                    }
+                }
+            }
+        } else
+        {
+            scene.fill(pos, Tile::none());
+        }
+    }
+
+    // only cells with tiles get rerolled so empty space doesnt pile up seeds
+    fn reroll_selection(&mut self)
+    {
+        let (min, max) = if let Some(selection) = self.selection
+        {
+            selection.bounds()
+        } else
+        {
+            eprintln!("nothing selected");
+
+            return;
+        };
+
+        let scene = &mut self.scenes[self.current_scene];
+        for y in min.y..=max.y
+        {
+            for x in min.x..=max.x
+            {
+                let pos = Point2::new(x, y);
+
+                if scene.get(pos).map(|tile| !tile.is_none()).unwrap_or(false)
+                {
+                    scene.reroll(pos);
+                }
+            }
+        }
+    }
+
+    fn copy_selection(&self)
+    {
+        let prefab = if let Some(x) = self.selection_prefab() { x } else { return };
+
+        if let Err(err) = self.window.borrow().clipboard.set_clipboard_text(&prefab.to_text())
+        {
+            eprintln!("cant copy to clipboard: {err}");
+        }
+    }
+
+    fn paste_clipboard(&mut self)
+    {
+        let text = match self.window.borrow().clipboard.clipboard_text()
+        {
+            Ok(x) => x,
+            Err(err) =>
+            {
+                eprintln!("cant read clipboard: {err}");
+
+                return;
+            }
+        };
+
+        match Prefab::from_text(&text)
+        {
+            Ok(prefab) => self.paste_prefab(prefab),
+            Err(err) => eprintln!("clipboard doesnt contain tiles: {err}")
+        }
+    }
+
+    fn import_prefab(&mut self, path: &Path)
+    {
+        match Prefab::load(path)
+        {
+            Ok(prefab) => self.paste_prefab(prefab),
+            Err(err) => eprintln!("cant import prefab {}: {err}", path.display())
+        }
+    }
+
+    // pastes at the tile under the mouse
+    fn paste_prefab(&mut self, prefab: Prefab)
+    {
+        let mut pos = self.screen_to_pos(self.mouse_pos);
+
+        if self.paste_snap
+        {
+            let grid = self.paste_grid.unwrap_or_else(||
+            {
+                prefab.size.map(|x| (x as i32).max(1))
+            });
+
+            pos = pos.zip(grid).map(|(pos, grid)| pos.div_euclid(grid) * grid);
+        }
+
+        let assets = self.assets.borrow();
+        let missing = prefab.paste(&mut self.scenes[self.current_scene], pos, |name|
+        {
+            assets.tile(name)
+        });
+
+        if !missing.is_empty()
+        {
+            eprintln!("prefab has unknown tiles: {}", missing.join(", "));
+        }
+    }
+
+    fn on_shortcut(&mut self, key: Keycode, shift: bool) -> bool
+    {
+        if self.viewer.is_some()
+        {
+            return false;
+        }
+
+        self.ensure_current_scene();
+
+        let shortcut = if let Some(&(_, shortcut)) = self.shortcuts.iter().find(|(k, _)| *k == key)
+        {
+            shortcut
+        } else
+        {
+            return false;
+        };
+
+        if let (Shortcut::Bookmark(index), true) = (shortcut, shift)
+        {
+            self.save_bookmark(index);
+
+            return true;
+        }
+
+        self.run_shortcut(shortcut);
+
+        true
+    }
+
+    fn run_shortcut(&mut self, shortcut: Shortcut)
+    {
+        let changes_scene = matches!(
+            shortcut,
+            Shortcut::Paste
+                | Shortcut::ImportPrefab
+                | Shortcut::Reroll
+        );
+
+        let moves_scene = matches!(
+            shortcut,
+            Shortcut::MirrorX
+                | Shortcut::MirrorY
+                | Shortcut::Rotate
+                | Shortcut::Translate(..)
+        );
+
+        if moves_scene
+        {
+            self.remember_moved_scene();
+        } else if changes_scene
+        {
+            self.remember_scene();
+        }
+
+        match shortcut
+        {
+            Shortcut::New =>
+            {
+                self.confirm("new map?", "unsaved changes will be lost", |game|
+                {
+                    game.scenes.clear();
+                    game.current_scene = 0;
+                    game.ensure_current_scene();
+
+                    game.clear_history();
+                });
+            },
+            Shortcut::Open =>
+            {
+                let path = self.save_path.clone();
+                let message = format!("unsaved changes will be lost, open {}?", path.display());
+
+                self.confirm("open?", message, move |game| game.open(path));
+            },
+            Shortcut::Undo => self.undo(),
+            Shortcut::Redo => self.redo(),
+            Shortcut::ToggleGrid => self.show_grid = !self.show_grid,
+            Shortcut::ToggleRulers => self.show_rulers = !self.show_rulers,
+            Shortcut::ToggleSplit => self.toggle_split(),
+            Shortcut::ToggleZoomSnap => self.set_snap_zoom(!self.snap_zoom),
+            Shortcut::ToggleLighting => self.set_lighting(!self.lighting),
+            Shortcut::ReferenceAlpha(amount) =>
+            {
+                if let Some(reference) = self.reference.as_mut()
+                {
+                    reference.alpha = (reference.alpha as i32 + amount * 32).clamp(0, 255) as u8;
+                }
+            },
+            Shortcut::ReferenceScale(amount) =>
+            {
+                if let Some(reference) = self.reference.as_mut()
+                {
+                    reference.scale /= 1.25_f32.powi(amount);
+                }
+            },
+            Shortcut::ToggleReferenceAbove =>
+            {
+                if let Some(reference) = self.reference.as_mut()
+                {
+                    reference.above = !reference.above;
+                }
+            },
+            Shortcut::ZoomIn => self.camera.height *= 0.8,
+            Shortcut::ZoomOut => self.camera.height /= 0.8,
+            Shortcut::FitScene => self.fit_scene(),
+            Shortcut::PixelZoom(scale) => self.pixel_zoom(scale),
+            Shortcut::Bookmark(index) => self.go_to_bookmark(index),
+            Shortcut::Save => self.save(),
+            Shortcut::RestoreBackup => self.restore_backup(),
+            Shortcut::Export(kind) => self.export(kind),
+            Shortcut::QuickExport => self.quick_export(),
+            Shortcut::ExportPrefab => self.export_prefab(),
+            Shortcut::Copy => self.copy_selection(),
+            Shortcut::Reroll => self.reroll_selection(),
+            Shortcut::TogglePasteSnap =>
+            {
+                self.paste_snap = !self.paste_snap;
+
+                println!("snap pastes to grid: {}", self.paste_snap);
+            },
+            Shortcut::Paste => self.paste_clipboard(),
+            Shortcut::ImportPrefab =>
+            {
+                if let Some(path) = self.last_prefab.clone()
+                {
+                    self.import_prefab(&path);
+                }
+            },
+            Shortcut::ToggleCrop =>
+            {
+                self.export_options.crop = !self.export_options.crop;
+
+                println!("crop on export: {}", self.export_options.crop);
+            },
+            Shortcut::MirrorX =>
+            {
+                let moved = self.scenes[self.current_scene].mirror_x();
+                self.move_link_targets(&moved);
+            },
+            Shortcut::MirrorY =>
+            {
+                let moved = self.scenes[self.current_scene].mirror_y();
+                self.move_link_targets(&moved);
+            },
+            Shortcut::Rotate =>
+            {
+                let moved = self.scenes[self.current_scene].rotate_clockwise();
+                self.move_link_targets(&moved);
+            },
+            Shortcut::Translate(x, y) =>
+            {
+                let moved = self.scenes[self.current_scene].translate(Point2::new(x, y));
+                self.move_link_targets(&moved);
+            }
+        }
+    }
+
+    // links into the current scene have to follow its tiles around
+    fn move_link_targets(&mut self, moved: &CellMap)
+    {
+        let current = self.current_scene;
+
+        self.scenes.iter_mut().for_each(|scene| scene.move_link_targets(current, moved));
+
+        self.linking = self.linking.and_then(|(scene, pos)|
+        {
+            if scene == current
+            {
+                moved(pos).map(|pos| (scene, pos))
+            } else
+            {
+                Some((scene, pos))
+            }
+        });
+    }
+
+    // call before changing the current scene so it can be undone
+    fn remember_scene(&mut self)
+    {
+        self.remember_scene_at(self.current_scene);
+    }
+
+    fn remember_scene_at(&mut self, index: usize)
+    {
+        self.history.remember(&self.scenes, [index]);
+    }
+
+    // moving a scene around moves the targets of links into it too, so the scenes with those
+    // links have to go back along with it
+    fn remember_moved_scene(&mut self)
+    {
+        let current = self.current_scene;
+
+        let linking = self.scenes.iter().enumerate().filter(|(index, scene)|
+        {
+            *index != current && scene.links().any(|(_, link)| link.scene == current)
+        }).map(|(index, _)| index);
+
+        let indices: Vec<usize> = Some(current).into_iter().chain(linking).collect();
+
+        self.history.remember(&self.scenes, indices);
+    }
+
+    fn ctrl_held(&self) -> bool
+    {
+        self.any_held(&[Scancode::LCtrl, Scancode::RCtrl])
+    }
+
+    fn shift_held(&self) -> bool
+    {
+        self.any_held(&[Scancode::LShift, Scancode::RShift])
+    }
+
+    fn any_held(&self, keys: &[Scancode]) -> bool
+    {
+        let window = self.window.borrow();
+        let keyboard = window.events.keyboard_state();
+
+        keys.iter().any(|key| keyboard.is_scancode_pressed(*key))
+    }
+
+    // the link goes from a tile in the scene it was started in to a tile in the current one
+    fn place_link(&mut self, scene: usize, from: Point2<i32>, to: Point2<i32>)
+    {
+        if scene >= self.scenes.len()
+        {
+            return;
+        }
+
+        self.remember_scene_at(scene);
+
+        let link = SceneLink{scene: self.current_scene, pos: to};
+        self.scenes[scene].set_link(from, Some(link));
+
+        let name = self.scene_name(self.current_scene);
+        self.toast(format!("linked to {name} at {} {}", to.x, to.y));
+    }
+
+    fn follow_link(&mut self, link: SceneLink)
+    {
+        if link.scene >= self.scenes.len()
+        {
+            self.toast(format!("scene {} doesnt exist anymore", link.scene));
+
+            return;
+        }
+
+        self.current_scene = link.scene;
+
+        // still holding ctrl from the click shouldnt zoom during the flight
+        self.release_ctrl_controls();
+
+        let pos = self.tile_to_camera(link.pos.map(|x| x as f32 + 0.5));
+
+        self.fly_to(Camera{pos, height: self.camera.height});
+    }
+
+    fn clear_history(&mut self)
+    {
+        self.history.clear();
+    }
+
+    fn undo(&mut self)
+    {
+        if let Some(index) = self.history.undo(&mut self.scenes)
+        {
+            self.current_scene = index;
+        }
+    }
+
+    fn redo(&mut self)
+    {
+        if let Some(index) = self.history.redo(&mut self.scenes)
+        {
+            self.current_scene = index;
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> bool
+    {
+        match event
+        {
+            Event::Quit{..} => return false,
+            Event::Window{win_event: WindowEvent::SizeChanged(width, height), ..} =>
+            {
+                self.resize(Point2::new(width as usize, height as usize));
+            },
+            Event::KeyDown{keycode: Some(key), keymod, repeat, ..} =>
+            {
+                if let Some(index) = self.rebinding
+                {
+                    if key == Keycode::Escape
+                    {
+                        self.rebinding = None;
+                        self.keybinds_ui = Some(self.create_keybinds_ui());
+                    } else
+                    {
+                        self.rebind(index, Keybind::Keyboard(key));
+                    }
+
+                    return true;
+                }
+
+                if self.confirm_dialog.is_some() && key == Keycode::Escape
+                {
+                    self.answer_confirm(false);
+
+                    return true;
+                }
+
+                if self.keybinds_ui.is_some() && key == Keycode::Escape
+                {
+                    self.keybinds_ui = None;
+
+                    return true;
+                }
+
+                if self.properties_input(key)
+                {
+                    return true;
+                }
+
+                if self.new_scene_dialog.is_some()
+                {
+                    if key == Keycode::Escape
+                    {
+                        self.new_scene_dialog = None;
+                    }
+
+                    return true;
+                }
+
+                if self.rename_scene_input(key)
+                {
+                    return true;
+                }
+
+                if key == Keycode::Escape && self.context_menu.is_some()
+                {
+                    self.context_menu = None;
+
+                    return true;
+                }
+
+                if key == Keycode::Escape && self.linking.is_some()
+                {
+                    self.linking = None;
+
+                    return true;
+                }
+
+                if key == Keycode::Escape && self.scene_diff.is_some()
+                {
+                    self.scene_diff = None;
+
+                    return true;
+                }
+
+                if key == Keycode::F1
+                {
+                    self.show_help = !self.show_help;
+
+                    return true;
+                }
+
+                if key == Keycode::F3
+                {
+                    self.show_profiler = !self.show_profiler;
+
+                    return true;
+                }
+
+                let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+                let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+
+                // ctrl with arrows moves the selection instead
+                if !ctrl
+                {
+                    let forward = match key
+                    {
+                        Keycode::Tab => Some(!shift),
+                        Keycode::Down | Keycode::Right => Some(true),
+                        Keycode::Up | Keycode::Left => Some(false),
+                        _ => None
+                    };
+
+                    if let Some(forward) = forward
+                    {
+                        self.move_focus(forward);
+
+                        return true;
+                    }
+
+                    if matches!(key, Keycode::Return | Keycode::KpEnter) && self.activate_focused()
+                    {
+                        return true;
+                    }
+                }
+
+                // nothing else happens while its open
+                if self.keybinds_ui.is_some() || self.confirm_dialog.is_some()
+                {
+                    return true;
+                }
+
+                // typing goes into the search box while the picker is open
+                if let (UiVariant::Tiles, false) = (&self.current_ui, ctrl)
+                {
+                    match key
+                    {
+                        Keycode::Backspace =>
+                        {
+                            self.search_tiles_input(|search| { search.pop(); });
+                        },
+                        Keycode::Escape => self.search_tiles_input(String::clear),
+                        _ => ()
+                    }
+
+                    return true;
+                }
+
+                // held ones r already held and actions shouldnt happen over and over
+                let recent = match key
+                {
+                    Keycode::Num1 => Some(0),
+                    Keycode::Num2 => Some(1),
+                    Keycode::Num3 => Some(2),
+                    Keycode::Num4 => Some(3),
+                    Keycode::Num5 => Some(4),
+                    Keycode::Num6 => Some(5),
+                    Keycode::Num7 => Some(6),
+                    Keycode::Num8 => Some(7),
+                    Keycode::Num9 => Some(8),
+                    Keycode::Num0 => Some(9),
+                    _ => None
+                };
+
+                if let (Some(index), false) = (recent, ctrl)
+                {
+                    if let Some(&tile) = self.recent_tiles.get(index)
+                    {
+                        self.set_current_tile(tile);
+                    }
+
+                    return true;
+                }
+
+                let shortcut = ctrl && self.on_shortcut(key, shift);
+                if shortcut
+                {
+                    self.release_ctrl_controls();
+                } else if !repeat
+                {
+                    self.set_control(Keybind::Keyboard(key), true);
+                }
+            },
+            Event::TextInput{text, ..} =>
+            {
+                if let Some(dialog) = self.properties_dialog.as_mut()
+                {
+                    dialog.input.push_str(&text);
+
+                    self.refresh_properties_ui();
+                } else if let Some((index, _)) = self.renaming_scene
+                {
+                    let scene = &mut self.scenes[index];
+
+                    let name = format!("{}{text}", scene.name().unwrap_or(""));
+                    scene.set_name(Some(name));
+                } else if let UiVariant::Tiles = self.current_ui
+                {
+                    self.search_tiles_input(|search| search.push_str(&text));
+                }
+            },
+            Event::KeyUp{keycode: Some(key), ..} =>
+            {
+                self.set_control(Keybind::Keyboard(key), false);
+            },
+            Event::DropFile{filename, ..} if self.viewer.is_none() =>
+            {
+                self.ensure_current_scene();
+                self.remember_scene();
+
+                self.import_prefab(Path::new(&filename));
+            },
+            Event::MouseMotion{x, y, ..} =>
+            {
+                self.mouse_pos = Point2::new(x, y);
+
+                self.update_split_side();
+            },
+            Event::MouseButtonDown{which: button, mouse_btn, clicks, x, y, ..} =>
+            {
+                let pos = self.screen_to_local(Point2{x, y});
+
+                // clicking anywhere keeps the new name
+                self.renaming_scene = None;
+
+                if let Some(index) = self.rebinding
+                {
+                    self.rebind(index, Keybind::Mouse(button));
+
+                    return true;
+                }
+
+                if let Some(dialog) = self.confirm_dialog.as_ref()
+                {
+                    if let (0, Some(answer)) = (button, dialog.ui.click(pos))
+                    {
+                        self.answer_confirm(answer);
+                    }
+
+                    return true;
+                }
+
+                if let Some(keybinds_ui) = self.keybinds_ui.as_ref()
+                {
+                    if let (0, Some(index)) = (button, keybinds_ui.click(pos))
+                    {
+                        self.rebinding = Some(index);
+                        self.keybinds_ui = Some(self.create_keybinds_ui());
+                    }
+
+                    return true;
+                }
+
+                if let Some(dialog) = self.new_scene_dialog.as_ref()
+                {
+                    if let (0, Some(index)) = (button, dialog.ui.click(pos))
+                    {
+                        let template = index.checked_sub(1)
+                            .and_then(|index| dialog.templates.get(index))
+                            .cloned();
+
+                        self.new_scene_dialog = None;
+
+                        self.new_scene(template.as_deref());
+                    }
+
+                    return true;
+                }
+
+                if let Some(dialog) = self.properties_dialog.as_ref()
+                {
+                    // clicking a property puts it in the line to be changed
+                    if let (0, Some(index)) = (button, dialog.ui.click(pos))
+                    {
+                        let input = self.scenes.get(dialog.scene)
+                            .and_then(|scene| scene.properties().nth(index))
+                            .map(|(key, value)| format!("{key} = {value}"));
+
+                        let dialog = self.properties_dialog.as_mut();
+                        if let (Some(input), Some(dialog)) = (input, dialog)
+                        {
+                            dialog.input = input;
+                        }
+
+                        self.refresh_properties_ui();
+                    }
+
+                    return true;
+                }
+
+                if let Some(menu) = self.context_menu.take()
+                {
+                    if let Some(action) = menu.ui.click(pos)
+                    {
+                        self.on_menu_action(action, menu.pos);
+                    }
+
+                    return true;
+                }
+
+                if mouse_btn == MouseButton::Right && self.viewer.is_none()
+                {
+                    if let UiVariant::Normal = self.current_ui
+                    {
+                        if let Some(UiMessage::Scene(index)) = self.ui.click(pos)
+                        {
+                            let mut actions = vec![
+                                MenuAction::RenameScene(index),
+                                MenuAction::DeleteScene(index),
+                                MenuAction::SceneProperties(index)
+                            ];
+
+                            if index > 0
+                            {
+                                actions.push(MenuAction::MoveSceneLeft(index));
+                            }
+
+                            if index + 1 < self.scenes.len()
+                            {
+                                actions.push(MenuAction::MoveSceneRight(index));
+                            }
+
+                            actions.push(MenuAction::SaveTemplate(index));
+                            actions.push(MenuAction::CompareSaved(index));
+
+                            if index != self.current_scene
+                            {
+                                actions.push(MenuAction::CompareWith(index));
+                            }
+
+                            if self.scene_diff.is_some()
+                            {
+                                actions.push(MenuAction::StopComparing);
+                            }
+
+                            self.open_menu(&actions, pos, Point2::new(0, 0));
+                        } else
+                        {
+                            self.open_context_menu(Point2{x, y});
+                        }
+
+                        return true;
+                    }
+                }
+
+                // picking where a link leads to or following one instead of using the tool
+                let on_scene = self.viewer.is_none()
+                    && matches!(self.current_ui, UiVariant::Normal)
+                    && self.ui.click(pos).is_none();
+
+                if button == 0 && on_scene
+                {
+                    let tile_pos = self.screen_to_pos(Point2{x, y});
+
+                    if let Some((scene, from)) = self.linking.take()
+                    {
+                        self.place_link(scene, from, tile_pos);
+
+                        return true;
+                    }
+
+                    let link = self.scenes.get(self.current_scene)
+                        .and_then(|scene| scene.link(tile_pos));
+
+                    if let (Some(link), true) = (link, self.ctrl_held())
+                    {
+                        self.follow_link(link);
+
+                        return true;
+                    }
+                }
+
+                if button == 0
+                {
+                    self.mouse_held = true;
+                }
+
+                // thats kinda cool i think thats a cool way to use pattern matching
+                if let (0, Some(message)) = (button, self.ui.click(pos))
+                {
+                    self.on_ui_message(message);
+
+                    return true;
+                }
+
+                match self.current_ui
+                {
+                    UiVariant::Tiles =>
+                    {
+                        if button == 0 && self.tiles_ui.press(pos)
+                        {
+                            return true;
+                        }
+
+                        if let (0, Some(message)) = (button, self.tiles_ui.click(pos))
+                        {
+                            self.on_tiles_message(message);
+                        }
+
+                        return true;
+                    },
+                    UiVariant::Normal => ()
+                }
+
+                if button == 0
+                {
+                    self.press_scene(Point2{x, y}, clicks);
+                }
+
+                self.set_control(Keybind::Mouse(button), true);
+            },
+            Event::MouseWheel{y, ..} =>
+            {
+                if let UiVariant::Tiles = self.current_ui
+                {
+                    let pos = self.screen_to_local(self.mouse_pos);
+
+                    self.tiles_ui.scroll(pos, -y as f32 * 0.1);
+                } else if self.shift_held()
+                {
+                    self.cycle_recent_tile(-y.signum());
+                }
+            },
+            Event::MouseButtonUp{which: button, x, y, ..} =>
+            {
+                if button == 0
+                {
+                    self.mouse_held = false;
+
+                    self.release_scene(Point2{x, y});
+                }
+
+                self.set_control(Keybind::Mouse(button), false);
+            },
+            _ => ()
+        }
+
+        true
+    }
+
+    fn press_scene(&mut self, pos: Point2<i32>, clicks: u8)
+    {
+        let select = self.tool == Tool::Select || self.pressed(ControlName::Select);
+
+        let press = MousePress{pos, time: Instant::now(), clicks, select};
+        self.mouse_press = Some(press);
+
+        if press.is_double() && press.select && self.viewer.is_none()
+        {
+            self.select_connected(self.screen_to_pos(pos));
+        }
+    }
+
+    // a quick click with the select tool gets rid of the selection, dragging makes a new one
+    fn release_scene(&mut self, pos: Point2<i32>)
+    {
+        let Some(press) = self.mouse_press.take()
+        else
+        {
+            return;
+        };
+
+        if press.select && !press.is_double() && press.is_click(pos)
+        {
+            self.selection = None;
+        }
+    }
+
+    // the smallest selection that has every tile connected to the one at pos
+    fn select_connected(&mut self, pos: Point2<i32>)
+    {
+        let Some(scene) = self.scenes.get(self.current_scene)
+        else
+        {
+            return;
+        };
+
+        let connected = scene.connected(pos);
+
+        let Some(&first) = connected.first()
+        else
+        {
+            return;
+        };
+
+        let (start, end) = connected.iter().fold((first, first), |(start, end), pos|
+        {
+            (start.zip(*pos).map(|(a, b)| a.min(b)), end.zip(*pos).map(|(a, b)| a.max(b)))
+        });
+
+        self.selection = Some(Selection{start, end});
+    }
+
+    fn tile_size(&self) -> Point2<f32>
+    {
+        let mut size = Point2::repeat(1.0 / self.camera.height);
+        size.x *= self.tile_aspect() / self.view_aspect();
+
+        size
+    }
+
+    // returns if any of the tiles r animated
+    fn draw_view(&self, hover: Option<Point2<i32>>, selection: Option<Selection>) -> bool
+    {
+        let (offset, size) = self.view_area();
+
+        let area = Rect::new(offset, 0, size.x as u32, size.y as u32);
+        self.renderer.borrow_mut().set_viewport(Some(area));
+
+        let reference = self.reference.as_ref();
+
+        if let Some(reference) = reference.filter(|reference| !reference.above)
+        {
+            self.draw_reference(reference);
+        }
+
+        // a cache is only up to date if nothing in it is animated
+        let animated = if let Some(viewer) = self.viewer.as_ref()
+        {
+            self.draw_mapped(viewer)
+        } else if !self.draw_scene_cache()
+        {
+            self.draw_scene(&self.scenes[self.current_scene])
+        } else
+        {
+            false
+        };
+
+        if self.lighting
+        {
+            self.draw_lighting();
+        }
+
+        if let Some(reference) = reference.filter(|reference| reference.above)
+        {
+            self.draw_reference(reference);
+        }
+
+        if self.show_grid
+        {
+            self.draw_grid();
+        }
+
+        if self.viewer.is_none()
+        {
+            self.draw_links();
+            self.draw_scene_diff();
+        }
+
+        if let Some(selection) = selection
+        {
+            self.draw_selection(selection);
+        }
+
+        if let Some(pos) = hover
+        {
+            self.draw_hover(pos);
+        }
+
+        if self.show_rulers
+        {
+            self.draw_rulers();
+        }
+
+        self.renderer.borrow_mut().set_viewport(None);
+
+        animated
+    }
+
+    fn draw_reference(&self, reference: &Reference)
+    {
+        let (texture, source) = self.assets.borrow().texture(reference.texture);
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        let mut size = renderer.texture_size(texture).map(|x| x as f32) / reference.scale;
+
+        // the scale is in tile heights, wide tiles would stretch it
+        size.x /= self.tile_aspect();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+
+        let mut top_left = self.point_to_view(reference.pos);
+        top_left.y = 1.0 - top_left.y;
+
+        let start = (top_left * window_size).map(|x| x.floor() as i32);
+        let size = (size * self.tile_size() * window_size).map(|x| x.round().max(1.0) as u32);
+
+        renderer.copy_tinted(
+            texture,
+            source,
+            Rect::new(start.x, start.y, size.x, size.y),
+            SdlColor::RGBA(255, 255, 255, reference.alpha)
+        );
+    }
+
+    // returns if any of the tiles r animated
+    fn draw_scene(&self, scene: &Scene) -> bool
+    {
+        let (min, max) = self.visible_tiles();
+
+        self.draw_tiles(scene.iter_area(min, max).map(|(pos, tile)|
+        {
+            (pos, *tile, scene.variant_seed(pos), scene.tint(pos))
+        }))
+    }
+
+    fn view_side(&self) -> usize
+    {
+        self.split.as_ref().map(|split| split.active_right as usize).unwrap_or(0)
+    }
+
+    // where the view is in the cache in pixels, none if it has to be drawn again
+    fn scene_cache_offset(&self, cache: &SceneCache) -> Option<Point2<i32>>
+    {
+        let (_, view_size) = self.view_area();
+
+        let same = !cache.animated
+            && cache.scene == self.current_scene
+            && cache.revision == self.scenes.get(self.current_scene)?.revision()
+            && cache.view_size == view_size
+            && cache.camera.height == self.camera.height;
+
+        if !same
+        {
+            return None;
+        }
+
+        let moved = (self.camera.pos - cache.camera.pos) / self.camera.height
+            * view_size.map(|x| x as f32);
+
+        let margin = (cache.size - view_size).map(|x| x as i32 / 2);
+        let offset = Point2::new(
+            margin.x + moved.x.round() as i32,
+            margin.y - moved.y.round() as i32
+        );
+
+        let inside = offset.x >= 0 && offset.y >= 0
+            && offset.x <= margin.x * 2 && offset.y <= margin.y * 2;
+
+        inside.then_some(offset)
+    }
+
+    fn update_scene_cache(&mut self)
+    {
+        if self.viewer.is_some()
+        {
+            return;
+        }
+
+        self.ensure_current_scene();
+
+        let side = self.view_side();
+
+        let cached = self.scene_caches[side].as_ref()
+            .and_then(|cache| self.scene_cache_offset(cache))
+            .is_some();
+
+        if cached
+        {
+            return;
+        }
+
+        let (_, view_size) = self.view_area();
+        let size = view_size.map(|x| x + x / 2);
+
+        // the old texture is fine if the size didnt change
+        let texture = {
+            let mut renderer = self.renderer.borrow_mut();
+
+            match self.scene_caches[side].take()
+            {
+                Some(cache) if cache.size == size => Some(cache.texture),
+                old =>
+                {
+                    if let Some(cache) = old
+                    {
+                        renderer.destroy_texture(cache.texture);
+                    }
+
+                    renderer.create_target(size.map(|x| x as u32))
+                }
+            }
+        };
+
+        // without render targets the tiles just get drawn straight to the window
+        let Some(texture) = texture
+        else
+        {
+            return;
+        };
+
+        let camera = self.camera;
+        let aspect = self.view_aspect();
+
+        self.view_override = Some(size);
+
+        // same middle and same tile size, just more of them
+        self.camera = Camera{
+            pos: Point2::new(camera.pos.x * aspect / self.view_aspect(), camera.pos.y),
+            height: camera.height * size.y as f32 / view_size.y as f32
+        };
+
+        {
+            let mut renderer = self.renderer.borrow_mut();
+
+            renderer.set_target(Some(texture));
+            renderer.set_color(SdlColor::RGBA(0, 0, 0, 0));
+            renderer.clear();
+        }
+
+        let animated = self.draw_scene(&self.scenes[self.current_scene]);
+
+        self.renderer.borrow_mut().set_target(None);
+
+        self.camera = camera;
+        self.view_override = None;
+
+        self.scene_caches[side] = Some(SceneCache{
+            texture,
+            size,
+            scene: self.current_scene,
+            revision: self.scenes[self.current_scene].revision(),
+            camera,
+            view_size,
+            animated
+        });
+    }
+
+    // the textures go back to the renderer instead of piling up
+    fn clear_scene_caches(&mut self)
+    {
+        let mut renderer = self.renderer.borrow_mut();
+
+        self.scene_caches.iter_mut().filter_map(Option::take).for_each(|cache|
+        {
+            renderer.destroy_texture(cache.texture);
+        });
+
+        self.light_caches.iter_mut().filter_map(Option::take).for_each(|cache|
+        {
+            renderer.destroy_texture(cache.texture);
+        });
+    }
+
+    fn update_light_cache(&mut self)
+    {
+        if !self.lighting || self.viewer.is_some()
+        {
+            return;
+        }
+
+        let side = self.view_side();
+        let (min, max) = self.visible_tiles();
+
+        let scene = &self.scenes[self.current_scene];
+
+        let cached = self.light_caches[side].as_ref().map(|cache|
+        {
+            cache.scene == self.current_scene
+                && cache.revision == scene.revision()
+                && cache.min == min
+                && cache.max == max
+        }).unwrap_or(false);
+
+        if cached
+        {
+            return;
+        }
+
+        let size = (max - min).map(|x| x as usize + 1);
+
+        if size.x > MAX_LIGHT_TILES || size.y > MAX_LIGHT_TILES
+        {
+            if let Some(cache) = self.light_caches[side].take()
+            {
+                self.renderer.borrow_mut().destroy_texture(cache.texture);
+            }
+
+            return;
+        }
+
+        let mut light = vec![0.0_f32; size.x * size.y];
+
+        {
+            let assets = self.assets.borrow();
+
+            let margin = MAX_LIGHT_RADIUS;
+            scene.iter_area(min - margin, max + margin).for_each(|(source, tile)|
+            {
+                if tile.is_none()
+                {
+                    return;
+                }
+
+                let Some(radius) = assets.tile_light(*tile)
+                else
+                {
+                    return;
+                };
+
+                let radius = radius.min(MAX_LIGHT_RADIUS as f32);
+                let reach = radius.ceil() as i32;
+
+                let start = (source - reach).zip(min).map(|(x, min)| x.max(min));
+                let end = (source + reach).zip(max).map(|(x, max)| x.min(max));
+
+                for y in start.y..=end.y
+                {
+                    for x in start.x..=end.x
+                    {
+                        let offset = (Point2::new(x, y) - source).map(|x| x as f32);
+                        let distance = (offset.x * offset.x + offset.y * offset.y).sqrt();
+
+                        // smoothstep so the edge of the light doesnt look like a ring
+                        let t = (1.0 - distance / radius).max(0.0);
+                        let brightness = t * t * (3.0 - 2.0 * t);
+
+                        let index = (max.y - y) as usize * size.x + (x - min.x) as usize;
+                        light[index] = light[index].max(brightness);
+                    }
+                }
+            });
+        }
+
+        let ambient = self.ambient_light;
+        let data = light.into_iter().flat_map(|brightness|
+        {
+            let brightness = ambient + (1.0 - ambient) * brightness;
+
+            [0, 0, 0, ((1.0 - brightness) * 255.0).round() as u8]
+        }).collect();
+
+        let image = Image::from_raw(size, data);
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        if let Some(cache) = self.light_caches[side].take()
+        {
+            renderer.destroy_texture(cache.texture);
+        }
+
+        let texture = renderer.create_texture(&image);
+        renderer.set_filter(texture, Filter::Linear);
+
+        self.light_caches[side] = Some(LightCache{
+            texture,
+            scene: self.current_scene,
+            revision: scene.revision(),
+            min,
+            max
+        });
+    }
+
+    fn draw_lighting(&self)
+    {
+        let Some(cache) = self.light_caches[self.view_side()].as_ref()
+        else
+        {
+            return;
+        };
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+        let to_screen = |pos: Point2<i32>|
+        {
+            let mut pos = self.pos_to_view(pos);
+            pos.y = 1.0 - pos.y;
+
+            (pos * window_size).map(|x| x.floor() as i32)
+        };
+
+        let top_left = to_screen(Point2::new(cache.min.x, cache.max.y + 1));
+        let bottom_right = to_screen(Point2::new(cache.max.x + 1, cache.min.y));
+
+        let size = (bottom_right - top_left).map(|x| x.max(1) as u32);
+
+        self.renderer.borrow_mut().copy(
+            cache.texture,
+            None,
+            Rect::new(top_left.x, top_left.y, size.x, size.y)
+        );
+    }
+
+    // returns if there was an up to date cache to draw
+    fn draw_scene_cache(&self) -> bool
+    {
+        let Some(cache) = self.scene_caches[self.view_side()].as_ref()
+        else
+        {
+            return false;
+        };
+
+        let Some(offset) = self.scene_cache_offset(cache)
+        else
+        {
+            return false;
+        };
+
+        let (_, view_size) = self.view_area();
+        let view_size = view_size.map(|x| x as u32);
+
+        let source = Rect::new(offset.x, offset.y, view_size.x, view_size.y);
+        let destination = Rect::new(0, 0, view_size.x, view_size.y);
+
+        self.renderer.borrow_mut().copy(cache.texture, Some(source), destination);
+
+        true
+    }
+
+    fn draw_mapped(&self, scene: &MappedScene) -> bool
+    {
+        let (min, max) = self.visible_tiles();
+
+        self.draw_tiles((min.y..=max.y).flat_map(|y|
+        {
+            (min.x..=max.x).filter_map(move |x|
+            {
+                let pos = Point2::new(x, y);
+
+                scene.get(pos).map(|tile| (pos, tile, 0, NO_TINT))
+            })
+        }))
+    }
+
+    // inclusive corners of the tiles that r on screen
+    fn visible_tiles(&self) -> (Point2<i32>, Point2<i32>)
+    {
+        let (offset, size) = self.view_area();
+        let size = size.map(|x| x as i32);
+
+        (
+            self.screen_to_pos(Point2::new(offset, size.y)),
+            self.screen_to_pos(Point2::new(offset + size.x, 0))
+        )
+    }
+
+    fn draw_tiles(&self, tiles: impl Iterator<Item=(Point2<i32>, Tile, u32, [u8; 3])>) -> bool
+    {
+        // whole pixel tiles line up by themselves without overlapping
+        let pixel_perfect = {
+            let pixels = self.view_area().1.y as f32 / self.camera.height;
+
+            self.snap_zoom && (pixels - pixels.round()).abs() < 0.001
+        };
+
+        let size = self.tile_size();
+        let window_size = self.view_area().1.map(|x| x as f32);
+
+        // u would think that ceil would work but nope
+        let scaled_size = if pixel_perfect
+        {
+            (size * window_size).map(|x| x.round() as u32)
+        } else
+        {
+            (size * window_size).map(|x| x as u32 + 1)
+        };
+
+        let mut animated = false;
+
+        // the textures only get looked up once per tile id, not once per position
+        let draws: Vec<_> = {
+            let assets = self.assets.borrow();
+
+            let mut resolved: Vec<Option<Option<ResolvedTile>>> = Vec::new();
+
+            tiles.filter(|(_, tile, _, _)| !tile.is_none()).filter_map(|(pos, tile, seed, tint)|
+            {
+                let id = tile.id();
+                if id >= resolved.len()
+                {
+                    resolved.resize_with(id + 1, || None);
+                }
+
+                let resolved = resolved[id].get_or_insert_with(||
+                {
+                    assets.resolve_tile(tile, scaled_size)
+                }).as_ref()?;
+
+                animated |= resolved.animated;
+
+                let (texture, source) = resolved.pick(pos, seed);
+
+                let mut pos = self.pos_to_view(pos);
+                pos.y = 1.0 - pos.y - size.y;
+
+                let scaled_pos = (pos * window_size).map(|x| x.floor() as i32);
+
+                let (x, y) = (scaled_pos.x, scaled_pos.y);
+                let destination = Rect::new(x, y, scaled_size.x, scaled_size.y);
+
+                Some((texture, source, destination, tint))
+            }).collect()
+        };
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        draws.into_iter().for_each(|(texture, source, destination, [r, g, b])|
+        {
+            renderer.copy_tinted(texture, source, destination, SdlColor::RGB(r, g, b));
+        });
+
+        animated
+    }
+
+    fn draw_grid(&self)
+    {
+        let (min, max) = self.visible_tiles();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+        let to_screen = |pos: Point2<i32>|
+        {
+            let mut pos = self.pos_to_view(pos);
+            pos.y = 1.0 - pos.y;
+
+            (pos * window_size).map(|x| x.floor() as i32)
+        };
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        renderer.set_color(SdlColor::RGB(60, 60, 60));
+
+        let bottom = to_screen(min).y;
+        let top = to_screen(max + 1).y;
+        for x in min.x..=(max.x + 1)
+        {
+            let x = to_screen(Point2::new(x, 0)).x;
+
+            renderer.draw_line(Point2::new(x, top), Point2::new(x, bottom));
+        }
+
+        let left = to_screen(min).x;
+        let right = to_screen(max + 1).x;
+        for y in min.y..=(max.y + 1)
+        {
+            let y = to_screen(Point2::new(0, y)).y;
+
+            renderer.draw_line(Point2::new(left, y), Point2::new(right, y));
+        }
+    }
+
+    fn draw_rulers(&self)
+    {
+        let (min, max) = self.visible_tiles();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+        let to_screen = |pos: Point2<i32>|
+        {
+            let mut pos = self.pos_to_view(pos);
+            pos.y = 1.0 - pos.y;
+
+            (pos * window_size).map(|x| x.floor() as i32)
+        };
+
+        let tile_pixels = window_size.y / self.camera.height;
+
+        // numbers every 1, 2, 5, 10, 20 and so on tiles so they dont overlap
+        let step = (0..24).map(|i| [1, 2, 5][i % 3] * 10_i32.pow(i as u32 / 3))
+            .find(|step| *step as f32 * tile_pixels >= 48.0)
+            .unwrap_or(i32::MAX);
+
+        let every_tile = tile_pixels >= 6.0;
+
+        // the top one goes under the menu bar
+        let top = 32;
+        let width = window_size.x as u32;
+        let height = window_size.y as u32;
+
+        let mut renderer = self.renderer.borrow_mut();
+        let assets = self.assets.borrow();
+
+        let label = |text: String| Label{
+            text,
+            font_size: Some(8.0),
+            color: SdlColor::RGB(200, 200, 200),
+            align: Align::Left
+        };
+
+        renderer.set_color(SdlColor::RGB(40, 40, 40));
+        renderer.fill_rect(Rect::new(0, top, width, RULER_SIZE as u32));
+        renderer.fill_rect(
+            Rect::new(0, top + RULER_SIZE, RULER_SIZE as u32, height.saturating_sub(top as u32))
+        );
+
+        let bottom = top + RULER_SIZE;
+        for x in min.x..=(max.x + 1)
+        {
+            let major = x % step == 0;
+            if !(major || every_tile)
+            {
+                continue;
+            }
+
+            let screen_x = to_screen(Point2::new(x, 0)).x;
+            if screen_x < RULER_SIZE
+            {
+                continue;
+            }
+
+            let length = if major { RULER_SIZE } else { RULER_SIZE / 3 };
+
+            renderer.set_color(SdlColor::RGB(150, 150, 150));
+            renderer.draw_line(
+                Point2::new(screen_x, bottom - length),
+                Point2::new(screen_x, bottom)
+            );
+
+            if major
+            {
+                let pos = Point2::new(screen_x + 2, top + 2);
+                let size = Point2::new(64, 8);
+
+                draw_label(&mut *renderer, &assets, &label(x.to_string()), 255, pos, size);
+            }
+        }
+
+        for y in min.y..=(max.y + 1)
+        {
+            let major = y % step == 0;
+            if !(major || every_tile)
+            {
+                continue;
+            }
+
+            // the bottom edge of the tile
+            let screen_y = to_screen(Point2::new(0, y)).y;
+            if screen_y < bottom
+            {
+                continue;
+            }
+
+            let length = if major { RULER_SIZE } else { RULER_SIZE / 3 };
+
+            renderer.set_color(SdlColor::RGB(150, 150, 150));
+            renderer.draw_line(
+                Point2::new(RULER_SIZE - length, screen_y),
+                Point2::new(RULER_SIZE, screen_y)
+            );
+
+            if major && screen_y - 10 >= bottom
+            {
+                let pos = Point2::new(2, screen_y - 10);
+                let size = Point2::new(64, 8);
+
+                draw_label(&mut *renderer, &assets, &label(y.to_string()), 255, pos, size);
+            }
+        }
+    }
+
+    // links get an outline and the name of where they go if it fits, a link thats still
+    // being placed gets a white one
+    fn draw_links(&self)
+    {
+        let Some(scene) = self.scenes.get(self.current_scene)
+        else
+        {
+            return;
+        };
+
+        let (min, max) = self.visible_tiles();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+        let tile_pixels = window_size.y / self.camera.height;
+
+        let linking = self.linking.filter(|(scene, _)| *scene == self.current_scene)
+            .map(|(_, pos)| (pos, None));
+
+        let links = scene.links().map(|(pos, link)| (pos, Some(link))).chain(linking)
+            .filter(|(pos, _)|
+            {
+                pos.x >= min.x && pos.y >= min.y && pos.x <= max.x && pos.y <= max.y
+            });
+
+        let mut renderer = self.renderer.borrow_mut();
+        let assets = self.assets.borrow();
+
+        links.for_each(|(pos, link)|
+        {
+            let mut top_left = self.pos_to_view(pos + Point2::new(0, 1));
+            top_left.y = 1.0 - top_left.y;
+
+            let start = (top_left * window_size).map(|x| x.floor() as i32 + 1);
+            let size = (self.tile_size() * window_size)
+                .map(|x| (x as u32).saturating_sub(1).max(1));
+
+            let color = if link.is_some() { SdlColor::RGB(220, 100, 255) } else { SdlColor::WHITE };
+
+            renderer.set_color(color);
+            renderer.draw_rect(Rect::new(start.x, start.y, size.x, size.y));
+
+            if let Some(link) = link.filter(|_| tile_pixels >= 32.0)
+            {
+                let label = Label{
+                    text: self.scene_name(link.scene),
+                    font_size: None,
+                    color,
+                    align: Align::Center
+                };
+
+                let pos = Point2::new(start.x + 2, start.y + size.y as i32 * 2 / 3);
+                let size = Point2::new(size.x.saturating_sub(4), size.y / 4);
+
+                draw_label(&mut *renderer, &assets, &label, 255, pos, size);
+            }
+        });
+    }
+
+    fn draw_scene_diff(&self)
+    {
+        let Some(diff) = self.scene_diff.as_ref().filter(|diff| diff.scene == self.current_scene)
+        else
+        {
+            return;
+        };
+
+        let (min, max) = self.visible_tiles();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        diff.cells.iter().filter(|(pos, _)|
+        {
+            pos.x >= min.x && pos.y >= min.y && pos.x <= max.x && pos.y <= max.y
+        }).for_each(|(pos, change)|
+        {
+            let mut top_left = self.pos_to_view(*pos + Point2::new(0, 1));
+            top_left.y = 1.0 - top_left.y;
+
+            let start = (top_left * window_size).map(|x| x.floor() as i32 + 1);
+            let size = (self.tile_size() * window_size)
+                .map(|x| (x as u32).saturating_sub(1).max(1));
+
+            let color = match change
+            {
+                CellChange::Added => SdlColor::RGB(80, 220, 80),
+                CellChange::Removed => SdlColor::RGB(255, 80, 80),
+                CellChange::Changed => SdlColor::RGB(80, 160, 255)
+            };
+
+            renderer.set_color(color);
+            renderer.draw_rect(Rect::new(start.x, start.y, size.x, size.y));
+
+            // a second outline inside so its easier to see over busy tiles
+            if size.x > 4 && size.y > 4
+            {
+                renderer.draw_rect(Rect::new(start.x + 1, start.y + 1, size.x - 2, size.y - 2));
+            }
+        });
+    }
+
+    fn draw_selection(&self, selection: Selection)
+    {
+        let (min, max) = selection.bounds();
+
+        let window_size = self.view_area().1.map(|x| x as f32);
+
+        let mut bottom_left = self.pos_to_view(min);
+        bottom_left.y = 1.0 - bottom_left.y;
+
+        let mut top_right = self.pos_to_view(max + 1);
+        top_right.y = 1.0 - top_right.y;
+
+        let start = (Point2::new(bottom_left.x, top_right.y) * window_size)
+            .map(|x| x.floor() as i32);
+        let end = (Point2::new(top_right.x, bottom_left.y) * window_size)
+            .map(|x| x.floor() as i32);
+
+        let size = (end - start).map(|x| x.max(1) as u32);
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        renderer.set_color(SdlColor::RGB(255, 255, 0));
+        renderer.draw_rect(Rect::new(start.x, start.y, size.x, size.y));
+    }
+
+    // outlines the tile under the mouse with a see through version of what would get painted
+    fn draw_hover(&self, pos: Point2<i32>)
+    {
+        let window_size = self.view_area().1.map(|x| x as f32);
+
+        let mut top_left = self.pos_to_view(pos + Point2::new(0, 1));
+        top_left.y = 1.0 - top_left.y;
+
+        let start = (top_left * window_size).map(|x| x.floor() as i32);
+        let size = (self.tile_size() * window_size).map(|x| (x as u32 + 1).max(2));
+
+        let destination = Rect::new(start.x, start.y, size.x, size.y);
+
+        let ghost = matches!(self.tool, Tool::Paint | Tool::Fill) && !self.current_tile.is_none();
+        if ghost
+        {
+            let seed = self.scenes[self.current_scene].variant_seed(pos);
+
+            let assets = self.assets.borrow();
+            if let Some(texture_id) = assets.tile_variant_texture_id(self.current_tile, pos, seed)
+            {
+                let (texture, source) = assets.texture(texture_id);
+
+                let [r, g, b] = self.brush_tint;
+
+                let tint = SdlColor::RGBA(r, g, b, 120);
+                self.renderer.borrow_mut().copy_tinted(texture, source, destination, tint);
+            }
+        }
+
+        let mut renderer = self.renderer.borrow_mut();
+
+        renderer.set_color(SdlColor::RGB(255, 255, 255));
+        renderer.draw_rect(destination);
+    }
+
+    fn screen_to_local(&self, pos: Point2<i32>) -> Point2<f32>
+    {
+        let mut pos = pos.map(|x| x as f32) / self.window_size.map(|x| x as f32);
+        pos.y = 1.0 - pos.y;
+
+        pos
+    }
+
+    // the part of the window the scene gets drawn in, the split view gives each camera half
+    fn view_area(&self) -> (i32, Point2<usize>)
+    {
+        if let Some(size) = self.view_override
+        {
+            return (0, size);
+        }
+
+        match self.split.as_ref()
+        {
+            Some(split) =>
+            {
+                let half = self.window_size.x / 2;
+                let offset = if split.active_right { half as i32 } else { 0 };
+
+                (offset, Point2::new(half, self.window_size.y))
+            },
+            None => (0, self.window_size)
+        }
+    }
+
+    fn view_aspect(&self) -> f32
+    {
+        let (_, size) = self.view_area();
+
+        size.x as f32 / size.y as f32
+    }
+
+    // width of a tile compared to its height
+    fn tile_aspect(&self) -> f32
+    {
+        self.tile_pixels.map(|size| size.x as f32 / size.y.max(1) as f32).unwrap_or(1.0)
+    }
+
+    // the project says it or its guessed from the tiles
+    fn tile_pixel_size(&self) -> Option<Point2<usize>>
+    {
+        self.tile_pixels.or_else(|| tileset::common_size(&self.assets.borrow().tile_sources()))
+    }
+
+    // the camera x is in view heights so both the view and the tile shape matter
+    fn tile_to_camera(&self, pos: Point2<f32>) -> Point2<f32>
+    {
+        Point2::new(pos.x * self.tile_aspect() / self.view_aspect(), pos.y)
+    }
+
+    fn camera_to_tile(&self, pos: Point2<f32>) -> Point2<f32>
+    {
+        Point2::new(pos.x * self.view_aspect() / self.tile_aspect(), pos.y)
+    }
+
+    fn screen_to_pos(&self, pos: Point2<i32>) -> Point2<i32>
+    {
+        let (offset, size) = self.view_area();
+
+        let mut pos = (pos - Point2::new(offset, 0)).map(|x| x as f32) / size.map(|x| x as f32);
+        pos.y = 1.0 - pos.y;
+
+        let scaled_pos = self.camera.pos / self.camera.height as f32;
+
+        let f_pos = (pos + scaled_pos - 0.5) / self.tile_size();
+
+        f_pos.map(|x| x.floor() as i32)
+    }
+
+    fn pos_to_view(&self, pos: Point2<i32>) -> Point2<f32>
+    {
+        self.point_to_view(pos.map(|x| x as f32))
+    }
+
+    fn point_to_view(&self, pos: Point2<f32>) -> Point2<f32>
+    {
+        pos * self.tile_size() - (self.camera.pos / self.camera.height as f32) + 0.5
+    }
+
+    fn scene_tabs_layout() -> Layout
+    {
+        Layout::new(
+            Anchor::TopRight,
+            Point2::new(Length::Relative(0.5), Length::Absolute(32.0))
+        )
+    }
+
+    fn scene_name(&self, index: usize) -> String
+    {
+        self.scenes.get(index).and_then(|scene| scene.name())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("scene {index}"))
+    }
+
+    fn scene_tab_text(&self, index: usize) -> String
+    {
+        match self.renaming_scene
+        {
+            Some((renaming, _)) if renaming == index =>
+            {
+                let name = self.scenes[index].name().unwrap_or("");
+
+                format!("{name}_")
+            },
+            _ => self.scene_name(index)
+        }
+    }
+
+    // only rebuilds the tabs if something about them changed
+    fn update_scene_tabs(&mut self)
+    {
+        let tabs: Vec<String> = (0..self.scenes.len())
+            .map(|index| self.scene_tab_text(index))
+            .collect();
+
+        let shown = (tabs, self.current_scene, self.window_size);
+
+        if self.scene_tabs_shown.as_ref() == Some(&shown)
+        {
+            return;
+        }
+
+        let texture = |name: &str| self.assets.borrow().ui_texture(name);
+
+        self.ui.replace(&self.scene_tabs, UiElement{
+            kind: UiElementType::Panel,
+            pos: Point2::new(0.0, 0.0),
+            size: Point2::new(0.0, 0.0),
+            texture: texture("background"),
+            color: None,
+            alpha: 255
+        });
+
+        self.ui.set_layout(&self.scene_tabs, Self::scene_tabs_layout());
+
+        // they get narrower when theres a lot of them, the last one adds a new scene
+        let tabs_amount = shown.0.len() + 1;
+        let tabs_width = self.window_size.x as f32 * 0.5;
+        let tab_width = (tabs_width / tabs_amount as f32).min(96.0);
+
+        let gap = 2.0;
+        let pixels = |x: f32, y: f32| Point2::new(Length::Absolute(x), Length::Absolute(y));
+
+        (0..tabs_amount).for_each(|index|
+        {
+            let text = shown.0.get(index);
+            let selected = index == self.current_scene;
+
+            let texture_name = match (text, selected)
+            {
+                (None, _) => "plus",
+                (Some(_), true) => "white",
+                (Some(_), false) => "background"
+            };
+
+            let tab = self.ui.push_child_anchored(
+                &self.scene_tabs,
+                UiElement{
+                    kind: UiElementType::Button,
+                    pos: Point2::new(0.0, 0.0),
+                    size: Point2::new(0.0, 0.0),
+                    texture: texture(texture_name),
+                    color: None,
+                    alpha: 255
+                },
+                Layout::new(Anchor::TopLeft, pixels(tab_width - gap, 32.0))
+                    .with_margin(pixels(index as f32 * tab_width, 0.0))
+            );
+
+            if let Some(text) = text
+            {
+                self.ui.push_child(&tab, UiElement{
+                    kind: UiElementType::Label(Label{
+                        text: text.clone(),
+                        font_size: None,
+                        color: if selected { SdlColor::BLACK } else { SdlColor::WHITE },
+                        align: Align::Center
+                    }),
+                    pos: Point2::new(0.1, 0.2),
+                    size: Point2::new(0.8, 0.6),
+                    texture: texture("white"),
+                    color: None,
+                    alpha: 255
+                });
+
+                self.ui.on_click(&tab, UiMessage::Scene(index));
+            } else
+            {
+                self.ui.on_click(&tab, UiMessage::NewScene);
+            }
+        });
+
+        self.scene_tabs_shown = Some(shown);
+    }
+
+    fn delete_scene(&mut self, index: usize)
+    {
+        if index >= self.scenes.len()
+        {
+            return;
+        }
+
+        self.scenes.remove(index);
+
+        // the scenes after it move back by 1
+        self.remap_scenes(|scene_index|
+        {
+            match scene_index.cmp(&index)
+            {
+                Ordering::Less => Some(scene_index),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(scene_index - 1)
+            }
+        });
+
+        if self.current_scene > index || self.current_scene >= self.scenes.len()
+        {
+            self.current_scene = self.current_scene.saturating_sub(1);
+        }
+
+        self.ensure_current_scene();
+    }
+
+    fn swap_scenes(&mut self, a: usize, b: usize)
+    {
+        if a == b || a >= self.scenes.len() || b >= self.scenes.len()
+        {
+            return;
+        }
+
+        self.scenes.swap(a, b);
+
+        let swapped = |index: usize|
+        {
+            if index == a
+            {
+                b
+            } else if index == b
+            {
+                a
+            } else
+            {
+                index
+            }
+        };
+
+        self.current_scene = swapped(self.current_scene);
+        self.remap_scenes(|index| Some(swapped(index)));
+    }
+
+    // keeps everything that points at a scene (except the current one) pointing at the same
+    // scene after theyre moved around, none means that scene is gone
+    fn remap_scenes(&mut self, remap: impl Fn(usize) -> Option<usize>)
+    {
+        self.scenes.iter_mut().for_each(|scene| scene.retarget_links(&remap));
+
+        self.linking = self.linking.and_then(|(scene, pos)| Some((remap(scene)?, pos)));
+
+        self.history.remap_scenes(&remap);
+
+        if let Some(split) = self.split.as_mut()
+        {
+            split.scene = remap(split.scene).unwrap_or(split.scene.saturating_sub(1));
+        }
+
+        self.last_export = self.last_export.take().and_then(|mut settings|
+        {
+            settings.scene = remap(settings.scene)?;
+
+            Some(settings)
+        });
+
+        self.renaming_scene = self.renaming_scene.take().and_then(|(index, old_name)|
+        {
+            remap(index).map(|index| (index, old_name))
+        });
+
+        self.properties_dialog = self.properties_dialog.take().and_then(|mut dialog|
+        {
+            dialog.scene = remap(dialog.scene)?;
+
+            Some(dialog)
+        });
+
+        self.scene_diff = self.scene_diff.take().and_then(|mut diff|
+        {
+            diff.scene = remap(diff.scene)?;
+
+            if let DiffTarget::Scene(index) = &mut diff.target
+            {
+                *index = remap(*index)?;
+            }
+
+            Some(diff)
+        });
+    }
+
+    fn compare_with_saved(&mut self, index: usize)
+    {
+        let path = self.save_path.clone();
+        let saved = self.load_project(&path).and_then(|project| project.into_scenes());
+
+        match saved
+        {
+            Ok(mut scenes) if index < scenes.len() =>
+            {
+                let target = DiffTarget::Saved(Box::new(scenes.swap_remove(index)));
+
+                self.compare_scene(index, target, "the saved one");
+            },
+            Ok(_) => self.toast(format!("{} isnt saved yet", self.scene_name(index))),
+            Err(err) =>
+            {
+                eprintln!("cant load {}: {err}", self.save_path.display());
+                self.toast(format!("cant load {}: {err}", self.save_path.display()));
+            }
+        }
+    }
+
+    fn compare_scene(&mut self, scene: usize, target: DiffTarget, target_name: &str)
+    {
+        self.scene_diff = Some(SceneDiff{scene, target, revisions: None, cells: Vec::new()});
+
+        self.update_scene_diff();
+
+        let Some(diff) = self.scene_diff.as_ref()
+        else
+        {
+            return;
+        };
+
+        let amount = |kind| diff.cells.iter().filter(|(_, change)| *change == kind).count();
+
+        let message = format!(
+            "{} against {target_name}: {} added, {} removed, {} changed",
+            self.scene_name(scene),
+            amount(CellChange::Added),
+            amount(CellChange::Removed),
+            amount(CellChange::Changed)
+        );
+
+        self.toast(message);
+    }
+
+    // only looks again when either scene got edited
+    fn update_scene_diff(&mut self)
+    {
+        let Some(diff) = self.scene_diff.as_mut()
+        else
+        {
+            return;
+        };
+
+        let target = match &diff.target
+        {
+            DiffTarget::Scene(index) => &self.scenes[*index],
+            DiffTarget::Saved(scene) => scene
+        };
+
+        let scene = &self.scenes[diff.scene];
+
+        let revisions = Some((target.revision(), scene.revision()));
+        if diff.revisions == revisions
+        {
+            return;
+        }
+
+        diff.revisions = revisions;
+        diff.cells = diff::changed_cells(target, scene).into_iter().filter_map(|(pos, old, new)|
+        {
+            CellChange::between(old, new).map(|change| (pos, change))
+        }).collect();
+    }
+
+    // returns if the key went into the name
+    fn rename_scene_input(&mut self, key: Keycode) -> bool
+    {
+        let Some((index, old_name)) = self.renaming_scene.clone()
+        else
+        {
+            return false;
+        };
+
+        match key
+        {
+            Keycode::Return | Keycode::KpEnter => self.renaming_scene = None,
+            Keycode::Escape =>
+            {
+                self.scenes[index].set_name(old_name);
+                self.renaming_scene = None;
+            },
+            Keycode::Backspace =>
+            {
+                let mut name = self.scenes[index].name().unwrap_or("").to_owned();
+                name.pop();
+
+                self.scenes[index].set_name((!name.is_empty()).then_some(name));
+            },
+            _ => ()
+        }
+
+        true
+    }
+
+    fn resize(&mut self, window_size: Point2<usize>)
+    {
+        self.window.borrow_mut().window_size = window_size.map(|x| x as u32);
+
+        self.window_size = window_size;
+        self.aspect = window_size.x as f32 / window_size.y as f32;
+
+        self.ui.resize();
+
+        if let Some(dialog) = self.confirm_dialog.as_mut()
+        {
+            dialog.ui.resize();
+        }
+
+        if let Some(dialog) = self.properties_dialog.as_mut()
+        {
+            dialog.ui.resize();
+        }
+
+        if let Some(dialog) = self.new_scene_dialog.as_mut()
+        {
+            dialog.ui.resize();
+        }
+
+        if let Some(running) = self.task.as_mut()
+        {
+            running.ui.resize();
+        }
+
+        // its placed for the old size and goes away on the next click anyway
+        self.context_menu = None;
+
+        let (panel_pos, panel_size) = Self::tiles_panel_area(self.aspect);
+
+        self.tiles_panel_pos = panel_pos;
+        self.tiles_panel_size = panel_size;
+
+        (self.tiles_window_animator_open, self.tiles_window_animator_close) =
+            Self::tiles_window_animators(panel_pos, panel_size);
+
+        self.rebuild_tiles_ui();
+    }
+
+    fn toast(&mut self, text: impl Into<String>)
+    {
+        let size = Point2::new(0.35, 0.06);
+        let margin = 0.01;
+
+        // the y gets set every frame depending on where its in the queue
+        let panel = self.toasts_ui.push(UiElement{
+            kind: UiElementType::Panel,
+            pos: Point2::new(1.0, 0.0),
+            size,
+            texture: self.assets.borrow().ui_texture("panel"),
+            color: None,
+            alpha: 0
+        });
+
+        self.toasts_ui.push_child(&panel, UiElement{
+            kind: UiElementType::Label(Label{
+                text: text.into(),
+                font_size: None,
+                color: SdlColor::WHITE,
+                align: Align::Left
+            }),
+            pos: Point2::new(0.05, 0.2),
+            size: Point2::new(0.9, 0.6),
+            texture: self.assets.borrow().ui_texture("white"),
+            color: None,
+            alpha: 255
+        });
+
+        let slide = Animator::new(vec![
+            AnimatedValue{
+                id: UiAnimatableId::PositionX,
+                range: 1.0..=(1.0 - size.x - margin),
+                curve: ValueAnimation::EaseOut(2.0),
+                duration: 0.0..=1.0
+            },
+            AnimatedValue{
+                id: UiAnimatableId::Alpha,
+                range: 0.0..=255.0,
+                curve: ValueAnimation::Linear,
+                duration: 0.0..=0.5
+            }
+        ], Duration::from_millis(300));
+
+        self.toasts_ui.animate(&panel, slide.clone());
+
+        self.toasts.push_back(Toast{shown: Instant::now(), slide, leaving: false});
+    }
+
+    fn update_toasts(&mut self)
+    {
+        let extra = self.toasts.iter().filter(|toast| !toast.leaving).count()
+            .saturating_sub(MAX_TOASTS);
+
+        // bottom of the stack is just above the status bar
+        let bottom = 32.0 / self.window_size.y as f32;
+        let gap = 0.01;
+
+        self.toasts.iter_mut().enumerate().filter(|(_, toast)| !toast.leaving).enumerate()
+            .for_each(|(order, (index, toast))|
+            {
+                if order < extra || toast.shown.elapsed() > TOAST_DURATION
+                {
+                    toast.leaving = true;
+
+                    self.toasts_ui.animate(&ElementId::new(index), toast.slide.reversed());
+                }
+            });
+
+        let total = self.toasts.len();
+        (0..total).for_each(|index|
+        {
+            let panel = self.toasts_ui.get(&ElementId::new(index));
+            let mut panel = panel.borrow_mut();
+
+            // newest at the bottom
+            let above = total - 1 - index;
+            let height = panel.area().1.y;
+
+            panel.set(&UiAnimatableId::PositionY, bottom + above as f32 * (height + gap));
+        });
+
+        while self.toasts.front().map(|toast| toast.leaving).unwrap_or(false)
+            && !self.toasts_ui.is_animating(&ElementId::new(0))
+        {
+            self.toasts.pop_front();
+            self.toasts_ui.remove(&ElementId::new(0));
+        }
+    }
+
+    // the size of the current tiles image if its not the project tile size (or a multiple)
+    fn current_tile_mismatch(&self) -> Option<Point2<usize>>
+    {
+        let tile_size = self.tile_pixels?;
+
+        let assets = self.assets.borrow();
+        assets.try_tile_texture_id(self.current_tile)?;
+
+        let size = assets.tile_source(self.current_tile).size;
+
+        (!tileset::fits_size(tile_size, size)).then_some(size)
+    }
+
+    fn update_status_label(&mut self)
+    {
+        let tile_pos = self.screen_to_pos(self.mouse_pos);
+        let zoom = CAMERA_HEIGHT / self.camera.height * 100.0;
+
+        let mut tile_name = self.assets.borrow().tile_display_name(self.current_tile)
+            .unwrap_or("unknown")
+            .to_owned();
+
+        // the startup warning is easy to forget about
+        if let Some(size) = self.current_tile_mismatch()
+        {
+            tile_name += &format!(" ({}x{} doesnt fit the tile size)", size.x, size.y);
+        }
+
+        let text = format!(
+            "x {} y {} | zoom {zoom:.0}% | {} | {tile_name}",
+            tile_pos.x,
+            tile_pos.y,
+            self.scene_name(self.current_scene)
+        );
+
+        if let Some(label) = self.ui.get(&self.status_label).borrow_mut().label()
+        {
+            label.text = text;
+        }
+    }
+
+    fn update_window_title(&mut self)
+    {
+        let title = format!("tile thingeringy - {}", self.scene_name(self.current_scene));
+
+        if title != self.window_title
+        {
+            self.window.borrow().set_title(&title);
+
+            self.window_title = title;
+        }
+    }
+
+    fn pressed(&self, control: ControlName) -> bool
+    {
+        self.controls[control as usize]
+    }
+}
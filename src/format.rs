@@ -0,0 +1,105 @@
+use std::{
+    borrow::Cow,
+    path::PathBuf
+};
+
+use crate::{Point2, Tile, Scene};
+
+pub mod godot;
+pub mod project;
+pub mod backup;
+pub mod prefab;
+pub mod template;
+pub mod source;
+pub mod binary;
+
+
+// double quoted with backslash escapes, the same for godot, rust and c
+fn quoted(text: &str) -> String
+{
+    let escaped: String = text.chars().map(|c|
+    {
+        match c
+        {
+            '\\' => "\\\\".to_owned(),
+            '"' => "\\\"".to_owned(),
+            '\n' => "\\n".to_owned(),
+            '\t' => "\\t".to_owned(),
+            c => c.to_string()
+        }
+    }).collect();
+
+    format!("\"{escaped}\"")
+}
+
+// where the pixels of a tile come from, exporters reference these instead of sdl textures
+#[derive(Debug, Clone)]
+pub struct TileSource
+{
+    pub path: PathBuf,
+    pub pos: Point2<usize>,
+    pub size: Point2<usize>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat
+{
+    Godot,
+    Rust,
+    C,
+    Binary
+}
+
+impl ExportFormat
+{
+    pub fn extension(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Godot => "tscn",
+            Self::Rust => "rs",
+            Self::C => "h",
+            Self::Binary => "ttmap"
+        }
+    }
+
+    pub fn to_bytes(
+        &self,
+        name: &str,
+        scene: &Scene,
+        tile_size: Option<Point2<usize>>,
+        tile_source: impl Fn(Tile) -> TileSource,
+        tile_name: impl Fn(Tile) -> Option<String>
+    ) -> Vec<u8>
+    {
+        match self
+        {
+            Self::Godot => godot::to_tscn(name, scene, tile_size, tile_source).into_bytes(),
+            Self::Rust => source::to_rust(name, scene, tile_name).into_bytes(),
+            Self::C => source::to_c(name, scene, tile_name).into_bytes(),
+            Self::Binary => binary::to_bytes(scene)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions
+{
+    // crop to the non empty tiles
+    pub crop: bool,
+    pub padding: usize
+}
+
+impl ExportOptions
+{
+    pub fn apply<'a>(&self, scene: &'a Scene) -> Cow<'a, Scene>
+    {
+        if self.crop
+        {
+            Cow::Owned(scene.cropped(self.padding))
+        } else
+        {
+            Cow::Borrowed(scene)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    fs,
+    io,
+    cmp::Reverse,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH}
+};
+
+
+// copies of a file taken right before it gets overwritten, only the newest few are kept
+#[derive(Debug, Clone)]
+pub struct Backups
+{
+    pub directory: PathBuf,
+    pub amount: usize
+}
+
+impl Default for Backups
+{
+    fn default() -> Self
+    {
+        Self{directory: PathBuf::from("backups"), amount: 5}
+    }
+}
+
+impl Backups
+{
+    pub fn save_over(
+        &self,
+        path: impl AsRef<Path>,
+        f: impl FnOnce(&Path) -> io::Result<()>
+    ) -> io::Result<()>
+    {
+        let path = path.as_ref();
+
+        if path.exists() && self.amount > 0
+        {
+            self.backup(path)?;
+        }
+
+        f(path)
+    }
+
+    pub fn backup(&self, path: &Path) -> io::Result<()>
+    {
+        fs::create_dir_all(&self.directory)?;
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|x| x.as_millis())
+            .unwrap_or(0);
+
+        let name = Self::file_name(path);
+        fs::copy(path, self.directory.join(format!("{name}.{timestamp}")))?;
+
+        self.list(path)?.into_iter().skip(self.amount).try_for_each(fs::remove_file)
+    }
+
+    // newest first
+    pub fn list(&self, path: impl AsRef<Path>) -> io::Result<Vec<PathBuf>>
+    {
+        if !self.directory.exists()
+        {
+            return Ok(Vec::new());
+        }
+
+        let prefix = format!("{}.", Self::file_name(path.as_ref()));
+
+        let mut backups = fs::read_dir(&self.directory)?.filter_map(|entry|
+        {
+            let path = entry.ok()?.path();
+
+            let timestamp: u128 = path.file_name()?.to_str()?.strip_prefix(&prefix)?
+                .parse().ok()?;
+
+            Some((timestamp, path))
+        }).collect::<Vec<_>>();
+
+        backups.sort_by_key(|(timestamp, _)| Reverse(*timestamp));
+
+        Ok(backups.into_iter().map(|(_, path)| path).collect())
+    }
+
+    fn file_name(path: &Path) -> String
+    {
+        path.file_name().map(|x| x.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+}
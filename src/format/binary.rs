@@ -0,0 +1,241 @@
+use std::{
+    fs::File,
+    io,
+    path::Path
+};
+
+use memmap2::Mmap;
+
+use crate::{Point2, Tile, Scene};
+
+
+// header is magic, version, width, height, offset x, offset y then u32 tile ids row by row
+// from the bottom, everything little endian, after the tiles theres the amount of scene
+// properties and then each key and value as a u32 length and that many utf8 bytes
+const MAGIC: &[u8; 8] = b"TTMAP\0\0\0";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = MAGIC.len() + 4 * 5;
+
+pub fn to_bytes(scene: &Scene) -> Vec<u8>
+{
+    let container = scene.to_container();
+    let size = container.size();
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + container.data().len() * 4);
+
+    bytes.extend(MAGIC);
+    bytes.extend(VERSION.to_le_bytes());
+    bytes.extend((size.x as u32).to_le_bytes());
+    bytes.extend((size.y as u32).to_le_bytes());
+    bytes.extend(scene.offset().x.to_le_bytes());
+    bytes.extend(scene.offset().y.to_le_bytes());
+
+    container.data().iter().for_each(|tile| bytes.extend((tile.id() as u32).to_le_bytes()));
+
+    let properties: Vec<_> = scene.properties().collect();
+
+    bytes.extend((properties.len() as u32).to_le_bytes());
+    properties.into_iter().flat_map(|(key, value)| [key, value]).for_each(|text|
+    {
+        bytes.extend((text.len() as u32).to_le_bytes());
+        bytes.extend(text.as_bytes());
+    });
+
+    bytes
+}
+
+// reads tiles straight from the file, nothing gets loaded into memory upfront
+pub struct MappedScene
+{
+    map: Mmap,
+    size: Point2<usize>,
+    offset: Point2<i32>
+}
+
+impl MappedScene
+{
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let file = File::open(path)?;
+
+        // changing the file while its mapped just shows garbage tiles, but if it gets
+        // truncated reading past the new end crashes with SIGBUS, copying it all would
+        // throw away the whole point of mapping it so just dont shrink open map files
+        let map = unsafe{ Mmap::map(&file)? };
+
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        if map.len() < HEADER_SIZE || &map[..MAGIC.len()] != MAGIC
+        {
+            return Err(invalid("not a binary map file"));
+        }
+
+        let field = |index: usize|
+        {
+            let start = MAGIC.len() + index * 4;
+
+            <[u8; 4]>::try_from(&map[start..start + 4]).unwrap()
+        };
+
+        if u32::from_le_bytes(field(0)) != VERSION
+        {
+            return Err(invalid("unsupported binary map version"));
+        }
+
+        let size = Point2::new(u32::from_le_bytes(field(1)), u32::from_le_bytes(field(2)))
+            .map(|x| x as usize);
+
+        let offset = Point2::new(i32::from_le_bytes(field(3)), i32::from_le_bytes(field(4)));
+
+        // the sizes come straight from the file so they can be anything
+        let tiles_end = size.x.checked_mul(size.y)
+            .and_then(|x| x.checked_mul(4))
+            .and_then(|x| x.checked_add(HEADER_SIZE));
+
+        if tiles_end.map(|end| map.len() < end).unwrap_or(true)
+        {
+            return Err(invalid("binary map file is truncated"));
+        }
+
+        Ok(Self{map, size, offset})
+    }
+
+    pub fn size(&self) -> Point2<usize>
+    {
+        self.size
+    }
+
+    pub fn offset(&self) -> Point2<i32>
+    {
+        self.offset
+    }
+
+    // files from before properties were a thing just dont have any
+    pub fn properties(&self) -> Vec<(String, String)>
+    {
+        let mut rest = &self.map[HEADER_SIZE + self.size.x * self.size.y * 4..];
+
+        let read_u32 = |rest: &mut &[u8]| -> Option<u32>
+        {
+            let value = u32::from_le_bytes(rest.get(..4)?.try_into().unwrap());
+            *rest = &rest[4..];
+
+            Some(value)
+        };
+
+        let read_text = |rest: &mut &[u8]| -> Option<String>
+        {
+            let length = read_u32(rest)? as usize;
+            let text = String::from_utf8_lossy(rest.get(..length)?).into_owned();
+            *rest = &rest[length..];
+
+            Some(text)
+        };
+
+        let amount = read_u32(&mut rest).unwrap_or(0);
+
+        (0..amount).map_while(|_|
+        {
+            Some((read_text(&mut rest)?, read_text(&mut rest)?))
+        }).collect()
+    }
+
+    pub fn get(&self, pos: Point2<i32>) -> Option<Tile>
+    {
+        let local = pos + self.offset;
+
+        let inside = local.x >= 0 && local.y >= 0
+            && (local.x as usize) < self.size.x && (local.y as usize) < self.size.y;
+
+        if !inside
+        {
+            return None;
+        }
+
+        let index = local.y as usize * self.size.x + local.x as usize;
+        let start = HEADER_SIZE + index * 4;
+
+        let id = u32::from_le_bytes(self.map[start..start + 4].try_into().unwrap());
+
+        Some(Tile::from_id(id as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    // every test gets its own file so they can run at the same time
+    fn temp_file(name: &str, bytes: &[u8]) -> PathBuf
+    {
+        let path = std::env::temp_dir()
+            .join(format!("tilesthingeringy_{}_{name}.ttmap", std::process::id()));
+
+        fs::write(&path, bytes).unwrap();
+
+        path
+    }
+
+    fn test_scene() -> Scene
+    {
+        let mut scene = Scene::new(Point2::new(3, 2), Point2::new(1, -1));
+
+        scene[Point2::new(-1, 1)] = Tile::new(0);
+        scene[Point2::new(1, 2)] = Tile::new(4);
+        scene.set_property("music", "cave.ogg");
+
+        scene
+    }
+
+    #[test]
+    fn round_trip()
+    {
+        let scene = test_scene();
+        let path = temp_file("round_trip", &to_bytes(&scene));
+
+        let mapped = MappedScene::open(&path).unwrap();
+
+        assert_eq!(mapped.size(), scene.size());
+        assert_eq!(mapped.offset(), scene.offset());
+        assert_eq!(mapped.properties(), vec![("music".to_owned(), "cave.ogg".to_owned())]);
+
+        scene.iter().for_each(|(pos, tile)| assert_eq!(mapped.get(pos), Some(*tile)));
+
+        assert_eq!(mapped.get(Point2::new(100, 100)), None);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn truncated_file_is_an_error()
+    {
+        let bytes = to_bytes(&test_scene());
+
+        // cuts into the last tile
+        let path = temp_file("truncated", &bytes[..HEADER_SIZE + 3 * 2 * 4 - 2]);
+
+        let err = MappedScene::open(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn overflowing_size_is_an_error()
+    {
+        let mut bytes = to_bytes(&test_scene());
+
+        let size_start = MAGIC.len() + 4;
+        bytes[size_start..size_start + 8].copy_from_slice(&[0xff; 8]);
+
+        let path = temp_file("overflow", &bytes);
+
+        let err = MappedScene::open(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(path).unwrap();
+    }
+}
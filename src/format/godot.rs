@@ -0,0 +1,115 @@
+use std::{
+    fs,
+    io,
+    fmt::Write,
+    path::Path,
+    collections::BTreeMap
+};
+
+use crate::{Point2, Scene, Tile};
+
+use super::{TileSource, quoted};
+
+
+pub fn export(
+    path: impl AsRef<Path>,
+    name: &str,
+    scene: &Scene,
+    tile_size: Option<Point2<usize>>,
+    tile_source: impl Fn(Tile) -> TileSource
+) -> io::Result<()>
+{
+    fs::write(path, to_tscn(name, scene, tile_size, tile_source))
+}
+
+// godot 4 tscn with a TileMap node and an embedded TileSet, the tile size is the projects
+pub fn to_tscn(
+    name: &str,
+    scene: &Scene,
+    tile_size: Option<Point2<usize>>,
+    tile_source: impl Fn(Tile) -> TileSource
+) -> String
+{
+    // godot wants source ids for only the tiles that r actually used
+    let mut sources: BTreeMap<usize, usize> = BTreeMap::new();
+    scene.iter().filter(|(_, tile)| !tile.is_none()).for_each(|(_, tile)|
+    {
+        sources.entry(tile.id()).or_insert(0);
+    });
+
+    sources.values_mut().enumerate().for_each(|(index, source)| *source = index);
+
+    let tiles: Vec<TileSource> = sources.keys().map(|id| tile_source(Tile::from_id(*id))).collect();
+
+    let tile_size = tile_size.or_else(|| tiles.first().map(|tile| tile.size))
+        .unwrap_or(Point2::repeat(1));
+
+    let mut out = String::new();
+
+    let load_steps = tiles.len() * 2 + 2;
+    writeln!(out, "[gd_scene load_steps={load_steps} format=3]").unwrap();
+    writeln!(out).unwrap();
+
+    tiles.iter().enumerate().for_each(|(index, tile)|
+    {
+        let path = tile.path.to_string_lossy().replace('\\', "/");
+
+        writeln!(
+            out,
+            "[ext_resource type=\"Texture2D\" path=\"res://{path}\" id=\"{}\"]",
+            index + 1
+        ).unwrap();
+    });
+
+    writeln!(out).unwrap();
+
+    tiles.iter().enumerate().for_each(|(index, tile)|
+    {
+        writeln!(out, "[sub_resource type=\"TileSetAtlasSource\" id=\"TileSetAtlasSource_{index}\"]")
+            .unwrap();
+        writeln!(out, "texture = ExtResource(\"{}\")", index + 1).unwrap();
+        writeln!(out, "margins = Vector2i({}, {})", tile.pos.x, tile.pos.y).unwrap();
+        writeln!(out, "texture_region_size = Vector2i({}, {})", tile.size.x, tile.size.y).unwrap();
+        writeln!(out, "0:0/0 = 0").unwrap();
+        writeln!(out).unwrap();
+    });
+
+    writeln!(out, "[sub_resource type=\"TileSet\" id=\"TileSet_0\"]").unwrap();
+    writeln!(out, "tile_size = Vector2i({}, {})", tile_size.x, tile_size.y).unwrap();
+
+    (0..tiles.len()).for_each(|index|
+    {
+        writeln!(out, "sources/{index} = SubResource(\"TileSetAtlasSource_{index}\")").unwrap();
+    });
+
+    writeln!(out).unwrap();
+
+    let tile_data = scene.iter().filter(|(_, tile)| !tile.is_none()).map(|(pos, tile)|
+    {
+        // godot has y pointing down
+        let x = pos.x & 0xffff;
+        let y = (-pos.y) & 0xffff;
+
+        let coords = (y << 16) | x;
+        let source = sources[&tile.id()];
+
+        format!("{coords}, {source}, 0")
+    }).collect::<Vec<_>>().join(", ");
+
+    writeln!(out, "[node name=\"{name}\" type=\"TileMap\"]").unwrap();
+    writeln!(out, "tile_set = SubResource(\"TileSet_0\")").unwrap();
+    writeln!(out, "format = 2").unwrap();
+    writeln!(out, "layer_0/tile_data = PackedInt32Array({tile_data})").unwrap();
+
+    // shows up as metadata on the node, get_meta in a script reads it
+    scene.properties().for_each(|(key, value)|
+    {
+        let key: String = key.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        writeln!(out, "metadata/{key} = {}", quoted(value)).unwrap();
+    });
+
+    out
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    io,
+    path::Path,
+    collections::HashMap
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Point2, Tile, Scene};
+
+
+// tiles r stored by name so prefabs work in projects with different tile ids
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prefab
+{
+    pub size: Point2<usize>,
+    pub palette: Vec<String>,
+    // index into the palette plus 1, 0 is an empty tile
+    pub tiles: Vec<usize>
+}
+
+impl Prefab
+{
+    pub fn from_region(
+        scene: &Scene,
+        min: Point2<i32>,
+        max: Point2<i32>,
+        tile_name: impl Fn(Tile) -> Option<String>
+    ) -> Self
+    {
+        let size = (max - min).map(|x| x as usize + 1);
+
+        let mut palette = Vec::new();
+        let mut indices: HashMap<Tile, usize> = HashMap::new();
+
+        let tiles = (0..size.y).flat_map(|y| (0..size.x).map(move |x| Point2::new(x, y)))
+            .map(|local|
+            {
+                let tile = scene.get(min + local.map(|x| x as i32)).copied()
+                    .unwrap_or_else(Tile::none);
+
+                if tile.is_none()
+                {
+                    return 0;
+                }
+
+                *indices.entry(tile).or_insert_with(||
+                {
+                    palette.push(tile_name(tile).unwrap_or_default());
+
+                    palette.len()
+                })
+            }).collect();
+
+        Self{size, palette, tiles}
+    }
+
+    // pos is where the bottom left corner goes, returns the names it couldnt find
+    pub fn paste(
+        &self,
+        scene: &mut Scene,
+        pos: Point2<i32>,
+        tile: impl Fn(&str) -> Option<Tile>
+    ) -> Vec<String>
+    {
+        let mut missing = Vec::new();
+
+        let palette: Vec<Tile> = self.palette.iter().map(|name|
+        {
+            tile(name).unwrap_or_else(||
+            {
+                missing.push(name.clone());
+
+                Tile::none()
+            })
+        }).collect();
+
+        self.tiles.iter().enumerate().filter(|(_, index)| **index != 0)
+            .for_each(|(index, palette_index)|
+            {
+                let local = Point2::new(index % self.size.x, index / self.size.x);
+
+                let tile = palette[palette_index - 1];
+                if !tile.is_none()
+                {
+                    scene[pos + local.map(|x| x as i32)] = tile;
+                }
+            });
+
+        missing
+    }
+
+    pub fn is_valid(&self) -> bool
+    {
+        let valid_indices = self.tiles.iter().all(|index| *index <= self.palette.len());
+
+        self.size.x * self.size.y == self.tiles.len() && valid_indices
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        Self::from_text(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        fs::write(path, self.to_text())
+    }
+
+    // single line so it survives being pasted into chats
+    pub fn to_text(&self) -> String
+    {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_text(text: &str) -> io::Result<Self>
+    {
+        let this: Self = serde_json::from_str(text.trim()).map_err(io::Error::from)?;
+
+        if !this.is_valid()
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed prefab"));
+        }
+
+        Ok(this)
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    collections::{HashMap, BTreeMap, BTreeSet}
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Point2, Tile, Scene, scene::SceneLink, container::Container2d, tileset::TileFit};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneData
+{
+    pub offset: Point2<i32>,
+    pub size: Point2<usize>,
+    pub tiles: Vec<usize>,
+    // global positions of rerolled cells and their variant seeds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variant_seeds: Vec<(Point2<i32>, u32)>,
+    // same for tinted cells and their colors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tints: Vec<(Point2<i32>, [u8; 3])>,
+    // marker positions, the index of the scene they lead to and where in it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<(Point2<i32>, usize, Point2<i32>)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>
+}
+
+impl From<&Scene> for SceneData
+{
+    fn from(scene: &Scene) -> Self
+    {
+        let container = scene.to_container();
+
+        let mut variant_seeds: Vec<_> = scene.variant_seeds().collect();
+        variant_seeds.sort_unstable_by_key(|(pos, _)| (pos.y, pos.x));
+
+        let mut tints: Vec<_> = scene.tints().collect();
+        tints.sort_unstable_by_key(|(pos, _)| (pos.y, pos.x));
+
+        let mut links: Vec<_> = scene.links().map(|(pos, link)| (pos, link.scene, link.pos))
+            .collect();
+        links.sort_unstable_by_key(|(pos, _, _)| (pos.y, pos.x));
+
+        Self{
+            offset: scene.offset(),
+            size: *container.size(),
+            tiles: container.data().iter().map(|tile| tile.id()).collect(),
+            variant_seeds,
+            tints,
+            links,
+            name: scene.name().map(ToOwned::to_owned),
+            properties: scene.properties()
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect()
+        }
+    }
+}
+
+impl TryFrom<SceneData> for Scene
+{
+    type Error = io::Error;
+
+    fn try_from(data: SceneData) -> Result<Self, Self::Error>
+    {
+        if data.size.x * data.size.y != data.tiles.len()
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "scene size doesnt match its tiles"));
+        }
+
+        let tiles = data.tiles.into_iter().map(Tile::from_id).collect();
+
+        let mut scene = Scene::from_container(Container2d::from_raw(data.size, tiles), data.offset);
+        data.variant_seeds.into_iter().for_each(|(pos, seed)| scene.set_variant_seed(pos, seed));
+        data.tints.into_iter().for_each(|(pos, tint)| scene.set_tint(pos, tint));
+        data.links.into_iter().for_each(|(pos, target, target_pos)|
+        {
+            scene.set_link(pos, Some(SceneLink{scene: target, pos: target_pos}));
+        });
+        scene.set_name(data.name);
+        data.properties.into_iter().for_each(|(key, value)| scene.set_property(key, value));
+
+        Ok(scene)
+    }
+}
+
+// where the tiles and ui textures r, unset ones fall back to tiles and ui next to the editor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetDirectories
+{
+    // has the tiles and ui directories in it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assets_dir: Option<PathBuf>,
+    // for tiles that arent in the assets directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tiles_dir: Option<PathBuf>
+}
+
+impl AssetDirectories
+{
+    // whatever this sets wins over the other one
+    pub fn or(self, other: Self) -> Self
+    {
+        Self{
+            assets_dir: self.assets_dir.or(other.assets_dir),
+            tiles_dir: self.tiles_dir.or(other.tiles_dir)
+        }
+    }
+
+    pub fn tiles(&self) -> PathBuf
+    {
+        self.tiles_dir.clone().unwrap_or_else(|| self.assets().join("tiles"))
+    }
+
+    pub fn ui(&self) -> PathBuf
+    {
+        self.assets().join("ui")
+    }
+
+    fn assets(&self) -> PathBuf
+    {
+        self.assets_dir.clone().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Project
+{
+    // tileset definitions relative to the project file, tiles dir gets scanned if empty
+    #[serde(default)]
+    pub tilesets: Vec<PathBuf>,
+    // size in pixels every tile should be, guessed from the tiles if none
+    #[serde(default)]
+    pub tile_size: Option<Point2<usize>>,
+    // tiles that arent the tile size can get padded or scaled to it when theyre loaded
+    #[serde(default, skip_serializing_if = "TileFit::is_stretch")]
+    pub tile_fit: TileFit,
+    // relative to the project file like the tilesets
+    #[serde(flatten)]
+    pub directories: AssetDirectories,
+    // what the ids in the scenes were called when saving, tiles can get different ids
+    // whenever files get added or renamed so they get looked up by name again
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tile_names: BTreeMap<usize, String>,
+    pub scenes: Vec<SceneData>
+}
+
+impl Project
+{
+    pub fn from_scenes<'a>(scenes: impl IntoIterator<Item=&'a Scene>) -> Self
+    {
+        Self{
+            tilesets: Vec::new(),
+            tile_size: None,
+            tile_fit: TileFit::default(),
+            directories: AssetDirectories::default(),
+            tile_names: BTreeMap::new(),
+            scenes: scenes.into_iter().map(SceneData::from).collect()
+        }
+    }
+
+    // remembers the names of every tile the scenes use
+    pub fn name_tiles(&mut self, tile_name: impl Fn(Tile) -> Option<String>)
+    {
+        let ids: BTreeSet<usize> = self.scenes.iter()
+            .flat_map(|scene| scene.tiles.iter().copied())
+            .filter(|id| *id != 0)
+            .collect();
+
+        self.tile_names = ids.into_iter().filter_map(|id|
+        {
+            tile_name(Tile::from_id(id)).map(|name| (id, name))
+        }).collect();
+    }
+
+    // turns the saved ids into whatever the tiles with those names have now,
+    // returns the names that couldnt be found, those tiles get erased because
+    // their old ids might belong to some other tile by now
+    pub fn remap_tiles(&mut self, mut tile: impl FnMut(&str) -> Option<Tile>) -> Vec<String>
+    {
+        let mut missing = Vec::new();
+
+        let remap: HashMap<usize, usize> = self.tile_names.iter().map(|(id, name)|
+        {
+            let found = tile(name);
+            if found.is_none()
+            {
+                missing.push(name.clone());
+            }
+
+            (*id, found.unwrap_or_else(Tile::none).id())
+        }).collect();
+
+        self.scenes.iter_mut().flat_map(|scene| scene.tiles.iter_mut()).for_each(|id|
+        {
+            if let Some(new_id) = remap.get(id)
+            {
+                *id = *new_id;
+            }
+        });
+
+        self.tile_names = self.tile_names.iter().filter_map(|(id, name)|
+        {
+            let id = remap.get(id).copied().unwrap_or(*id);
+
+            (id != Tile::none().id()).then(|| (id, name.clone()))
+        }).collect();
+
+        missing
+    }
+
+    pub fn tileset_paths(&self, project_path: impl AsRef<Path>) -> Vec<PathBuf>
+    {
+        let parent = project_path.as_ref().parent().unwrap_or(Path::new(""));
+
+        self.tilesets.iter().map(|path| parent.join(path)).collect()
+    }
+
+    pub fn asset_directories(&self, project_path: impl AsRef<Path>) -> AssetDirectories
+    {
+        let parent = project_path.as_ref().parent().unwrap_or(Path::new(""));
+
+        AssetDirectories{
+            assets_dir: self.directories.assets_dir.as_ref().map(|path| parent.join(path)),
+            tiles_dir: self.directories.tiles_dir.as_ref().map(|path| parent.join(path))
+        }
+    }
+
+    pub fn into_scenes(self) -> io::Result<Vec<Scene>>
+    {
+        self.scenes.into_iter().map(Scene::try_from).collect()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let text = fs::read_to_string(path)?;
+
+        serde_json::from_str(&text).map_err(io::Error::from)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        let text = serde_json::to_string(self).map_err(io::Error::from)?;
+
+        fs::write(path, text)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn missing_tiles_get_erased()
+    {
+        let mut scene = Scene::new(Point2::new(2, 1), Point2::new(0, 0));
+        scene[Point2::new(0, 0)] = Tile::new(0);
+        scene[Point2::new(1, 0)] = Tile::new(1);
+
+        let mut project = Project::from_scenes([&scene]);
+        project.name_tiles(|tile| Some(["grass", "stone"][tile.id() - 1].to_owned()));
+
+        // stone is gone and water got its old id
+        let registry = [("grass", Tile::new(0)), ("water", Tile::new(1))];
+
+        let missing = project.remap_tiles(|name|
+        {
+            registry.iter().find(|(other, _)| *other == name).map(|(_, tile)| *tile)
+        });
+
+        assert_eq!(missing, vec!["stone".to_owned()]);
+        assert_eq!(project.scenes[0].tiles, vec![Tile::new(0).id(), Tile::none().id()]);
+        assert_eq!(project.tile_names, BTreeMap::from([(Tile::new(0).id(), "grass".to_owned())]));
+    }
+}
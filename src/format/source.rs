@@ -0,0 +1,140 @@
+use std::fmt::Write;
+
+use crate::{Point2, Tile, Scene};
+
+use super::quoted;
+
+
+// rows go from the top of the scene to the bottom like in an image
+fn rows(scene: &Scene) -> Vec<Vec<usize>>
+{
+    let container = scene.to_container();
+    let size = *container.size();
+
+    (0..size.y).rev().map(|y|
+    {
+        (0..size.x).map(|x| container[Point2::new(x, y)].id()).collect()
+    }).collect()
+}
+
+fn legend(out: &mut String, scene: &Scene, tile_name: impl Fn(Tile) -> Option<String>)
+{
+    let mut ids: Vec<usize> = scene.iter().map(|(_, tile)| tile.id()).filter(|id| *id != 0)
+        .collect();
+
+    ids.sort_unstable();
+    ids.dedup();
+
+    writeln!(out, "// 0 = empty").unwrap();
+    ids.into_iter().for_each(|id|
+    {
+        let name = tile_name(Tile::from_id(id)).unwrap_or_default();
+
+        writeln!(out, "// {id} = {name}").unwrap();
+    });
+}
+
+fn identifier(name: &str) -> String
+{
+    let name: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if name.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(true)
+    {
+        format!("_{name}")
+    } else
+    {
+        name
+    }
+}
+
+pub fn to_rust(name: &str, scene: &Scene, tile_name: impl Fn(Tile) -> Option<String>) -> String
+{
+    let rows = rows(scene);
+    let size = scene.size();
+
+    let mut out = String::new();
+
+    legend(&mut out, scene, tile_name);
+
+    let name = identifier(name).to_uppercase();
+    writeln!(out, "pub const {name}: [[u32; {}]; {}] = [", size.x, size.y).unwrap();
+
+    rows.into_iter().for_each(|row|
+    {
+        let row = row.into_iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+
+        writeln!(out, "    [{row}],").unwrap();
+    });
+
+    writeln!(out, "];").unwrap();
+
+    let properties: Vec<_> = scene.properties().collect();
+    if !properties.is_empty()
+    {
+        writeln!(out).unwrap();
+        writeln!(out, "pub const {name}_PROPERTIES: [(&str, &str); {}] = [", properties.len())
+            .unwrap();
+
+        properties.into_iter().for_each(|(key, value)|
+        {
+            writeln!(out, "    ({}, {}),", quoted(key), quoted(value)).unwrap();
+        });
+
+        writeln!(out, "];").unwrap();
+    }
+
+    out
+}
+
+pub fn to_c(name: &str, scene: &Scene, tile_name: impl Fn(Tile) -> Option<String>) -> String
+{
+    let rows = rows(scene);
+    let size = scene.size();
+
+    let mut out = String::new();
+
+    legend(&mut out, scene, tile_name);
+
+    let name = identifier(name);
+    let upper_name = name.to_uppercase();
+
+    writeln!(out, "#define {upper_name}_WIDTH {}", size.x).unwrap();
+    writeln!(out, "#define {upper_name}_HEIGHT {}", size.y).unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "static const unsigned int {name}[{upper_name}_HEIGHT][{upper_name}_WIDTH] = {{")
+        .unwrap();
+
+    rows.into_iter().for_each(|row|
+    {
+        let row = row.into_iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+
+        writeln!(out, "    {{{row}}},").unwrap();
+    });
+
+    writeln!(out, "}};").unwrap();
+
+    let properties: Vec<_> = scene.properties().collect();
+    if !properties.is_empty()
+    {
+        writeln!(out).unwrap();
+        writeln!(out, "#define {upper_name}_PROPERTIES_COUNT {}", properties.len()).unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "static const char *const {name}_properties[{upper_name}_PROPERTIES_COUNT][2] = {{"
+        ).unwrap();
+
+        properties.into_iter().for_each(|(key, value)|
+        {
+            writeln!(out, "    {{{}, {}}},", quoted(key), quoted(value)).unwrap();
+        });
+
+        writeln!(out, "}};").unwrap();
+    }
+
+    out
+}
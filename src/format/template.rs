@@ -0,0 +1,96 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    collections::BTreeMap
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Point2, Tile, Scene};
+
+use super::prefab::Prefab;
+
+
+// a starting point for new scenes like a room with its walls already up,
+// tiles go by name like in prefabs so templates work across projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Template
+{
+    // where the bottom left corner of the tiles goes
+    pub pos: Point2<i32>,
+    pub tiles: Prefab,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>
+}
+
+impl Template
+{
+    pub fn from_scene(scene: &Scene, tile_name: impl Fn(Tile) -> Option<String>) -> Self
+    {
+        let (pos, tiles) = if let Some((min, max)) = scene.bounds()
+        {
+            (min, Prefab::from_region(scene, min, max, tile_name))
+        } else
+        {
+            let empty = Prefab{size: Point2::new(0, 0), palette: Vec::new(), tiles: Vec::new()};
+
+            (Point2::new(0, 0), empty)
+        };
+
+        let properties = scene.properties()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+
+        Self{pos, tiles, properties}
+    }
+
+    // returns the names of the tiles it couldnt find too
+    pub fn to_scene(&self, tile: impl Fn(&str) -> Option<Tile>) -> (Scene, Vec<String>)
+    {
+        let mut scene = Scene::new(self.tiles.size, self.pos);
+
+        let missing = self.tiles.paste(&mut scene, self.pos, tile);
+
+        self.properties.iter().for_each(|(key, value)| scene.set_property(key, value));
+
+        (scene, missing)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let this: Self = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(io::Error::from)?;
+
+        if !this.tiles.is_valid()
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed template tiles"));
+        }
+
+        Ok(this)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()>
+    {
+        fs::write(path, serde_json::to_string_pretty(self).unwrap())
+    }
+
+    // every json file in the directory sorted by name, nothing if it doesnt exist
+    pub fn list(directory: impl AsRef<Path>) -> Vec<PathBuf>
+    {
+        let Ok(entries) = fs::read_dir(directory)
+        else
+        {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map(|x| x == "json").unwrap_or(false))
+            .collect();
+
+        paths.sort();
+
+        paths
+    }
+}
@@ -0,0 +1,131 @@
+use std::mem;
+
+use crate::Scene;
+
+
+const MAX_HISTORY: usize = 100;
+
+// every scene an edit changed with the index it had, the first one is where the edit happened,
+// the rest r scenes that only changed along with it (like links into a mirrored scene)
+type Entry = Vec<(usize, Scene)>;
+
+#[derive(Debug, Default)]
+pub struct History
+{
+    undo: Vec<Entry>,
+    redo: Vec<Entry>
+}
+
+impl History
+{
+    // call before changing the scenes so it can be undone
+    pub fn remember(&mut self, scenes: &[Scene], indices: impl IntoIterator<Item=usize>)
+    {
+        let entry: Entry = indices.into_iter()
+            .filter_map(|index| scenes.get(index).map(|scene| (index, scene.clone())))
+            .collect();
+
+        if entry.is_empty()
+        {
+            return;
+        }
+
+        if self.undo.len() >= MAX_HISTORY
+        {
+            self.undo.remove(0);
+        }
+
+        self.undo.push(entry);
+        self.redo.clear();
+    }
+
+    pub fn clear(&mut self)
+    {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    // returns the scene the undone edit happened in
+    pub fn undo(&mut self, scenes: &mut [Scene]) -> Option<usize>
+    {
+        Self::swap(&mut self.undo, &mut self.redo, scenes)
+    }
+
+    pub fn redo(&mut self, scenes: &mut [Scene]) -> Option<usize>
+    {
+        Self::swap(&mut self.redo, &mut self.undo, scenes)
+    }
+
+    fn swap(from: &mut Vec<Entry>, to: &mut Vec<Entry>, scenes: &mut [Scene]) -> Option<usize>
+    {
+        let entry = from.pop()?;
+
+        let main = entry.first().map(|(index, _)| *index);
+
+        let swapped = entry.into_iter().filter_map(|(index, scene)|
+        {
+            scenes.get_mut(index).map(|current| (index, mem::replace(current, scene)))
+        }).collect();
+
+        to.push(swapped);
+
+        main
+    }
+
+    // for when scenes get moved around, none means that scene is gone
+    pub fn remap_scenes(&mut self, remap: impl Fn(usize) -> Option<usize>)
+    {
+        let remap_entries = |entries: Vec<Entry>| -> Vec<Entry>
+        {
+            entries.into_iter().map(|entry|
+            {
+                entry.into_iter().filter_map(|(index, mut scene)|
+                {
+                    scene.retarget_links(&remap);
+
+                    remap(index).map(|index| (index, scene))
+                }).collect::<Entry>()
+            }).filter(|entry| !entry.is_empty()).collect()
+        };
+
+        self.undo = remap_entries(mem::take(&mut self.undo));
+        self.redo = remap_entries(mem::take(&mut self.redo));
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::{Point2, Tile, scene::SceneLink};
+
+    #[test]
+    fn undo_restores_links_into_moved_scene()
+    {
+        let mut target = Scene::new(Point2::new(4, 1), Point2::new(0, 0));
+        target[Point2::new(0, 0)] = Tile::new(0);
+
+        let mut other = Scene::new(Point2::new(1, 1), Point2::new(0, 0));
+        other.set_link(Point2::new(0, 0), Some(SceneLink{scene: 0, pos: Point2::new(0, 0)}));
+
+        let mut scenes = vec![target, other];
+        let mut history = History::default();
+
+        history.remember(&scenes, [0, 1]);
+
+        let moved = scenes[0].mirror_x();
+        scenes.iter_mut().for_each(|scene| scene.move_link_targets(0, &moved));
+
+        let link = |scenes: &[Scene]| scenes[1].link(Point2::new(0, 0)).unwrap().pos;
+
+        assert_eq!(link(&scenes), Point2::new(3, 0));
+
+        assert_eq!(history.undo(&mut scenes), Some(0));
+        assert_eq!(link(&scenes), Point2::new(0, 0));
+        assert_eq!(scenes[0][Point2::new(0, 0)], Tile::new(0));
+
+        assert_eq!(history.redo(&mut scenes), Some(0));
+        assert_eq!(link(&scenes), Point2::new(3, 0));
+    }
+}
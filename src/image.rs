@@ -1,8 +1,21 @@
-use std::path::Path;
+use std::{
+    fs::File,
+    io::{self, Read, BufReader},
+    path::Path,
+    time::Duration
+};
+
+use image::{
+    AnimationDecoder,
+    ImageFormat,
+    ImageError,
+    codecs::{gif::GifDecoder, png::PngDecoder}
+};
 
 use crate::Point2;
 
 
+#[derive(Clone)]
 pub struct Image
 {
     data: Vec<u8>,
@@ -12,15 +25,321 @@ pub struct Image
 
 impl Image
 {
-    pub fn load(path: impl AsRef<Path>) -> Self
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, image::ImageError>
     {
-        let image = image::open(path).unwrap().into_rgba8();
+        let image = image::open(path)?.into_rgba8();
 
-        Self{
+        Ok(Self{
             size: Point2::new(image.width() as usize, image.height() as usize),
             data: image.into_raw(),
             bpp: 4
+        })
+    }
+
+    // every frame of a gif or an animated png with how long it stays, none if it isnt animated
+    pub fn load_animation(
+        path: impl AsRef<Path>
+    ) -> Result<Option<Vec<(Self, Duration)>>, ImageError>
+    {
+        let path = path.as_ref();
+
+        let open = || File::open(path).map(BufReader::new).map_err(ImageError::IoError);
+
+        let format = ImageFormat::from_path(path);
+
+        // decoding every frame takes forever so only actual animations get that far,
+        // if the header is broken the normal loading can say whats wrong
+        let animated = match format
+        {
+            Ok(ImageFormat::Gif) => gif_is_animated(&mut open()?),
+            Ok(ImageFormat::Png) => png_is_animated(&mut open()?),
+            _ => Ok(false)
+        };
+
+        if !animated.unwrap_or(false)
+        {
+            return Ok(None);
         }
+
+        let frames = match format
+        {
+            Ok(ImageFormat::Gif) => GifDecoder::new(open()?)?.into_frames().collect_frames()?,
+            Ok(ImageFormat::Png) =>
+            {
+                let decoder = PngDecoder::new(open()?)?;
+
+                if !decoder.is_apng()
+                {
+                    return Ok(None);
+                }
+
+                decoder.apng().into_frames().collect_frames()?
+            },
+            _ => return Ok(None)
+        };
+
+        if frames.len() < 2
+        {
+            return Ok(None);
+        }
+
+        // the frames r already drawn over the previous ones so they all cover the whole image
+        Ok(Some(frames.into_iter().map(|frame|
+        {
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let duration = Duration::from_secs_f64(
+                numerator as f64 / denominator.max(1) as f64 / 1000.0
+            );
+
+            let image = frame.into_buffer();
+
+            let size = Point2::new(image.width() as usize, image.height() as usize);
+
+            (Self::from_raw(size, image.into_raw()), duration)
+        }).collect()))
+    }
+
+    // next to each other left to right, they all have to be the same size
+    pub fn strip<'a>(images: impl IntoIterator<Item=&'a Self>) -> Self
+    {
+        let images: Vec<&Self> = images.into_iter().collect();
+
+        let frame_size = images.first().map(|image| *image.size()).unwrap_or(Point2::repeat(0));
+        let size = Point2::new(frame_size.x * images.len(), frame_size.y);
+
+        let data = (0..size.y).flat_map(|y|
+        {
+            images.iter().flat_map(move |image|
+            {
+                let start = y * image.bytes_row();
+
+                image.data[start..start + image.bytes_row()].iter().copied()
+            })
+        }).collect();
+
+        Self::from_raw(size, data)
+    }
+
+    // magenta and black squares, stands in for images that couldnt be loaded
+    pub fn placeholder(size: Point2<usize>) -> Self
+    {
+        let size = size.map(|x| x.max(2));
+        let square = size.map(|x| x / 2);
+
+        let data = (0..size.y).flat_map(|y| (0..size.x).map(move |x| (x, y))).flat_map(|(x, y)|
+        {
+            if (x / square.x + y / square.y).is_multiple_of(2)
+            {
+                [255, 0, 255, 255]
+            } else
+            {
+                [0, 0, 0, 255]
+            }
+        }).collect();
+
+        Self::from_raw(size, data)
+    }
+
+    // centered on a see through image of this size, parts that stick out get cut off
+    pub fn padded(&self, size: Point2<usize>) -> Self
+    {
+        let offset = size.zip(self.size).map(|(size, image)| (size as i32 - image as i32) / 2);
+
+        let mut data = vec![0; size.x * size.y * 4];
+
+        (0..size.y).for_each(|y|
+        {
+            (0..size.x).for_each(|x|
+            {
+                let source = Point2::new(x as i32 - offset.x, y as i32 - offset.y);
+
+                let inside = source.x >= 0 && source.y >= 0
+                    && (source.x as usize) < self.size.x && (source.y as usize) < self.size.y;
+
+                if inside
+                {
+                    let from = source.y as usize * self.bytes_row() + source.x as usize * 4;
+                    let to = (y * size.x + x) * 4;
+
+                    data[to..to + 4].copy_from_slice(&self.data[from..from + 4]);
+                }
+            });
+        });
+
+        Self::from_raw(size, data)
+    }
+
+    // as big as fits in the size without squishing, nearest pixel so pixel art stays sharp
+    pub fn scaled(&self, size: Point2<usize>) -> Self
+    {
+        let scale = (size.x as f32 / self.size.x as f32).min(size.y as f32 / self.size.y as f32);
+
+        let scaled_size = self.size.map(|x| ((x as f32 * scale).round() as usize).max(1));
+
+        let data = (0..scaled_size.y).flat_map(|y| (0..scaled_size.x).map(move |x| (x, y)))
+            .flat_map(|(x, y)|
+            {
+                let source_x = (((x as f32 + 0.5) / scale) as usize).min(self.size.x - 1);
+                let source_y = (((y as f32 + 0.5) / scale) as usize).min(self.size.y - 1);
+
+                let index = source_y * self.bytes_row() + source_x * 4;
+
+                self.data[index..index + 4].iter().copied()
+            }).collect();
+
+        Self::from_raw(scaled_size, data).padded(size)
+    }
+
+    // a ring of dots with 1 lit up, going through the frames spins it
+    pub fn spinner(size: usize, frame: usize, frames: usize) -> Self
+    {
+        let center = size as f32 / 2.0;
+        let radius = center * 0.7;
+        let dot = (size as f32 / 8.0).max(1.0);
+
+        let data = (0..size).flat_map(|y| (0..size).map(move |x| (x, y))).flat_map(|(x, y)|
+        {
+            let pos = Point2::new(x as f32 + 0.5 - center, y as f32 + 0.5 - center);
+
+            let closest = (0..frames).find(|index|
+            {
+                let angle = *index as f32 / frames as f32 * std::f32::consts::TAU;
+                let dot_pos = Point2::new(angle.sin() * radius, -angle.cos() * radius);
+
+                let distance = pos - dot_pos;
+
+                (distance.x * distance.x + distance.y * distance.y).sqrt() <= dot
+            });
+
+            match closest
+            {
+                Some(index) if index == frame => [255, 255, 255, 255],
+                Some(_) => [120, 120, 120, 160],
+                None => [40, 40, 40, 120]
+            }
+        }).collect();
+
+        Self::from_raw(Point2::repeat(size), data)
+    }
+
+    pub fn from_raw(size: Point2<usize>, data: Vec<u8>) -> Self
+    {
+        assert_eq!(size.x * size.y * 4, data.len());
+
+        Self{data, size, bpp: 4}
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), image::ImageError>
+    {
+        image::save_buffer(
+            path,
+            &self.data,
+            self.size.x as u32,
+            self.size.y as u32,
+            image::ColorType::Rgba8
+        )
+    }
+
+    // every pixel is the average of 4, see through pixels dont darken their neighbors
+    pub fn halved(&self) -> Self
+    {
+        let size = self.size.map(|x| (x / 2).max(1));
+
+        let mut data = vec![0; size.x * size.y * 4];
+
+        (0..size.y).for_each(|y|
+        {
+            (0..size.x).for_each(|x|
+            {
+                let mut sum = [0_u32; 4];
+
+                [(0, 0), (1, 0), (0, 1), (1, 1)].into_iter().for_each(|(offset_x, offset_y)|
+                {
+                    let source_x = (x * 2 + offset_x).min(self.size.x - 1);
+                    let source_y = (y * 2 + offset_y).min(self.size.y - 1);
+
+                    let index = source_y * self.bytes_row() + source_x * 4;
+                    let alpha = self.data[index + 3] as u32;
+
+                    (0..3).for_each(|channel|
+                    {
+                        sum[channel] += self.data[index + channel] as u32 * alpha;
+                    });
+
+                    sum[3] += alpha;
+                });
+
+                let index = (y * size.x + x) * 4;
+
+                if sum[3] > 0
+                {
+                    (0..3).for_each(|channel|
+                    {
+                        data[index + channel] = (sum[channel] / sum[3]) as u8;
+                    });
+                }
+
+                data[index + 3] = (sum[3] / 4) as u8;
+            });
+        });
+
+        Self::from_raw(size, data)
+    }
+
+    pub fn cropped(&self, pos: Point2<usize>, size: Point2<usize>) -> Self
+    {
+        let row_bytes = size.x * 4;
+
+        let data = (0..size.y).flat_map(|y|
+        {
+            let start = (pos.y + y) * self.bytes_row() + pos.x * 4;
+
+            self.data[start..start + row_bytes].iter().copied()
+        }).collect();
+
+        Self::from_raw(size, data)
+    }
+
+    // every pixel is the average of all the pixels it covers, like halved but any size smaller
+    pub fn shrunk(&self, size: Point2<usize>) -> Self
+    {
+        let size = size.zip(self.size).map(|(size, current)| size.clamp(1, current.max(1)));
+
+        let span = |x: usize, size: usize, current: usize|
+        {
+            let start = x * current / size;
+
+            start..((x + 1) * current / size).max(start + 1)
+        };
+
+        let data = (0..size.y).flat_map(|y| (0..size.x).map(move |x| (x, y))).flat_map(|(x, y)|
+        {
+            let mut sum = [0_u64; 4];
+            let mut count = 0;
+
+            span(y, size.y, self.size.y).for_each(|source_y|
+            {
+                span(x, size.x, self.size.x).for_each(|source_x|
+                {
+                    let index = source_y * self.bytes_row() + source_x * 4;
+                    let alpha = self.data[index + 3] as u64;
+
+                    (0..3).for_each(|channel|
+                    {
+                        sum[channel] += self.data[index + channel] as u64 * alpha;
+                    });
+
+                    sum[3] += alpha;
+                    count += 1;
+                });
+            });
+
+            let color = |channel: usize| sum[channel].checked_div(sum[3]).unwrap_or(0) as u8;
+
+            [color(0), color(1), color(2), (sum[3] / count) as u8]
+        }).collect();
+
+        Self::from_raw(size, data)
     }
 
     pub fn data(&self) -> &[u8]
@@ -28,6 +347,11 @@ impl Image
         &self.data
     }
 
+    pub fn data_mut(&mut self) -> &mut [u8]
+    {
+        &mut self.data
+    }
+
     pub fn size(&self) -> &Point2<usize>
     {
         &self.size
@@ -43,3 +367,112 @@ impl Image
         self.bpp * self.size.x
     }
 }
+
+fn skip(reader: &mut BufReader<File>, amount: usize) -> io::Result<()>
+{
+    reader.seek_relative(amount as i64)
+}
+
+fn read_byte(reader: &mut BufReader<File>) -> io::Result<u8>
+{
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+
+    Ok(byte[0])
+}
+
+// only looks at the chunk headers, an animation control chunk before the image data means apng
+fn png_is_animated(reader: &mut BufReader<File>) -> io::Result<bool>
+{
+    // the signature
+    skip(reader, 8)?;
+
+    loop
+    {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+
+        match &header[4..]
+        {
+            b"acTL" => return Ok(true),
+            b"IDAT" | b"IEND" => return Ok(false),
+            // the crc comes after the data
+            _ => skip(reader, length as usize + 4)?
+        }
+    }
+}
+
+// counts the frames without decompressing any of them, 2 is enough to know
+fn gif_is_animated(reader: &mut BufReader<File>) -> io::Result<bool>
+{
+    let color_table_size = |packed: u8| 3 * (1 << ((packed & 0b111) + 1));
+
+    let skip_sub_blocks = |reader: &mut BufReader<File>| -> io::Result<()>
+    {
+        loop
+        {
+            let size = read_byte(reader)?;
+
+            if size == 0
+            {
+                return Ok(());
+            }
+
+            skip(reader, size as usize)?;
+        }
+    };
+
+    // the signature and the screen size
+    skip(reader, 10)?;
+
+    let packed = read_byte(reader)?;
+
+    // background color and aspect ratio
+    skip(reader, 2)?;
+
+    if packed & 0x80 != 0
+    {
+        skip(reader, color_table_size(packed))?;
+    }
+
+    let mut frames = 0;
+
+    loop
+    {
+        match read_byte(reader)?
+        {
+            // extension
+            0x21 =>
+            {
+                skip(reader, 1)?;
+                skip_sub_blocks(reader)?;
+            },
+            // image descriptor
+            0x2c =>
+            {
+                frames += 1;
+
+                if frames > 1
+                {
+                    return Ok(true);
+                }
+
+                skip(reader, 8)?;
+
+                let packed = read_byte(reader)?;
+
+                if packed & 0x80 != 0
+                {
+                    skip(reader, color_table_size(packed))?;
+                }
+
+                // the lzw code size
+                skip(reader, 1)?;
+                skip_sub_blocks(reader)?;
+            },
+            _ => return Ok(false)
+        }
+    }
+}
@@ -0,0 +1,43 @@
+pub use crate::image::Image;
+pub use point::Point2;
+pub use scene::{Tile, Scene};
+
+#[cfg(feature = "sdl")]
+pub use editor::{Game, GameWindow, EventFlow, EventHook};
+#[cfg(feature = "sdl")]
+pub use assets::{Assets, TextureId};
+#[cfg(feature = "sdl")]
+pub use renderer::{Renderer, RenderTexture, Filter, SdlRenderer, SoftwareRenderer};
+
+mod point;
+mod image;
+
+#[cfg(feature = "sdl")]
+mod ui;
+#[cfg(feature = "sdl")]
+mod editor;
+#[cfg(feature = "sdl")]
+mod font;
+#[cfg(feature = "sdl")]
+mod assets;
+#[cfg(feature = "sdl")]
+mod theme;
+#[cfg(feature = "sdl")]
+mod renderer;
+#[cfg(feature = "sdl")]
+mod profiler;
+#[cfg(feature = "sdl")]
+mod loader;
+
+pub mod animator;
+pub mod container;
+pub mod scene;
+pub mod format;
+pub mod spritesheet;
+pub mod registry;
+pub mod tileset;
+pub mod watcher;
+pub mod notifier;
+pub mod task;
+pub mod diff;
+pub mod history;
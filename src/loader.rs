@@ -0,0 +1,98 @@
+use std::{
+    thread,
+    path::PathBuf,
+    sync::{
+        Arc,
+        Mutex,
+        mpsc::{self, Sender, Receiver}
+    }
+};
+
+use crate::Image;
+
+
+// no point in more, the disk cant keep up anyway
+const MAX_THREADS: usize = 4;
+
+pub type LoadedImage = (usize, PathBuf, Result<Image, image::ImageError>);
+
+// decodes images on other threads, the textures still have to get made on the main one
+pub struct ImageLoader
+{
+    requests: Sender<(usize, PathBuf)>,
+    loaded: Receiver<LoadedImage>,
+    pending: usize
+}
+
+impl ImageLoader
+{
+    pub fn new() -> Self
+    {
+        let (requests, receiver) = mpsc::channel::<(usize, PathBuf)>();
+        let (sender, loaded) = mpsc::channel();
+
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let threads = thread::available_parallelism().map(|x| x.get()).unwrap_or(1)
+            .min(MAX_THREADS);
+
+        (0..threads).for_each(|_|
+        {
+            let receiver = receiver.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move ||
+            {
+                loop
+                {
+                    // the lock has to be let go before decoding or only 1 thread does anything
+                    let request = receiver.lock().unwrap().recv();
+
+                    // stops once the loader is gone
+                    let Ok((index, path)) = request
+                    else
+                    {
+                        return;
+                    };
+
+                    let image = Image::load(&path);
+
+                    if sender.send((index, path, image)).is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        });
+
+        Self{requests, loaded, pending: 0}
+    }
+
+    // the index is whatever the image is for, it comes back with the image
+    pub fn request(&mut self, index: usize, path: PathBuf)
+    {
+        self.pending += 1;
+
+        self.requests.send((index, path)).expect("image loading threads died");
+    }
+
+    // whatever got done since last time
+    pub fn take_loaded(&mut self) -> Vec<LoadedImage>
+    {
+        let loaded: Vec<_> = self.loaded.try_iter().collect();
+
+        self.pending -= loaded.len();
+
+        loaded
+    }
+
+    // waits for everything thats left
+    pub fn take_all(&mut self) -> Vec<LoadedImage>
+    {
+        let loaded: Vec<_> = self.loaded.iter().take(self.pending).collect();
+
+        self.pending = 0;
+
+        loaded
+    }
+}
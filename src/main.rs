@@ -1,954 +1,558 @@
 use std::{
-    fs,
-    mem,
-    iter,
-    thread,
+    env,
+    process,
     rc::Rc,
     cell::RefCell,
-    collections::HashMap,
-    path::{Path, PathBuf},
-    time::Duration,
-    ops::{Index, IndexMut}
+    path::PathBuf,
+    collections::HashMap
 };
 
 use sdl2::{
-    EventPump,
-    event::Event,
     rect::Rect,
-    video::{Window, WindowContext},
-    render::{Canvas, Texture, TextureCreator, BlendMode},
-    keyboard::Keycode,
-    pixels::{
-        PixelFormatEnum,
-        Color as SdlColor
-    }
+    pixels::Color,
+    messagebox::{self, MessageBoxFlag}
 };
 
-use ui::{Ui, UiElement, UiElementType, ElementId, UiAnimatableId};
-use container::Container2d;
-use animator::{Animator, AnimatedValue, ValueAnimation};
-
-pub use crate::image::Image;
-pub use point::Point2;
+use tilesthingeringy::{
+    Point2,
+    Tile,
+    GameWindow,
+    Game,
+    Assets,
+    Renderer,
+    Filter,
+    SoftwareRenderer,
+    tileset::{self, TileFit},
+    notifier::Notifier,
+    diff,
+    format::project::{Project, AssetDirectories}
+};
 
-mod point;
-mod image;
-mod container;
-mod ui;
 
-pub mod animator;
+const PROJECT_PATH: &str = "map.json";
+const KEYBINDS_PATH: &str = "keybinds.toml";
 
+// these take a directory after them
+const DIRECTORY_FLAGS: [&str; 2] = ["--assets-dir", "--tiles-dir"];
 
-struct Camera
+// needed before anything gets loaded so every argument gets looked through for them,
+// they win over the directories in the project
+fn asset_directories(project: Option<&Project>) -> AssetDirectories
 {
-    pub pos: Point2<f32>,
-    pub height: f32
-}
+    let args: Vec<String> = env::args().collect();
 
-impl Camera
-{
-    pub fn new(height: f32) -> Self
+    let flag = |name: &str|
     {
-        Self{pos: Point2::new(0.0, 0.0), height}
-    }
-}
+        args.windows(2).find(|pair| pair[0] == name).map(|pair| PathBuf::from(&pair[1]))
+    };
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct Tile(usize);
+    let flags = AssetDirectories{
+        assets_dir: flag("--assets-dir"),
+        tiles_dir: flag("--tiles-dir")
+    };
 
-impl Tile
-{
-    pub fn new(id: usize) -> Self
-    {
-        Tile(id + 1)
-    }
+    flags.or(project.map(|project| project.asset_directories(PROJECT_PATH)).unwrap_or_default())
+}
 
-    pub fn none() -> Self
-    {
-        Self(0)
-    }
+fn without_directory_flags(args: impl Iterator<Item=String>) -> Vec<String>
+{
+    let mut args = args.peekable();
+    let mut rest = Vec::new();
 
-    pub fn is_none(&self) -> bool
+    while let Some(arg) = args.next()
     {
-        self.0 == 0
+        if DIRECTORY_FLAGS.contains(&arg.as_str())
+        {
+            args.next();
+        } else
+        {
+            rest.push(arg);
+        }
     }
 
-    pub fn id(&self) -> usize
-    {
-        self.0
-    }
+    rest
 }
 
-struct Scene
+fn validate_tile_sizes(assets: &Assets, tile_size: Option<Point2<usize>>, fit: TileFit)
 {
-    container: Container2d<Tile>,
-    offset: Point2<i32>
-}
+    let sources = assets.tile_sources();
 
-impl Scene
-{
-    pub fn new(size: Point2<usize>, offset: Point2<i32>) -> Self
+    // only a tile size from the project gets fitted to
+    let fitted = match fit
     {
-        let container = Container2d::new(size);
+        _ if tile_size.is_none() => "",
+        TileFit::Stretch => "",
+        TileFit::Pad => ", they got padded to it",
+        TileFit::Scale => ", they got scaled to it"
+    };
 
-        Self{container, offset}
-    }
-
-    pub fn extend_to_contain(&mut self, global_pos: Point2<i32>)
+    let tile_size = if let Some(x) = tile_size.or_else(|| tileset::common_size(&sources))
     {
-        let pos = global_pos.map(|x| x as i32) + self.offset;
-
-        let size = self.container.size().map(|x| x as i32);
-        let distance = pos.zip(size).map(|(pos, size)|
-        {
-            if pos >= size
-            {
-                pos - size + 1
-            } else if pos < 0
-            {
-                pos
-            } else
-            {
-                0
-            }
-        });
-
-        let new_size = size + distance.map(|x| x.abs());
-
-        if new_size != size
-        {
-            let this_offset = distance.map(|x| if x < 0 { x } else { 0 });
-
-            self.offset -= this_offset;
-
-            let mut new_container = Container2d::new(new_size.map(|x| x as usize));
-
-            for (pos, tile) in self.container.iter()
-            {
-                let new_pos = pos.map(|x| x as i32) - this_offset;
-
-                new_container[new_pos.map(|x| x as usize)] = *tile;
-            }
+        x
+    } else
+    {
+        return;
+    };
 
-            self.container = new_container;
-        }
-    }
+    let mismatches = tileset::validate_sizes(tile_size, &sources);
 
-    pub fn iter(&self) -> impl Iterator<Item=(Point2<i32>, &Tile)>
+    if mismatches.is_empty()
     {
-        self.container.iter().map(|(pos, tile)|
-        {
-            (pos.map(|x| x as i32) - self.offset, tile)
-        })
+        return;
     }
 
-    fn to_local(&self, pos: Point2<i32>) -> Point2<usize>
+    let files = mismatches.iter().map(|mismatch|
     {
-        let local = pos + self.offset;
+        format!("{} ({}x{})", mismatch.path.display(), mismatch.size.x, mismatch.size.y)
+    }).collect::<Vec<_>>().join("\n");
 
-        assert!(local.x >= 0 && local.y >= 0);
+    let message = format!(
+        "these tiles arent {}x{} (or a multiple of it){fitted}:\n{files}",
+        tile_size.x,
+        tile_size.y
+    );
 
-        local.map(|x| x as usize)
-    }
+    eprintln!("{message}");
+
+    let _ = messagebox::show_simple_message_box(
+        MessageBoxFlag::WARNING,
+        "tile size mismatch",
+        &message,
+        None
+    );
 }
 
-impl Index<Point2<i32>> for Scene
+// the tilesets of the project if it has any, otherwise everything in the tiles directory
+fn load_tiles(assets: &mut Assets, project: Option<&Project>)
 {
-    type Output = Tile;
+    assets.set_tile_size(project.and_then(|project| project.tile_size));
+    assets.set_tile_fit(project.map(|project| project.tile_fit).unwrap_or_default());
 
-    fn index(&self, index: Point2<i32>) -> &Self::Output
-    {
-        self.container.index(self.to_local(index))
-    }
-}
+    let tilesets = project
+        .map(|project| project.tileset_paths(PROJECT_PATH))
+        .unwrap_or_default();
 
-impl IndexMut<Point2<i32>> for Scene
-{
-    fn index_mut(&mut self, index: Point2<i32>) -> &mut Self::Output
+    if tilesets.is_empty()
     {
-        self.extend_to_contain(index);
-
-        self.container.index_mut(self.to_local(index))
+        assets.load_tiles(asset_directories(project).tiles());
+    } else
+    {
+        tilesets.iter().for_each(|path|
+        {
+            if let Err(err) = assets.load_tileset(path)
+            {
+                eprintln!("cant load tileset {}: {err}", path.display());
+            }
+        });
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum ControlName
-{
-    Forward = 0,
-    Back,
-    Right,
-    Left,
-    ZoomOut,
-    ZoomIn,
-    CreateTile,
-    DeleteTile,
-    LAST
-}
-
-const FPS: usize = 60;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct TextureId(usize);
-
-pub struct Assets
-{
-    creator: TextureCreator<WindowContext>,
-    texture_ids: HashMap<PathBuf, usize>,
-    tiles: Vec<TextureId>,
-    // i despise the lifetime on the texture, this sdl wrapper is absolute CANCER
-    textures: Vec<Texture<'static>>
-}
-
-impl Assets
+// draws a scene into a png without opening a window, 1 texture pixel is 1 image pixel
+fn run_render(args: &[String]) -> i32
 {
-    pub fn new(creator: TextureCreator<WindowContext>) -> Self
+    let usage = ||
     {
-        Self{
-            creator,
-            texture_ids: HashMap::new(),
-            tiles: Vec::new(),
-            textures: Vec::new()
-        }
-    }
+        eprintln!("usage: render <output png> [scene index]");
 
-    pub fn add_tile(&mut self, path: impl Into<PathBuf>)
-    {
-        let id = self.add_texture(path);
+        1
+    };
 
-        self.tiles.push(id);
-    }
-
-    pub fn add_texture(&mut self, path: impl Into<PathBuf>) -> TextureId
+    let (output, index) = match args
     {
-        let path = path.into();
-
-        let id = self.textures.len();
+        [output] => (output, 0),
+        [output, index] => match index.parse()
+        {
+            Ok(index) => (output, index),
+            Err(_) => return usage()
+        },
+        _ => return usage()
+    };
 
-        let image = Image::load(&path);
+    let mut project = match Project::load(PROJECT_PATH)
+    {
+        Ok(x) => x,
+        Err(err) =>
+        {
+            eprintln!("cant load {PROJECT_PATH}: {err}");
 
-        let texture = unsafe{ self.texture_from_image(image) };
-        self.textures.push(texture);
+            return 1;
+        }
+    };
 
-        self.texture_ids.insert(path, id);
+    // the size gets set once its known
+    let renderer = Rc::new(RefCell::new(SoftwareRenderer::new(Point2::new(1, 1))));
+    let mut assets = Assets::new(renderer.clone());
 
-        TextureId(id)
-    }
+    load_tiles(&mut assets, Some(&project));
 
-    unsafe fn texture_from_image(&self, image: Image) -> Texture<'static>
+    let missing = project.remap_tiles(|name| assets.tile(name));
+    if !missing.is_empty()
     {
-        let mut texture = self.creator.create_texture_static(
-            PixelFormatEnum::RGBA32,
-            image.size().x as u32,
-            image.size().y as u32
-        ).unwrap();
-        texture.set_blend_mode(BlendMode::Blend);
+        eprintln!("{PROJECT_PATH} has unknown tiles (they got erased): {}", missing.join(", "));
+    }
 
-        let data = image.data();
+    let tile_size = project.tile_size.or_else(|| tileset::common_size(&assets.tile_sources()));
 
-        texture.update(None, data, image.bytes_row()).unwrap();
+    let scene = match project.into_scenes()
+    {
+        Ok(mut scenes) if index < scenes.len() => scenes.swap_remove(index),
+        Ok(scenes) =>
+        {
+            eprintln!("theres no scene {index}, only {}", scenes.len());
 
-        Self::make_texture_static(texture)
-    }
+            return 1;
+        },
+        Err(err) =>
+        {
+            eprintln!("cant load scenes from {PROJECT_PATH}: {err}");
 
-    unsafe fn make_texture_static(texture: Texture<'_>) -> Texture<'static>
-    {
-        mem::transmute(texture)
-    }
+            return 1;
+        }
+    };
 
-    pub fn texture_id(&self, name: impl AsRef<Path>) -> TextureId
+    let (Some(tile_size), Some((min, max))) = (tile_size, scene.bounds())
+    else
     {
-        TextureId(self.texture_ids[name.as_ref()])
-    }
+        eprintln!("nothing to draw");
 
-    pub fn tile_texture_id(&self, tile: Tile) -> TextureId
-    {
-        assert!(!tile.is_none());
+        return 1;
+    };
 
-        self.tiles[tile.id() - 1]
-    }
+    let tile_size = tile_size.map(|x| x as u32);
+    let size = (max - min + 1).map(|x| x as u32) * tile_size;
 
-    pub fn texture<'a>(&'a self, id: TextureId) -> &'a Texture<'static>
-    {
-        &self.textures[id.0]
-    }
-}
+    let mut renderer = renderer.borrow_mut();
 
-pub struct GameWindow
-{
-    window_size: Point2<u32>,
-    canvas: Canvas<Window>,
-    events: EventPump,
-    assets: Rc<RefCell<Assets>>
-}
+    renderer.resize(size);
 
-impl GameWindow
-{
-    pub fn new(window_size: Point2<u32>) -> Self
+    scene.filled().for_each(|(pos, tile)|
     {
-        let ctx = sdl2::init().unwrap();
-        let video = ctx.video().unwrap();
-
-        let window = video.window("tile thingeringy", window_size.x, window_size.y)
-            .build()
-            .unwrap();
+        let seed = scene.variant_seed(pos);
 
-        let canvas = window.into_canvas().build().unwrap();
+        if let Some(id) = assets.tile_variant_texture_id(*tile, pos, seed)
+        {
+            let (texture, source) = assets.texture(id);
+            let [r, g, b] = scene.tint(pos);
 
-        let events = ctx.event_pump().unwrap();
+            let x = ((pos.x - min.x) as u32 * tile_size.x) as i32;
+            let y = ((max.y - pos.y) as u32 * tile_size.y) as i32;
 
-        let assets = Rc::new(RefCell::new(Assets::new(canvas.texture_creator())));
+            let destination = Rect::new(x, y, tile_size.x, tile_size.y);
 
-        Self{
-            window_size,
-            canvas,
-            events,
-            assets
+            renderer.copy_tinted(texture, source, destination, Color::RGB(r, g, b));
         }
-    }
+    });
 
-    pub fn window_size(&self) -> &Point2<u32>
+    if let Err(err) = renderer.image().save(output)
     {
-        &self.window_size
+        eprintln!("cant save {output}: {err}");
+
+        return 1;
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Keybind
-{
-    Keyboard(Keycode),
-    Mouse(u32)
+    0
 }
 
-impl From<Keycode> for Keybind
+// exit codes work like the usual diff, 0 if same, 1 if different and 2 if something broke
+fn run_diff(paths: &[String]) -> i32
 {
-    fn from(value: Keycode) -> Self
+    let (old_path, new_path) = if let [old, new] = paths
     {
-        Self::Keyboard(value)
-    }
-}
-
-impl From<u32> for Keybind
-{
-    fn from(value: u32) -> Self
+        (old, new)
+    } else
     {
-        Self::Mouse(value)
-    }
-}
+        eprintln!("usage: diff <old project> <new project>");
 
-enum UiVariant
-{
-    Normal,
-    Tiles
-}
+        return 2;
+    };
 
-// giga super big struct cuz im lazy
-struct Game
-{
-    aspect: f32,
-    window_size: Point2<usize>,
-    camera: Camera,
-    controls: [bool; ControlName::LAST as usize],
-    scenes: Vec<Scene>,
-    current_scene: usize,
-    current_tile: Tile,
-    window: Rc<RefCell<GameWindow>>,
-    assets: Rc<RefCell<Assets>>,
-    next_scene_button: ElementId,
-    prev_scene_button: ElementId,
-    current_tile_button: ElementId,
-    tile_buttons: Vec<ElementId>,
-    keybinds: Vec<(Keybind, ControlName)>,
-    mouse_pos: Point2<i32>,
-    ui: Ui,
-    tiles_panel: ElementId,
-    tiles_window_animator_open: Animator<UiAnimatableId>,
-    tiles_window_animator_close: Animator<UiAnimatableId>,
-    tiles_ui: Ui,
-    current_ui: UiVariant
-}
-
-impl Game
-{
-    pub fn new(
-        window_size: Point2<usize>,
-        window: Rc<RefCell<GameWindow>>,
-        tiles_amount: usize
-    ) -> Self
+    let load = |path: &str|
     {
-        let aspect = window_size.x as f32 / window_size.y as f32;
-
-        let camera = Camera::new(10.0);
-
-        let controls = [false; ControlName::LAST as usize];
-
-        let scenes = Vec::new();
+        Project::load(path).map_err(|err|
+        {
+            eprintln!("cant load {path}: {err}");
+        })
+    };
 
-        let current_tile = Tile::new(0);
+    let (old, mut new) = match (load(old_path), load(new_path))
+    {
+        (Ok(old), Ok(new)) => (old, new),
+        _ => return 2
+    };
 
-        let assets = window.borrow().assets.clone();
+    // the new ids get turned into the old ones so tiles that only got renumbered dont show up
+    if !old.tile_names.is_empty()
+    {
+        let old_ids: HashMap<&str, usize> = old.tile_names.iter()
+            .map(|(id, name)| (name.as_str(), *id))
+            .collect();
 
-        let mut ui = Ui::new(window.clone(), assets.clone());
+        // tiles the old project didnt have get ids it never used, otherwise they could
+        // look like whatever old tile had the same id
+        let mut unused = old.scenes.iter().flat_map(|scene| scene.tiles.iter())
+            .chain(old.tile_names.keys())
+            .max()
+            .copied()
+            .unwrap_or(0);
 
-        let texture_id_inner = |name: String|
-        {
-            assets.borrow().texture_id(name)
-        };
+        let mut added: HashMap<String, usize> = HashMap::new();
 
-        let texture_id = |name: &str|
+        new.remap_tiles(|name|
         {
-            texture_id_inner(name.to_owned())
-        };
+            let id = old_ids.get(name).copied().unwrap_or_else(||
+            {
+                *added.entry(name.to_owned()).or_insert_with(||
+                {
+                    unused += 1;
 
-        let tile_texture_id = |tile: Tile|
-        {
-            assets.borrow().tile_texture_id(tile)
-        };
-
-        let next_scene_button = ui.push(UiElement{
-            kind: UiElementType::Button,
-            pos: Point2::new(1.0 - 0.08, 1.0 - (0.07 * aspect)),
-            size: Point2::new(0.08, 0.07 * aspect),
-            texture: texture_id("ui/plus.png")
-        });
+                    unused
+                })
+            });
 
-        let prev_scene_button = ui.push(UiElement{
-            kind: UiElementType::Button,
-            pos: Point2::new(1.0 - (0.08 * 2.0) - 0.02, 1.0 - (0.07 * aspect)),
-            size: Point2::new(0.08, 0.07 * aspect),
-            texture: texture_id("ui/minus.png")
+            Some(Tile::from_id(id))
         });
+    }
 
-        let current_tile_button;
+    let scenes = |project: Project, path: &str|
+    {
+        project.into_scenes().map_err(|err|
         {
-            let size = 0.1;
-            let margin = size * 0.1;
-
-            ui.push(UiElement{
-                kind: UiElementType::Panel,
-                pos: Point2::new(0.0, 1.0 - ((size + margin) * aspect)),
-                size: Point2::new(size + margin, (size + margin) * aspect),
-                texture: texture_id("ui/white.png")
-            });
+            eprintln!("cant load {path}: {err}");
+        })
+    };
 
-            ui.push(UiElement{
-                kind: UiElementType::Panel,
-                pos: Point2::new(0.0, 1.0 - (size * aspect)),
-                size: Point2::new(size, size * aspect),
-                texture: texture_id("ui/background.png")
-            });
+    let (old, new) = match (scenes(old, old_path), scenes(new, new_path))
+    {
+        (Ok(old), Ok(new)) => (old, new),
+        _ => return 2
+    };
 
-            current_tile_button = ui.push(UiElement{
-                kind: UiElementType::Button,
-                pos: Point2::new(0.0, 1.0 - (size * aspect)),
-                size: Point2::new(size, size * aspect),
-                texture: tile_texture_id(current_tile)
-            });
-        }
+    let changes = diff::diff(&old, &new);
 
-        let mut tiles_ui = Ui::new(window.clone(), assets.clone());
+    changes.iter().for_each(|change| println!("{change}"));
 
-        let mut tile_buttons = Vec::with_capacity(tiles_amount);
+    if changes.is_empty()
+    {
+        0
+    } else
+    {
+        1
+    }
+}
 
-        let margin = 0.1;
-        let panel_size = 1.0 - margin * 2.0;
+fn main()
+{
+    if env::args().nth(1).as_deref() == Some("diff")
+    {
+        let paths: Vec<String> = env::args().skip(2).collect();
 
-        let panel_size = if aspect < 1.0
-        {
-            panel_size
-        } else
-        {
-            panel_size / aspect
-        };
+        process::exit(run_diff(&paths));
+    }
 
-        let panel_size = Point2::new(panel_size, panel_size * aspect);
-        let panel_pos = (-panel_size + 1.0) * 0.5;
+    if env::args().nth(1).as_deref() == Some("render")
+    {
+        let args = without_directory_flags(env::args().skip(2));
 
-        let tiles_panel;
-        {
-            tiles_panel = tiles_ui.push(UiElement{
-                kind: UiElementType::Panel,
-                pos: panel_pos,
-                size: panel_size,
-                texture: texture_id("ui/panel.png")
-            });
+        process::exit(run_render(&args));
+    }
 
-            let items_row = (tiles_amount as f32).sqrt().ceil() as usize;
+    // opens the window like usual but only to time a huge random scene in it
+    let benchmark = env::args().nth(1).as_deref() == Some("bench");
 
-            for tile_id in 0..tiles_amount
-            {
-                let margin = 0.045;
-                let padding = 0.1;
+    let window_size = Point2{x: 640, y: 480};
 
-                let tile = Tile::new(tile_id);
+    // needed before the window exists, everything else gets parsed after
+    let vsync = env::args().any(|arg| arg == "--vsync");
 
-                let item_pos = Point2::new(tile_id % items_row, tile_id / items_row);
+    let window = if vsync
+    {
+        GameWindow::with_vsync(window_size)
+    } else
+    {
+        GameWindow::new(window_size)
+    };
 
-                let row_size = items_row as f32 + (items_row - 1) as f32 * padding;
-                let tile_size = (1.0 - margin * 2.0) / row_size;
+    let window = Rc::new(RefCell::new(window));
 
-                let padding = tile_size * padding;
+    let project = Project::load(PROJECT_PATH).ok();
+    let directories = asset_directories(project.as_ref());
 
-                let mut tile_pos = item_pos.map(|x| x as f32) * (tile_size + padding);
-                tile_pos.y = 1.0 - tile_pos.y - tile_size - margin;
-                tile_pos.x += margin;
+    {
+        let window = window.borrow_mut();
+        let mut assets = window.assets().borrow_mut();
 
-                let tile_element_id = tiles_ui.push_child(&tiles_panel, UiElement{
-                    kind: UiElementType::Button,
-                    pos: tile_pos,
-                    size: Point2::repeat(tile_size),
-                    texture: tile_texture_id(tile)
-                });
+        // so big tilesets dont keep the window from showing up
+        assets.set_background_loading(true);
 
-                tile_buttons.push(tile_element_id);
-            }
-        }
+        load_tiles(&mut assets, project.as_ref());
 
-        let tiles_window_animator_open;
-        let tiles_window_animator_close;
+        // the editor cant do anything without tiles, like when the tiles dir is mistyped
+        if assets.tiles().next().is_none()
         {
-            let thin_line = panel_size.y * 0.02;
-
-            let x_curve = ValueAnimation::EaseIn(0.7);
-            let y_curve = ValueAnimation::EaseIn(0.9);
-
-            let y_scale_start = 0.2;
-            let x_scale_end = 0.4;
-
-            tiles_window_animator_open = Animator::new(vec![
-                AnimatedValue{
-                    id: UiAnimatableId::ScaleY,
-                    range: thin_line..=panel_size.y,
-                    curve: y_curve.clone(),
-                    duration: y_scale_start..=1.0
-                },
-                AnimatedValue{
-                    id: UiAnimatableId::PositionY,
-                    range: (panel_size.y / 2.0 + panel_pos.y)..=panel_pos.y,
-                    curve: y_curve,
-                    duration: y_scale_start..=1.0
-                },
-                AnimatedValue{
-                    id: UiAnimatableId::ScaleX,
-                    range: 0.0..=panel_size.x,
-                    curve: x_curve.clone(),
-                    duration: 0.0..=x_scale_end
-                },
-                AnimatedValue{
-                    id: UiAnimatableId::PositionX,
-                    range: (panel_size.x / 2.0 + panel_pos.x)..=panel_pos.x,
-                    curve: x_curve,
-                    duration: 0.0..=x_scale_end
-                }
-            ], Duration::from_millis(200));
+            let message = format!(
+                "couldnt find any tiles in {} (or the tilesets of {PROJECT_PATH})",
+                directories.tiles().display()
+            );
 
-            tiles_window_animator_close = tiles_window_animator_open.reversed();
-        }
+            eprintln!("{message}");
 
-        let keybinds: Vec<(Keybind, _)> = vec![
-            (Keycode::W.into(), ControlName::Forward),
-            (Keycode::S.into(), ControlName::Back),
-            (Keycode::A.into(), ControlName::Left),
-            (Keycode::D.into(), ControlName::Right),
-            (Keycode::Space.into(), ControlName::ZoomOut),
-            (Keycode::LCtrl.into(), ControlName::ZoomIn),
-            (0.into(), ControlName::CreateTile),
-            (Keycode::Z.into(), ControlName::CreateTile),
-            (2.into(), ControlName::DeleteTile),
-            (Keycode::X.into(), ControlName::DeleteTile),
-        ];
-
-        let mut this = Self{
-            aspect,
-            window_size,
-            camera,
-            controls,
-            scenes,
-            current_scene: 0,
-            current_tile,
-            next_scene_button,
-            prev_scene_button,
-            current_tile_button,
-            tile_buttons,
-            keybinds,
-            mouse_pos: Point2::new(0, 0),
-            window,
-            assets,
-            ui,
-            tiles_panel,
-            tiles_window_animator_open,
-            tiles_window_animator_close,
-            tiles_ui,
-            current_ui: UiVariant::Normal
-        };
-
-        this.ensure_current_tile();
-
-        this
-    }
+            let _ = messagebox::show_simple_message_box(
+                MessageBoxFlag::ERROR,
+                "no tiles",
+                &message,
+                None
+            );
 
-    pub fn run(mut self)
-    {
-        loop
-        {
-            if !self.single_frame()
-            {
-                return;
-            }
-
-            thread::sleep(Duration::from_millis(1000 / FPS as u64));
+            process::exit(1)
         }
-    }
 
-    fn ensure_current_tile(&mut self)
-    {
-        let texture = self.assets.borrow().tile_texture_id(self.current_tile);
+        assets.load_textures(directories.ui());
 
-        *self.ui.get(&self.current_tile_button).borrow_mut().texture() = texture;
-    }
+        assets.build_atlas();
 
-    fn ensure_current_scene(&mut self)
-    {
-        while self.scenes.len() <= self.current_scene
-        {
-            let size = Point2::new(0, 0);
-            let offset = Point2::new(0, 0);
+        let tile_size = project.as_ref().and_then(|project| project.tile_size);
+        let fit = project.map(|project| project.tile_fit).unwrap_or_default();
 
-            self.scenes.push(Scene::new(size, offset));
-        }
+        validate_tile_sizes(&assets, tile_size, fit);
     }
 
-    fn single_frame(&mut self) -> bool
-    {
-        let window = self.window.clone();
-        for event in window.borrow_mut().events.poll_iter()
-        {
-            if !self.on_event(event)
-            {
-                return false;
-            }
-        }
-
-        self.ensure_current_scene();
+    let mut game = Game::new(window_size.map(|x| x as usize), window);
 
-        let dt = (1000 / FPS) as f32;
-        let speed = 0.002 * self.camera.height.sqrt() * dt;
+    game.open(PROJECT_PATH);
 
-        if self.pressed(ControlName::Forward)
-        {
-            self.camera.pos.y += speed;
-        } else if self.pressed(ControlName::Back)
-        {
-            self.camera.pos.y -= speed;
-        }
+    game.load_keybinds(KEYBINDS_PATH);
 
-        if self.pressed(ControlName::Right)
-        {
-            self.camera.pos.x += speed;
-        } else if self.pressed(ControlName::Left)
-        {
-            self.camera.pos.x -= speed;
-        }
+    game.watch(directories.tiles(), directories.ui());
 
-        let zoom_scale = 0.9_f32.powf(0.05 * dt);
+    let mut args = env::args().skip(1 + benchmark as usize).peekable();
 
-        if self.pressed(ControlName::ZoomOut)
-        {
-            self.camera.height /= zoom_scale;
-        } else if self.pressed(ControlName::ZoomIn)
+    let benchmark_size = if benchmark
+    {
+        args.next_if(|arg| !arg.starts_with("--")).map(|size| size.parse().unwrap_or_else(|_|
         {
-            self.camera.height *= zoom_scale;
-        }
+            eprintln!("usage: bench [scene size] [options]");
+
+            process::exit(1)
+        })).unwrap_or(2000)
+    } else
+    {
+        0
+    };
 
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
         {
-            let create_tile = self.pressed(ControlName::CreateTile);
-            if create_tile || self.pressed(ControlName::DeleteTile)
+            "--view" =>
             {
-                let tile_pos = self.screen_to_pos(self.mouse_pos);
-
-                if create_tile
+                let path = args.next().unwrap_or_else(||
                 {
-                    self.scenes[self.current_scene][tile_pos] = self.current_tile;
-                } else
-                {
-                    self.scenes[self.current_scene][tile_pos] = Tile::none();
-                }
-            }
-        }
-
-        {
-            let canvas = &mut self.window.borrow_mut().canvas;
-
-            canvas.set_draw_color(SdlColor::RGB(0, 0, 0));
-            canvas.clear();
-        }
+                    eprintln!("--view needs a binary map file");
 
-        self.draw_scene(&self.scenes[self.current_scene]);
+                    process::exit(1)
+                });
 
-        self.ui.draw();
+                if let Err(err) = game.view(&path)
+                {
+                    eprintln!("cant view {path}: {err}");
 
-        let panel = self.tiles_ui.get(&self.tiles_panel);
-        let draw_tiles_ui = match self.current_ui
-        {
-            UiVariant::Tiles =>
+                    process::exit(1)
+                }
+            },
+            "--tile-pages" => game.set_tile_pages(true),
+            "--clamp-camera" => game.set_clamp_camera(true),
+            "--snap-zoom" => game.set_snap_zoom(true),
+            "--linear-filter" => game.set_tile_filter(Filter::Linear),
+            "--mipmaps" => game.set_mipmaps(true),
+            "--vsync" => (),
+            x if DIRECTORY_FLAGS.contains(&x) =>
             {
-                self.tiles_window_animator_open.animate(&mut *panel.borrow_mut());
-
-                true
+                args.next();
             },
-            UiVariant::Normal =>
+            "--no-idle" => game.set_idle_throttle(false),
+            "--lighting" => game.set_lighting(true),
+            "--ambient" =>
             {
-                if self.tiles_window_animator_close.is_playing()
+                let ambient = args.next().and_then(|x| x.parse().ok()).unwrap_or_else(||
                 {
-                    self.tiles_window_animator_close.animate(&mut *panel.borrow_mut());
-
-                    true
-                } else
-                {
-                    false
-                }
-            }
-        };
-
-        if draw_tiles_ui
-        {
-            self.tiles_ui.draw();
-        }
+                    eprintln!("--ambient needs a number from 0 to 1");
 
-        self.window.borrow_mut().canvas.present();
+                    process::exit(1)
+                });
 
-        true
-    }
+                game.set_ambient_light(ambient);
+            },
+            "--fps" =>
+            {
+                let fps = args.next().and_then(|fps| fps.parse().ok()).unwrap_or_else(||
+                {
+                    eprintln!("--fps needs a number");
 
-    fn set_control(&mut self, control: Keybind, state: bool)
-    {
-        if let Some((_, control)) = self.keybinds.iter().find(|(k, _)|
-        {
-            *k == control
-        })
-        {
-            self.controls[*control as usize] = state;
-        }
-    }
+                    process::exit(1)
+                });
 
-    fn on_event(&mut self, event: Event) -> bool
-    {
-        match event
-        {
-            Event::Quit{..} => return false,
-            Event::KeyDown{keycode: Some(key), ..} =>
-            {
-                self.set_control(Keybind::Keyboard(key), true);
+                game.set_fps(fps);
             },
-            Event::KeyUp{keycode: Some(key), ..} =>
+            "--reference" =>
             {
-                self.set_control(Keybind::Keyboard(key), false);
+                let path = args.next().unwrap_or_else(||
+                {
+                    eprintln!("--reference needs an image");
+
+                    process::exit(1)
+                });
+
+                if let Err(err) = game.set_reference(&path)
+                {
+                    eprintln!("cant load reference {path}: {err}");
+                }
             },
-            Event::MouseMotion{x, y, ..} =>
+            "--notify-file" =>
             {
-                self.mouse_pos = Point2::new(x, y);
+                let path = args.next().unwrap_or_else(||
+                {
+                    eprintln!("--notify-file needs a path");
+
+                    process::exit(1)
+                });
+
+                game.add_notifier(Notifier::touch(path));
             },
-            Event::MouseButtonDown{which: button, x, y, ..} =>
+            "--notify-udp" =>
             {
-                let pos = self.screen_to_local(Point2{x, y});
-
-                // thats kinda cool i think thats a cool way to use pattern matching
-                if let (0, Some(ui_event)) = (button, self.ui.click(pos))
+                let address = args.next().and_then(|x| x.parse().ok()).unwrap_or_else(||
                 {
-                    let id = ui_event.element_id;
+                    eprintln!("--notify-udp needs an address like 127.0.0.1:7777");
 
-                    if id == self.next_scene_button
-                    {
-                        self.current_scene += 1;
+                    process::exit(1)
+                });
 
-                        self.print_current_scene();
-                    } else if id == self.prev_scene_button
+                match Notifier::udp(address)
+                {
+                    Ok(notifier) => game.add_notifier(notifier),
+                    Err(err) =>
                     {
-                        // yea im not crashing my computer again
-                        self.current_scene = self.current_scene.saturating_sub(1);
+                        eprintln!("cant create udp notifier: {err}");
 
-                        self.print_current_scene();
-                    } else if id == self.current_tile_button
-                    {
-                        self.current_ui = match self.current_ui
-                        {
-                            UiVariant::Normal =>
-                            {
-                                self.tiles_window_animator_open.reset();
-
-                                UiVariant::Tiles
-                            },
-                            UiVariant::Tiles =>
-                            {
-                                self.tiles_window_animator_close.reset();
-
-                                UiVariant::Normal
-                            }
-                        };
-                    } else
-                    {
-                        panic!("unhandled element id: {:?}", id)
+                        process::exit(1)
                     }
-
-                    return true;
                 }
-
-                match self.current_ui
-                {
-                    UiVariant::Tiles =>
-                    {
-                        if let (0, Some(ui_event)) = (button, self.tiles_ui.click(pos))
-                        {
-                            let id = ui_event.element_id;
-
-                            if let Some(tile_id) = self.tile_buttons.iter()
-                                .position(|element| *element == id)
-                            {
-                                let tile = Tile::new(tile_id);
-
-                                self.current_tile = tile;
-
-                                self.ensure_current_tile();
-                            } else
-                            {
-                                panic!("cant find button with id: {:?}", id);
-                            }
-                        }
-
-                        return true;
-                    },
-                    UiVariant::Normal => ()
-                }
-
-                self.set_control(Keybind::Mouse(button), true);
             },
-            Event::MouseButtonUp{which: button, ..} =>
+            x =>
             {
-                self.set_control(Keybind::Mouse(button), false);
-            },
-            _ => ()
-        }
-
-        true
-    }
-
-    fn tile_size(&self) -> Point2<f32>
-    {
-        let mut size = Point2::repeat(1.0 / self.camera.height);
-        size.x /= self.aspect;
+                eprintln!("unknown argument: {x}");
 
-        size
-    }
-
-    fn draw_scene(&self, scene: &Scene)
-    {
-        for (pos, tile) in scene.iter()
-        {
-            if tile.is_none()
-            {
-                continue;
+                process::exit(1)
             }
-
-            let size = self.tile_size();
-
-            let mut pos = self.pos_to_view(pos);
-            pos.y = 1.0 - pos.y - size.y;
-
-            let texture_id = self.assets.borrow().tile_texture_id(*tile);
-
-            let mut window = self.window.borrow_mut();
-
-            let assets = self.assets.borrow();
-            let texture = assets.texture(texture_id);
-
-            let window_size = self.window_size.map(|x| x as f32);
-
-            let scaled_pos = (pos * window_size).map(|x| x.floor() as i32);
-
-            // u would think that ceil would work but nope
-            let scaled_size = (size * window_size).map(|x| x as u32 + 1);
-
-            let x = scaled_pos.x;
-            let y = scaled_pos.y;
-            let width = scaled_size.x;
-            let height = scaled_size.y;
-
-            window.canvas.copy(&texture, None, Rect::new(x, y, width, height))
-                .unwrap();
         }
     }
 
-    fn screen_to_local(&self, pos: Point2<i32>) -> Point2<f32>
-    {
-        let mut pos = pos.map(|x| x as f32) / self.window_size.map(|x| x as f32);
-        pos.y = 1.0 - pos.y;
-
-        pos
-    }
-
-    fn screen_to_pos(&self, pos: Point2<i32>) -> Point2<i32>
-    {
-        let pos = self.screen_to_local(pos);
-
-        let scaled_pos = self.camera.pos / self.camera.height as f32;
-
-        let f_pos = (pos + scaled_pos - 0.5) / self.tile_size();
-
-        f_pos.map(|x| x.floor() as i32)
-    }
-
-    fn pos_to_screen(&self, pos: Point2<i32>) -> Point2<f32>
-    {
-        pos.map(|x| x as f32) * self.tile_size()
-    }
-
-    fn pos_to_view(&self, pos: Point2<i32>) -> Point2<f32>
-    {
-        self.pos_to_screen(pos) - (self.camera.pos / self.camera.height as f32) + 0.5
-    }
-
-    fn print_current_scene(&self)
-    {
-        println!("current scene: {}", self.current_scene);
-    }
-
-    fn pressed(&self, control: ControlName) -> bool
+    if benchmark
     {
-        self.controls[control as usize]
-    }
-}
-
-fn main()
-{
-    let window_size = Point2{x: 640, y: 480};
-
-    let window = Rc::new(RefCell::new(GameWindow::new(window_size)));
+        game.benchmark(benchmark_size);
 
-    let mut tiles_amount = 0;
-
-    {
-        let window = window.borrow_mut();
-        let mut assets = window.assets.borrow_mut();
-
-        fs::read_dir("tiles").unwrap().into_iter().inspect(|_| tiles_amount += 1)
-            .zip(iter::repeat(true))
-            .chain(fs::read_dir("ui").unwrap().into_iter().zip(iter::repeat(false)))
-            .map(|(entry, is_tile)| (entry.unwrap(), is_tile))
-            .for_each(|(entry, is_tile)|
-            {
-                let path = entry.path();
-
-                if is_tile
-                {
-                    assets.add_tile(path);
-                } else
-                {
-                    assets.add_texture(path);
-                }
-            });
+        return;
     }
 
-    let game = Game::new(window_size.map(|x| x as usize), window, tiles_amount);
-
     game.run();
 }
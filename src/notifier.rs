@@ -0,0 +1,78 @@
+use std::{
+    fs,
+    io,
+    net::{UdpSocket, SocketAddr},
+    path::{Path, PathBuf}
+};
+
+use crate::format::ExportFormat;
+
+
+// every message is a single line of utf8 text ending with a newline:
+// saved <absolute project path>
+// exported <format extension> <absolute export path>
+// paths go last so they can have spaces in them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change
+{
+    Saved(PathBuf),
+    Exported(ExportFormat, PathBuf)
+}
+
+impl Change
+{
+    pub fn message(&self) -> String
+    {
+        let absolute = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+
+        match self
+        {
+            Self::Saved(path) => format!("saved {}\n", absolute(path).display()),
+            Self::Exported(kind, path) =>
+            {
+                format!("exported {} {}\n", kind.extension(), absolute(path).display())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Notifier
+{
+    // overwrites the file with the last message, so watching its modification time is enough
+    Touch(PathBuf),
+    // sends each message as 1 datagram
+    Udp{socket: UdpSocket, target: SocketAddr}
+}
+
+impl Notifier
+{
+    pub fn touch(path: impl Into<PathBuf>) -> Self
+    {
+        Self::Touch(path.into())
+    }
+
+    pub fn udp(target: SocketAddr) -> io::Result<Self>
+    {
+        let local: SocketAddr = if target.is_ipv4()
+        {
+            ([0, 0, 0, 0], 0).into()
+        } else
+        {
+            ([0u16; 8], 0).into()
+        };
+
+        Ok(Self::Udp{socket: UdpSocket::bind(local)?, target})
+    }
+
+    pub fn notify(&self, change: &Change) -> io::Result<()>
+    {
+        let message = change.message();
+
+        match self
+        {
+            Self::Touch(path) => fs::write(path, message),
+            Self::Udp{socket, target} => socket.send_to(message.as_bytes(), target).map(|_| ())
+        }
+    }
+}
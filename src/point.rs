@@ -13,10 +13,13 @@ use std::{
     }
 };
 
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "sdl")]
 use sdl2::rect::Point as SDLPoint;
 
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Point2<T>
 {
     pub x: T,
@@ -89,6 +92,7 @@ impl Point2<f64>
     }
 }
 
+#[cfg(feature = "sdl")]
 impl Into<SDLPoint> for Point2<usize>
 {
     fn into(self) -> SDLPoint
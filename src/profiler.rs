@@ -0,0 +1,222 @@
+use std::{
+    collections::VecDeque,
+    time::{Instant, Duration}
+};
+
+use sdl2::{
+    rect::Rect,
+    pixels::Color
+};
+
+use crate::{
+    Point2,
+    Assets,
+    Renderer,
+    ui::{Label, Align, draw_label}
+};
+
+
+// how many of the last frames the graph shows
+const HISTORY: usize = 120;
+
+// in pixels
+const BAR_WIDTH: u32 = 2;
+const GRAPH_HEIGHT: u32 = 80;
+const LINE_HEIGHT: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase
+{
+    Events,
+    Scene,
+    Ui
+}
+
+impl Phase
+{
+    const COUNT: usize = 3;
+
+    fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Events => "events",
+            Self::Scene => "scene",
+            Self::Ui => "ui"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FrameTimes
+{
+    // only the work, not the sleeping between frames
+    total: Duration,
+    phases: [Duration; Phase::COUNT],
+    draw_calls: usize
+}
+
+pub struct Profiler
+{
+    frames: VecDeque<FrameTimes>,
+    current: FrameTimes,
+    frame_start: Instant,
+    phase_start: Instant
+}
+
+impl Profiler
+{
+    pub fn new() -> Self
+    {
+        Self{
+            frames: VecDeque::with_capacity(HISTORY),
+            current: FrameTimes::default(),
+            frame_start: Instant::now(),
+            phase_start: Instant::now()
+        }
+    }
+
+    pub fn begin_frame(&mut self)
+    {
+        self.current = FrameTimes::default();
+
+        self.frame_start = Instant::now();
+        self.phase_start = self.frame_start;
+    }
+
+    pub fn begin_phase(&mut self)
+    {
+        self.phase_start = Instant::now();
+    }
+
+    // a phase can happen more than once a frame, like the scene with the split view
+    pub fn end_phase(&mut self, phase: Phase)
+    {
+        self.current.phases[phase as usize] += self.phase_start.elapsed();
+    }
+
+    pub fn end_frame(&mut self, draw_calls: usize)
+    {
+        self.current.total = self.frame_start.elapsed();
+        self.current.draw_calls = draw_calls;
+
+        if self.frames.len() == HISTORY
+        {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back(self.current);
+    }
+
+    fn average(&self, f: impl Fn(&FrameTimes) -> Duration) -> Duration
+    {
+        let total: Duration = self.frames.iter().map(f).sum();
+
+        total / self.frames.len().max(1) as u32
+    }
+
+    // the budget is how long a frame can take at the fps cap, its line goes in the middle
+    pub fn draw(
+        &self,
+        renderer: &mut dyn Renderer,
+        assets: &Assets,
+        pos: Point2<i32>,
+        budget: Duration
+    )
+    {
+        let last = self.frames.back().copied().unwrap_or_default();
+
+        let ms = |x: Duration| x.as_secs_f32() * 1000.0;
+
+        let slowest = self.frames.iter().map(|frame| frame.total).max().unwrap_or_default();
+
+        let mut lines = vec![
+            format!(
+                "frame {:.2}ms avg {:.2}ms max {:.2}ms",
+                ms(last.total),
+                ms(self.average(|frame| frame.total)),
+                ms(slowest)
+            )
+        ];
+
+        lines.extend([Phase::Events, Phase::Scene, Phase::Ui].into_iter().map(|phase|
+        {
+            let index = phase as usize;
+
+            format!(
+                "{} {:.2}ms avg {:.2}ms",
+                phase.name(),
+                ms(last.phases[index]),
+                ms(self.average(|frame| frame.phases[index]))
+            )
+        }));
+
+        lines.push(format!("draw calls {}", last.draw_calls));
+
+        let memory = assets.texture_memory();
+        let megabytes = |bytes: usize| bytes as f32 / (1024.0 * 1024.0);
+
+        lines.push(format!(
+            "textures {} {:.1}mb ({} unused {:.1}mb)",
+            memory.textures,
+            megabytes(memory.bytes),
+            memory.unused,
+            megabytes(memory.unused_bytes)
+        ));
+
+        let width = HISTORY as u32 * BAR_WIDTH;
+        let text_height = lines.len() as u32 * LINE_HEIGHT;
+
+        let margin = 4;
+        let background = Rect::new(
+            pos.x - margin,
+            pos.y - margin,
+            width + margin as u32 * 2,
+            text_height + GRAPH_HEIGHT + margin as u32 * 3
+        );
+
+        renderer.set_color(Color::RGB(20, 20, 30));
+        renderer.fill_rect(background);
+
+        let label = Label{
+            text: lines.join("\n"),
+            font_size: Some(LINE_HEIGHT as f32 - 2.0),
+            color: Color::WHITE,
+            align: Align::Left
+        };
+
+        draw_label(renderer, assets, &label, 255, pos, Point2::new(width, text_height));
+
+        let bottom = pos.y + (text_height + margin as u32 + GRAPH_HEIGHT) as i32;
+
+        // twice the budget fills the graph, anything slower just gets cut off
+        let scale = GRAPH_HEIGHT as f32 / (ms(budget) * 2.0).max(0.001);
+
+        let start = pos.x + ((HISTORY - self.frames.len()) as u32 * BAR_WIDTH) as i32;
+        self.frames.iter().enumerate().for_each(|(index, frame)|
+        {
+            let height = (ms(frame.total) * scale).round().clamp(1.0, GRAPH_HEIGHT as f32) as u32;
+
+            let color = if frame.total > budget
+            {
+                Color::RGB(255, 80, 80)
+            } else
+            {
+                Color::RGB(80, 220, 80)
+            };
+
+            renderer.set_color(color);
+
+            let x = start + (index as u32 * BAR_WIDTH) as i32;
+            renderer.fill_rect(Rect::new(x, bottom - height as i32, BAR_WIDTH, height));
+        });
+
+        let budget_y = bottom - (GRAPH_HEIGHT / 2) as i32;
+
+        renderer.set_color(Color::RGB(255, 255, 255));
+        renderer.draw_line(
+            Point2::new(pos.x, budget_y),
+            Point2::new(pos.x + width as i32, budget_y)
+        );
+    }
+}
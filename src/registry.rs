@@ -0,0 +1,155 @@
+use std::{
+    fmt,
+    collections::HashMap
+};
+
+use crate::Tile;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError
+{
+    IdTaken(Tile),
+    NameTaken(String)
+}
+
+impl fmt::Display for RegistryError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Self::IdTaken(tile) => write!(f, "tile id {} is already registered", tile.id()),
+            Self::NameTaken(name) => write!(f, "tile name {name} is already registered")
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TileEntry<T>
+{
+    pub name: String,
+    pub value: T
+}
+
+// maps tiles to whatever the user needs per tile, ids dont depend on the order things got added
+#[derive(Debug, Clone)]
+pub struct TileRegistry<T>
+{
+    names: HashMap<String, Tile>,
+    entries: Vec<Option<TileEntry<T>>>
+}
+
+impl<T> Default for TileRegistry<T>
+{
+    fn default() -> Self
+    {
+        Self{names: HashMap::new(), entries: Vec::new()}
+    }
+}
+
+impl<T> TileRegistry<T>
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn register(
+        &mut self,
+        tile: Tile,
+        name: impl Into<String>,
+        value: T
+    ) -> Result<(), RegistryError>
+    {
+        assert!(!tile.is_none());
+
+        let name = name.into();
+
+        if self.get(tile).is_some()
+        {
+            return Err(RegistryError::IdTaken(tile));
+        }
+
+        if self.names.contains_key(&name)
+        {
+            return Err(RegistryError::NameTaken(name));
+        }
+
+        let index = tile.id() - 1;
+        if self.entries.len() <= index
+        {
+            self.entries.resize_with(index + 1, || None);
+        }
+
+        self.names.insert(name.clone(), tile);
+        self.entries[index] = Some(TileEntry{name, value});
+
+        Ok(())
+    }
+
+    // registers after the highest id so ids of removed tiles dont get reused
+    pub fn push(&mut self, name: impl Into<String>, value: T) -> Result<Tile, RegistryError>
+    {
+        let tile = Tile::new(self.entries.len());
+
+        self.register(tile, name, value).map(|_| tile)
+    }
+
+    pub fn remove(&mut self, tile: Tile) -> Option<TileEntry<T>>
+    {
+        if tile.is_none()
+        {
+            return None;
+        }
+
+        let entry = self.entries.get_mut(tile.id() - 1)?.take()?;
+
+        self.names.remove(&entry.name);
+
+        Some(entry)
+    }
+
+    pub fn get(&self, tile: Tile) -> Option<&TileEntry<T>>
+    {
+        if tile.is_none()
+        {
+            return None;
+        }
+
+        self.entries.get(tile.id() - 1).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, tile: Tile) -> Option<&mut TileEntry<T>>
+    {
+        if tile.is_none()
+        {
+            return None;
+        }
+
+        self.entries.get_mut(tile.id() - 1).and_then(Option::as_mut)
+    }
+
+    pub fn tile(&self, name: &str) -> Option<Tile>
+    {
+        self.names.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(Tile, &TileEntry<T>)>
+    {
+        self.entries.iter().enumerate().filter_map(|(index, entry)|
+        {
+            entry.as_ref().map(|entry| (Tile::new(index), entry))
+        })
+    }
+}
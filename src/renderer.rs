@@ -0,0 +1,661 @@
+use std::mem;
+
+use sdl2::{
+    sys::SDL_ScaleMode,
+    rect::Rect,
+    video::{Window, WindowContext},
+    render::{Canvas, Texture, TextureCreator, BlendMode},
+    pixels::{Color, PixelFormatEnum}
+};
+
+use crate::{Point2, Image};
+
+
+// textures live in the renderer, everything else just holds onto these
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderTexture(usize);
+
+impl RenderTexture
+{
+    pub fn new(index: usize) -> Self
+    {
+        Self(index)
+    }
+
+    pub fn index(&self) -> usize
+    {
+        self.0
+    }
+}
+
+// how a texture looks when its drawn bigger or smaller than it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter
+{
+    Nearest,
+    Linear
+}
+
+// everything the editor and the ui draw with, positions r in pixels of the current target
+pub trait Renderer
+{
+    // of the target if theres one
+    fn size(&self) -> Point2<u32>;
+
+    fn create_texture(&mut self, image: &Image) -> RenderTexture;
+
+    // something to draw into instead of the screen, none if that isnt supported
+    fn create_target(&mut self, size: Point2<u32>) -> Option<RenderTexture>;
+
+    fn destroy_texture(&mut self, texture: RenderTexture);
+
+    fn texture_size(&self, texture: RenderTexture) -> Point2<u32>;
+
+    // textures start out as nearest
+    fn set_filter(&mut self, texture: RenderTexture, filter: Filter);
+
+    // everything gets drawn into the texture until its set back to none
+    fn set_target(&mut self, texture: Option<RenderTexture>);
+
+    // used by clear and by all the lines and rects
+    fn set_color(&mut self, color: Color);
+
+    fn clear(&mut self);
+
+    fn draw_line(&mut self, start: Point2<i32>, end: Point2<i32>);
+
+    fn draw_rect(&mut self, rect: Rect);
+
+    fn fill_rect(&mut self, rect: Rect);
+
+    // the tint gets multiplied with the colors and alpha of the texture
+    fn copy_tinted(
+        &mut self,
+        texture: RenderTexture,
+        source: Option<Rect>,
+        destination: Rect,
+        tint: Color
+    );
+
+    fn copy(&mut self, texture: RenderTexture, source: Option<Rect>, destination: Rect)
+    {
+        self.copy_tinted(texture, source, destination, Color::WHITE);
+    }
+
+    // moves the origin to the corner of the area and cuts off everything outside of it
+    fn set_viewport(&mut self, area: Option<Rect>);
+
+    // cuts off everything outside of the area without moving anything
+    fn set_clip(&mut self, area: Option<Rect>);
+
+    fn present(&mut self);
+
+    // how many things got drawn since the last time this got called
+    fn take_draw_calls(&mut self) -> usize;
+}
+
+pub struct SdlRenderer
+{
+    // with unsafe_textures these dont borrow the creator, dropping one does nothing and
+    // whatever is left gets freed along with the canvas
+    textures: Vec<Option<Texture>>,
+    creator: TextureCreator<WindowContext>,
+    canvas: Canvas<Window>,
+    draw_calls: usize
+}
+
+impl SdlRenderer
+{
+    pub fn new(canvas: Canvas<Window>) -> Self
+    {
+        Self{
+            textures: Vec::new(),
+            creator: canvas.texture_creator(),
+            canvas,
+            draw_calls: 0
+        }
+    }
+
+    fn push(&mut self, mut texture: Texture) -> RenderTexture
+    {
+        texture.set_blend_mode(BlendMode::Blend);
+
+        // destroyed ones leave holes that can be reused
+        if let Some(index) = self.textures.iter().position(Option::is_none)
+        {
+            self.textures[index] = Some(texture);
+
+            RenderTexture(index)
+        } else
+        {
+            self.textures.push(Some(texture));
+
+            RenderTexture(self.textures.len() - 1)
+        }
+    }
+
+    fn texture(&self, texture: RenderTexture) -> &Texture
+    {
+        self.textures[texture.0].as_ref().expect("texture was destroyed")
+    }
+
+    pub fn set_title(&mut self, title: &str)
+    {
+        if let Err(err) = self.canvas.window_mut().set_title(title)
+        {
+            eprintln!("cant set window title: {err}");
+        }
+    }
+}
+
+impl Renderer for SdlRenderer
+{
+    fn size(&self) -> Point2<u32>
+    {
+        let (width, height) = self.canvas.output_size().unwrap();
+
+        Point2::new(width, height)
+    }
+
+    fn create_texture(&mut self, image: &Image) -> RenderTexture
+    {
+        let mut texture = self.creator.create_texture_static(
+            PixelFormatEnum::RGBA32,
+            image.size().x as u32,
+            image.size().y as u32
+        ).unwrap();
+
+        texture.update(None, image.data(), image.bytes_row()).unwrap();
+
+        self.push(texture)
+    }
+
+    fn create_target(&mut self, size: Point2<u32>) -> Option<RenderTexture>
+    {
+        let texture = self.creator.create_texture_target(
+            PixelFormatEnum::RGBA8888,
+            size.x,
+            size.y
+        ).ok()?;
+
+        Some(self.push(texture))
+    }
+
+    fn destroy_texture(&mut self, texture: RenderTexture)
+    {
+        if let Some(texture) = self.textures[texture.0].take()
+        {
+            // fine since the canvas it came from is right here and still alive
+            unsafe{ texture.destroy(); }
+        }
+    }
+
+    fn texture_size(&self, texture: RenderTexture) -> Point2<u32>
+    {
+        let query = self.texture(texture).query();
+
+        Point2::new(query.width, query.height)
+    }
+
+    fn set_filter(&mut self, texture: RenderTexture, filter: Filter)
+    {
+        let mode = match filter
+        {
+            Filter::Nearest => SDL_ScaleMode::SDL_ScaleModeNearest,
+            Filter::Linear => SDL_ScaleMode::SDL_ScaleModeLinear
+        };
+
+        unsafe{ sdl2::sys::SDL_SetTextureScaleMode(self.texture(texture).raw(), mode); }
+    }
+
+    fn set_target(&mut self, texture: Option<RenderTexture>)
+    {
+        let texture = texture.map(|texture| self.texture(texture).raw())
+            .unwrap_or(std::ptr::null_mut());
+
+        unsafe{ sdl2::sys::SDL_SetRenderTarget(self.canvas.raw(), texture); }
+    }
+
+    fn set_color(&mut self, color: Color)
+    {
+        self.canvas.set_draw_color(color);
+    }
+
+    fn clear(&mut self)
+    {
+        self.draw_calls += 1;
+
+        self.canvas.clear();
+    }
+
+    fn draw_line(&mut self, start: Point2<i32>, end: Point2<i32>)
+    {
+        self.draw_calls += 1;
+
+        self.canvas.draw_line((start.x, start.y), (end.x, end.y)).unwrap();
+    }
+
+    fn draw_rect(&mut self, rect: Rect)
+    {
+        self.draw_calls += 1;
+
+        self.canvas.draw_rect(rect).unwrap();
+    }
+
+    fn fill_rect(&mut self, rect: Rect)
+    {
+        self.draw_calls += 1;
+
+        self.canvas.fill_rect(rect).unwrap();
+    }
+
+    fn copy_tinted(
+        &mut self,
+        texture: RenderTexture,
+        source: Option<Rect>,
+        destination: Rect,
+        tint: Color
+    )
+    {
+        self.draw_calls += 1;
+
+        let texture = self.textures[texture.0].as_mut().expect("texture was destroyed");
+
+        texture.set_color_mod(tint.r, tint.g, tint.b);
+        texture.set_alpha_mod(tint.a);
+
+        self.canvas.copy(texture, source, destination).unwrap();
+    }
+
+    fn set_viewport(&mut self, area: Option<Rect>)
+    {
+        self.canvas.set_viewport(area);
+    }
+
+    fn set_clip(&mut self, area: Option<Rect>)
+    {
+        self.canvas.set_clip_rect(area);
+    }
+
+    fn present(&mut self)
+    {
+        self.canvas.present();
+    }
+
+    fn take_draw_calls(&mut self) -> usize
+    {
+        mem::take(&mut self.draw_calls)
+    }
+}
+
+// sdl blending, the alpha of whats underneath gets kept around too
+fn blend(pixel: &mut [u8], color: [u8; 4])
+{
+    let alpha = color[3] as u32;
+    let mix = |a: u8, b: u8| ((a as u32 * alpha + b as u32 * (255 - alpha)) / 255) as u8;
+
+    pixel[0] = mix(color[0], pixel[0]);
+    pixel[1] = mix(color[1], pixel[1]);
+    pixel[2] = mix(color[2], pixel[2]);
+    pixel[3] = (alpha + pixel[3] as u32 * (255 - alpha) / 255) as u8;
+}
+
+// draws into images in memory, works without a window or a gpu
+pub struct SoftwareRenderer
+{
+    screen: Image,
+    textures: Vec<Option<Image>>,
+    target: Option<RenderTexture>,
+    color: Color,
+    viewport: Option<Rect>,
+    clip: Option<Rect>,
+    draw_calls: usize
+}
+
+impl SoftwareRenderer
+{
+    pub fn new(size: Point2<u32>) -> Self
+    {
+        let size = size.map(|x| x as usize);
+
+        Self{
+            screen: Image::from_raw(size, vec![0; size.x * size.y * 4]),
+            textures: Vec::new(),
+            target: None,
+            color: Color::RGB(0, 0, 0),
+            viewport: None,
+            clip: None,
+            draw_calls: 0
+        }
+    }
+
+    // starts over with an empty screen, textures stay
+    pub fn resize(&mut self, size: Point2<u32>)
+    {
+        let size = size.map(|x| x as usize);
+
+        self.screen = Image::from_raw(size, vec![0; size.x * size.y * 4]);
+    }
+
+    // everything that didnt get drawn into a target
+    pub fn image(&self) -> &Image
+    {
+        &self.screen
+    }
+
+    fn push(&mut self, image: Image) -> RenderTexture
+    {
+        if let Some(index) = self.textures.iter().position(Option::is_none)
+        {
+            self.textures[index] = Some(image);
+
+            RenderTexture(index)
+        } else
+        {
+            self.textures.push(Some(image));
+
+            RenderTexture(self.textures.len() - 1)
+        }
+    }
+
+    fn image_of(&self, texture: RenderTexture) -> &Image
+    {
+        self.textures[texture.0].as_ref().expect("texture was destroyed")
+    }
+
+    fn target_mut(&mut self) -> &mut Image
+    {
+        match self.target
+        {
+            Some(texture) => self.textures[texture.0].as_mut().expect("texture was destroyed"),
+            None => &mut self.screen
+        }
+    }
+
+    // the origin and the corners of the part that can be drawn in, the end is exclusive
+    fn drawable(&self) -> (Point2<i32>, Point2<i32>, Point2<i32>)
+    {
+        let size = self.size().map(|x| x as i32);
+
+        let (origin, mut end) = match self.viewport
+        {
+            Some(area) =>
+            {
+                (Point2::new(area.x(), area.y()), Point2::new(area.right(), area.bottom()))
+            },
+            None => (Point2::new(0, 0), size)
+        };
+
+        let mut start = origin;
+
+        // the clip is inside of the viewport
+        if let Some(clip) = self.clip
+        {
+            let clip_start = origin + Point2::new(clip.x(), clip.y());
+            let clip_end = origin + Point2::new(clip.right(), clip.bottom());
+
+            start = start.zip(clip_start).map(|(a, b)| a.max(b));
+            end = end.zip(clip_end).map(|(a, b)| a.min(b));
+        }
+
+        (origin, start.map(|x| x.max(0)), end.zip(size).map(|(a, b)| a.min(b)))
+    }
+
+    // f gets the position inside of the area and gives back the color for it
+    fn draw_pixels(
+        &mut self,
+        pos: Point2<i32>,
+        size: Point2<u32>,
+        blended: bool,
+        mut f: impl FnMut(Point2<i32>) -> [u8; 4]
+    )
+    {
+        let (origin, min, max) = self.drawable();
+
+        let start = origin + pos;
+        let end = start + size.map(|x| x as i32);
+
+        let from = start.zip(min).map(|(a, b)| a.max(b));
+        let to = end.zip(max).map(|(a, b)| a.min(b));
+
+        let target = self.target_mut();
+        let width = target.size().x;
+        let data = target.data_mut();
+
+        (from.y..to.y).for_each(|y|
+        {
+            (from.x..to.x).for_each(|x|
+            {
+                let color = f(Point2::new(x, y) - start);
+
+                let index = (y as usize * width + x as usize) * 4;
+                let pixel = &mut data[index..index + 4];
+
+                if blended
+                {
+                    blend(pixel, color);
+                } else
+                {
+                    pixel.copy_from_slice(&color);
+                }
+            });
+        });
+    }
+
+    fn draw_color(&self) -> [u8; 4]
+    {
+        [self.color.r, self.color.g, self.color.b, self.color.a]
+    }
+}
+
+impl Renderer for SoftwareRenderer
+{
+    fn size(&self) -> Point2<u32>
+    {
+        let image = self.target.map(|texture| self.image_of(texture)).unwrap_or(&self.screen);
+
+        image.size().map(|x| x as u32)
+    }
+
+    fn create_texture(&mut self, image: &Image) -> RenderTexture
+    {
+        self.push(Image::from_raw(*image.size(), image.data().to_vec()))
+    }
+
+    fn create_target(&mut self, size: Point2<u32>) -> Option<RenderTexture>
+    {
+        let size = size.map(|x| x as usize);
+
+        Some(self.push(Image::from_raw(size, vec![0; size.x * size.y * 4])))
+    }
+
+    fn destroy_texture(&mut self, texture: RenderTexture)
+    {
+        self.textures[texture.0] = None;
+    }
+
+    fn texture_size(&self, texture: RenderTexture) -> Point2<u32>
+    {
+        self.image_of(texture).size().map(|x| x as u32)
+    }
+
+    // its always nearest, good enough for pixel art
+    fn set_filter(&mut self, _texture: RenderTexture, _filter: Filter) {}
+
+    fn set_target(&mut self, texture: Option<RenderTexture>)
+    {
+        self.target = texture;
+    }
+
+    fn set_color(&mut self, color: Color)
+    {
+        self.color = color;
+    }
+
+    // same as sdl, the viewport and clip dont matter here
+    fn clear(&mut self)
+    {
+        self.draw_calls += 1;
+
+        let color = self.draw_color();
+
+        self.target_mut().data_mut().chunks_exact_mut(4).for_each(|pixel|
+        {
+            pixel.copy_from_slice(&color);
+        });
+    }
+
+    fn draw_line(&mut self, start: Point2<i32>, end: Point2<i32>)
+    {
+        self.draw_calls += 1;
+
+        let color = self.draw_color();
+
+        let delta = (end - start).abs();
+        let step = (end - start).map(|x| x.signum());
+
+        let mut pos = start;
+        let mut error = delta.x - delta.y;
+
+        loop
+        {
+            self.draw_pixels(pos, Point2::new(1, 1), false, |_| color);
+
+            if pos == end
+            {
+                break;
+            }
+
+            let doubled = error * 2;
+
+            if doubled > -delta.y
+            {
+                error -= delta.y;
+                pos.x += step.x;
+            }
+
+            if doubled < delta.x
+            {
+                error += delta.x;
+                pos.y += step.y;
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, rect: Rect)
+    {
+        self.draw_calls += 1;
+
+        let color = self.draw_color();
+
+        let pos = Point2::new(rect.x(), rect.y());
+        let (width, height) = (rect.width(), rect.height());
+
+        let bottom = pos + Point2::new(0, height as i32 - 1);
+        let right = pos + Point2::new(width as i32 - 1, 0);
+
+        self.draw_pixels(pos, Point2::new(width, 1), false, |_| color);
+        self.draw_pixels(bottom, Point2::new(width, 1), false, |_| color);
+        self.draw_pixels(pos, Point2::new(1, height), false, |_| color);
+        self.draw_pixels(right, Point2::new(1, height), false, |_| color);
+    }
+
+    fn fill_rect(&mut self, rect: Rect)
+    {
+        self.draw_calls += 1;
+
+        let color = self.draw_color();
+
+        let size = Point2::new(rect.width(), rect.height());
+
+        self.draw_pixels(Point2::new(rect.x(), rect.y()), size, false, |_| color);
+    }
+
+    // nearest neighbor scaling, tiles r pixel art anyway
+    fn copy_tinted(
+        &mut self,
+        texture: RenderTexture,
+        source: Option<Rect>,
+        destination: Rect,
+        tint: Color
+    )
+    {
+        self.draw_calls += 1;
+
+        // taken out so it can be read while drawing into the target, unless it is the target,
+        // then it gets read from a copy so the drawing doesnt see its own changes
+        let is_target = self.target == Some(texture);
+
+        let image = if is_target
+        {
+            self.textures[texture.0].clone()
+        } else
+        {
+            self.textures[texture.0].take()
+        };
+
+        let Some(image) = image
+        else
+        {
+            eprintln!("cant draw texture {}, it was destroyed", texture.0);
+
+            return;
+        };
+
+        let source = source.unwrap_or_else(||
+        {
+            Rect::new(0, 0, image.size().x as u32, image.size().y as u32)
+        });
+
+        let size = Point2::new(destination.width(), destination.height());
+
+        let width = image.bytes_row();
+        let data = image.data();
+
+        let tint = [tint.r, tint.g, tint.b, tint.a];
+
+        self.draw_pixels(Point2::new(destination.x(), destination.y()), size, true, |pos|
+        {
+            let scaled = pos.map(|x| x as usize)
+                .zip(Point2::new(source.width(), source.height()).map(|x| x as usize))
+                .zip(size.map(|x| x as usize))
+                .map(|((pos, source), size)| pos * source / size);
+
+            let x = source.x() as usize + scaled.x;
+            let y = source.y() as usize + scaled.y;
+
+            let index = y * width + x * 4;
+
+            let mut color = [0; 4];
+            color.iter_mut().enumerate().for_each(|(channel, value)|
+            {
+                *value = (data[index + channel] as u32 * tint[channel] as u32 / 255) as u8;
+            });
+
+            color
+        });
+
+        if !is_target
+        {
+            self.textures[texture.0] = Some(image);
+        }
+    }
+
+    fn set_viewport(&mut self, area: Option<Rect>)
+    {
+        self.viewport = area;
+    }
+
+    fn set_clip(&mut self, area: Option<Rect>)
+    {
+        self.clip = area;
+    }
+
+    // theres nothing to show it on
+    fn present(&mut self) {}
+
+    fn take_draw_calls(&mut self) -> usize
+    {
+        mem::take(&mut self.draw_calls)
+    }
+}
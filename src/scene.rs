@@ -0,0 +1,644 @@
+use std::{
+    mem,
+    ops::{Index, IndexMut},
+    sync::atomic::{AtomicU64, Ordering},
+    collections::{HashMap, HashSet, BTreeMap}
+};
+
+use crate::{Point2, container::Container2d};
+
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tile(usize);
+
+impl Tile
+{
+    pub fn new(id: usize) -> Self
+    {
+        Tile(id + 1)
+    }
+
+    pub fn none() -> Self
+    {
+        Self(0)
+    }
+
+    pub fn is_none(&self) -> bool
+    {
+        self.0 == 0
+    }
+
+    pub fn from_id(id: usize) -> Self
+    {
+        Self(id)
+    }
+
+    pub fn id(&self) -> usize
+    {
+        self.0
+    }
+}
+
+// side length of the square chunks the tiles r stored in
+const CHUNK_SIZE: i32 = 32;
+
+const EMPTY: &Tile = &Tile(0);
+
+// multiplying by white leaves the tile as it is
+pub const NO_TINT: [u8; 3] = [255, 255, 255];
+
+// shared by every scene so 2 different scenes never end up with the same revision
+static REVISIONS: AtomicU64 = AtomicU64::new(0);
+
+fn next_revision() -> u64
+{
+    REVISIONS.fetch_add(1, Ordering::Relaxed)
+}
+
+// a marker on a tile that leads somewhere in another scene, like a door between rooms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneLink
+{
+    pub scene: usize,
+    pub pos: Point2<i32>
+}
+
+// where a cell ends up after the scene gets moved around, none if it was outside of it
+pub type CellMap = Box<dyn Fn(Point2<i32>) -> Option<Point2<i32>>>;
+
+#[derive(Debug, Clone)]
+pub struct Scene
+{
+    // only chunks that ever got painted in exist
+    chunks: HashMap<Point2<i32>, Container2d<Tile>>,
+    // the area the scene covers, local positions r global ones plus the offset
+    offset: Point2<i32>,
+    size: Point2<usize>,
+    // chunk positions r global ones plus this, only changes when tiles get moved around
+    shift: Point2<i32>,
+    // only cells that got rerolled r in here, everything else uses 0
+    variant_seeds: HashMap<Point2<i32>, u32>,
+    // same for tinted cells, the colors get multiplied with the tile
+    tints: HashMap<Point2<i32>, [u8; 3]>,
+    links: HashMap<Point2<i32>, SceneLink>,
+    name: Option<String>,
+    // whatever a game wants to know about the level, like its music
+    properties: BTreeMap<String, String>,
+    revision: u64
+}
+
+impl Scene
+{
+    pub fn new(size: Point2<usize>, offset: Point2<i32>) -> Self
+    {
+        Self{
+            chunks: HashMap::new(),
+            offset,
+            size,
+            shift: offset,
+            variant_seeds: HashMap::new(),
+            tints: HashMap::new(),
+            links: HashMap::new(),
+            name: None,
+            properties: BTreeMap::new(),
+            revision: next_revision()
+        }
+    }
+
+    pub fn from_container(container: Container2d<Tile>, offset: Point2<i32>) -> Self
+    {
+        let mut this = Self::new(*container.size(), offset);
+
+        container.iter().filter(|(_, tile)| !tile.is_none()).for_each(|(pos, tile)|
+        {
+            this[pos.map(|x| x as i32) - offset] = *tile;
+        });
+
+        this
+    }
+
+    // the whole area of the scene as one grid, empty parts included
+    pub fn to_container(&self) -> Container2d<Tile>
+    {
+        let mut container = Container2d::new(self.size);
+
+        self.chunks.iter().for_each(|(chunk_pos, chunk)|
+        {
+            chunk.iter().filter(|(_, tile)| !tile.is_none()).for_each(|(pos, tile)|
+            {
+                let local = Self::chunk_to_global(*chunk_pos, pos) - self.shift + self.offset;
+
+                container[local.map(|x| x as usize)] = *tile;
+            });
+        });
+
+        container
+    }
+
+    pub fn size(&self) -> Point2<usize>
+    {
+        self.size
+    }
+
+    pub fn name(&self) -> Option<&str>
+    {
+        self.name.as_deref()
+    }
+
+    pub fn set_name(&mut self, name: Option<String>)
+    {
+        self.name = name;
+    }
+
+    pub fn property(&self, key: &str) -> Option<&str>
+    {
+        self.properties.get(key).map(String::as_str)
+    }
+
+    // an empty value removes it
+    pub fn set_property(&mut self, key: impl Into<String>, value: impl Into<String>)
+    {
+        let (key, value) = (key.into(), value.into());
+
+        if value.is_empty()
+        {
+            self.properties.remove(&key);
+        } else
+        {
+            self.properties.insert(key, value);
+        }
+    }
+
+    pub fn properties(&self) -> impl Iterator<Item=(&str, &str)>
+    {
+        self.properties.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn offset(&self) -> Point2<i32>
+    {
+        self.offset
+    }
+
+    // changes whenever the tiles do, clones keep it since they look the same
+    pub fn revision(&self) -> u64
+    {
+        self.revision
+    }
+
+    pub fn extend_to_contain(&mut self, global_pos: Point2<i32>)
+    {
+        let pos = global_pos.map(|x| x as i32) + self.offset;
+
+        let size = self.size.map(|x| x as i32);
+        let distance = pos.zip(size).map(|(pos, size)|
+        {
+            if pos >= size
+            {
+                pos - size + 1
+            } else if pos < 0
+            {
+                pos
+            } else
+            {
+                0
+            }
+        });
+
+        let new_size = size + distance.map(|x| x.abs());
+
+        // the chunks dont care, its just the area that grows
+        if new_size != size
+        {
+            let this_offset = distance.map(|x| if x < 0 { x } else { 0 });
+
+            self.offset -= this_offset;
+            self.size = new_size.map(|x| x as usize);
+        }
+    }
+
+    // inclusive corners of the area with non empty tiles
+    pub fn bounds(&self) -> Option<(Point2<i32>, Point2<i32>)>
+    {
+        self.filled().fold(None, |acc, (pos, _)|
+        {
+            Some(match acc
+            {
+                Some((min, max)) =>
+                (
+                    min.zip(pos).map(|(a, b)| a.min(b)),
+                    max.zip(pos).map(|(a, b)| a.max(b))
+                ),
+                None => (pos, pos)
+            })
+        })
+    }
+
+    // the top left corner of the cropped scene ends up at the origin
+    pub fn cropped(&self, padding: usize) -> Self
+    {
+        let (min, max) = if let Some(x) = self.bounds()
+        {
+            x
+        } else
+        {
+            let mut empty = Self::new(Point2::new(0, 0), Point2::new(0, 0));
+            empty.properties = self.properties.clone();
+
+            return empty;
+        };
+
+        let padding = padding as i32;
+
+        let min = min - padding;
+        let max = max + padding;
+
+        let size = (max - min) + 1;
+
+        let mut container = Container2d::new(size.map(|x| x as usize));
+
+        self.filled().for_each(|(pos, tile)|
+        {
+            container[(pos - min).map(|x| x as usize)] = *tile;
+        });
+
+        let offset = Point2::new(0, max.y - min.y);
+
+        let mut cropped = Self::from_container(container, offset);
+        cropped.properties = self.properties.clone();
+
+        // the cell at min ends up at the local origin
+        let moved = |pos: Point2<i32>| pos - min - offset;
+
+        cropped.variant_seeds = moved_cells(&self.variant_seeds, |pos| Some(moved(pos)));
+        cropped.tints = moved_cells(&self.tints, |pos| Some(moved(pos)));
+        cropped.links = moved_cells(&self.links, |pos| Some(moved(pos)));
+
+        cropped
+    }
+
+    // all of these keep the scene inside its current bounds, the returned map is for
+    // fixing up links from other scenes that point in here
+    pub fn mirror_x(&mut self) -> CellMap
+    {
+        let size = self.size;
+
+        self.remap(size, move |pos| Point2::new(size.x - 1 - pos.x, pos.y))
+    }
+
+    pub fn mirror_y(&mut self) -> CellMap
+    {
+        let size = self.size;
+
+        self.remap(size, move |pos| Point2::new(pos.x, size.y - 1 - pos.y))
+    }
+
+    pub fn rotate_clockwise(&mut self) -> CellMap
+    {
+        let size = self.size;
+
+        self.remap(Point2::new(size.y, size.x), move |pos| Point2::new(pos.y, size.x - 1 - pos.x))
+    }
+
+    pub fn translate(&mut self, amount: Point2<i32>) -> CellMap
+    {
+        self.offset -= amount;
+        self.shift -= amount;
+
+        let moved = move |pos| Some(pos + amount);
+        self.move_cells(moved);
+
+        self.revision = next_revision();
+
+        Box::new(moved)
+    }
+
+    fn remap(
+        &mut self,
+        new_size: Point2<usize>,
+        f: impl Fn(Point2<usize>) -> Point2<usize> + 'static
+    ) -> CellMap
+    {
+        let old_size = self.size;
+
+        let mut new_container = Container2d::new(new_size);
+
+        for (pos, tile) in self.to_container().iter()
+        {
+            new_container[f(pos)] = *tile;
+        }
+
+        let remapped = Self::from_container(new_container, self.offset);
+
+        self.chunks = remapped.chunks;
+        self.size = remapped.size;
+        self.shift = remapped.shift;
+
+        // the offset stays the same so global positions go through the same local ones
+        let offset = self.offset;
+
+        let moved = move |pos: Point2<i32>|
+        {
+            let local = pos + offset;
+
+            let inside = local.x >= 0 && local.y >= 0
+                && (local.x as usize) < old_size.x && (local.y as usize) < old_size.y;
+
+            inside.then(|| f(local.map(|x| x as usize)).map(|x| x as i32) - offset)
+        };
+
+        self.move_cells(&moved);
+
+        self.revision = next_revision();
+
+        Box::new(moved)
+    }
+
+    // keeps the stuff thats stored per cell on the same tiles after theyre moved around
+    fn move_cells(&mut self, moved: impl Fn(Point2<i32>) -> Option<Point2<i32>>)
+    {
+        self.variant_seeds = moved_cells(&mem::take(&mut self.variant_seeds), &moved);
+        self.tints = moved_cells(&mem::take(&mut self.tints), &moved);
+        self.links = moved_cells(&mem::take(&mut self.links), &moved);
+    }
+
+    // for links from anywhere into a scene that just got moved around with this map
+    pub fn move_link_targets(&mut self, scene: usize, moved: &CellMap)
+    {
+        self.links.values_mut().filter(|link| link.scene == scene).for_each(|link|
+        {
+            if let Some(pos) = moved(link.pos)
+            {
+                link.pos = pos;
+            }
+        });
+    }
+
+    pub fn variant_seed(&self, pos: Point2<i32>) -> u32
+    {
+        self.variant_seeds.get(&pos).copied().unwrap_or(0)
+    }
+
+    pub fn set_variant_seed(&mut self, pos: Point2<i32>, seed: u32)
+    {
+        self.revision = next_revision();
+
+        if seed == 0
+        {
+            self.variant_seeds.remove(&pos);
+        } else
+        {
+            self.variant_seeds.insert(pos, seed);
+        }
+    }
+
+    pub fn reroll(&mut self, pos: Point2<i32>)
+    {
+        self.set_variant_seed(pos, self.variant_seed(pos).wrapping_add(1));
+    }
+
+    pub fn variant_seeds(&self) -> impl Iterator<Item=(Point2<i32>, u32)> + '_
+    {
+        self.variant_seeds.iter().map(|(pos, seed)| (*pos, *seed))
+    }
+
+    pub fn tint(&self, pos: Point2<i32>) -> [u8; 3]
+    {
+        self.tints.get(&pos).copied().unwrap_or(NO_TINT)
+    }
+
+    pub fn set_tint(&mut self, pos: Point2<i32>, tint: [u8; 3])
+    {
+        if self.tint(pos) == tint
+        {
+            return;
+        }
+
+        self.revision = next_revision();
+
+        if tint == NO_TINT
+        {
+            self.tints.remove(&pos);
+        } else
+        {
+            self.tints.insert(pos, tint);
+        }
+    }
+
+    pub fn tints(&self) -> impl Iterator<Item=(Point2<i32>, [u8; 3])> + '_
+    {
+        self.tints.iter().map(|(pos, tint)| (*pos, *tint))
+    }
+
+    pub fn link(&self, pos: Point2<i32>) -> Option<SceneLink>
+    {
+        self.links.get(&pos).copied()
+    }
+
+    pub fn set_link(&mut self, pos: Point2<i32>, link: Option<SceneLink>)
+    {
+        self.revision = next_revision();
+
+        if let Some(link) = link
+        {
+            self.links.insert(pos, link);
+        } else
+        {
+            self.links.remove(&pos);
+        }
+    }
+
+    pub fn links(&self) -> impl Iterator<Item=(Point2<i32>, SceneLink)> + '_
+    {
+        self.links.iter().map(|(pos, link)| (*pos, *link))
+    }
+
+    // for when scenes get moved around, links to a scene thats gone get removed
+    pub fn retarget_links(&mut self, remap: impl Fn(usize) -> Option<usize>)
+    {
+        self.revision = next_revision();
+
+        self.links.retain(|_, link|
+        {
+            remap(link.scene).map(|scene| link.scene = scene).is_some()
+        });
+    }
+
+    // every position with the same tile thats reachable without going diagonally,
+    // never goes outside of the scene so empty space doesnt go on forever
+    pub fn connected(&self, pos: Point2<i32>) -> Vec<Point2<i32>>
+    {
+        let target = if let Some(x) = self.get(pos)
+        {
+            *x
+        } else
+        {
+            return Vec::new();
+        };
+
+        let mut visited = HashSet::from([pos]);
+        let mut pending = vec![pos];
+
+        while let Some(pos) = pending.pop()
+        {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)].into_iter().for_each(|(x, y)|
+            {
+                let next = pos + Point2::new(x, y);
+
+                if self.get(next) == Some(&target) && visited.insert(next)
+                {
+                    pending.push(next);
+                }
+            });
+        }
+
+        visited.into_iter().collect()
+    }
+
+    pub fn fill(&mut self, pos: Point2<i32>, tile: Tile)
+    {
+        self.connected(pos).into_iter().for_each(|pos| self[pos] = tile);
+    }
+
+    pub fn fill_tinted(&mut self, pos: Point2<i32>, tile: Tile, tint: [u8; 3])
+    {
+        self.connected(pos).into_iter().for_each(|pos|
+        {
+            self[pos] = tile;
+            self.set_tint(pos, tint);
+        });
+    }
+
+    pub fn get(&self, pos: Point2<i32>) -> Option<&Tile>
+    {
+        let local = pos + self.offset;
+        let size = self.size;
+
+        let inside = local.x >= 0 && local.y >= 0
+            && (local.x as usize) < size.x && (local.y as usize) < size.y;
+
+        inside.then(|| self.stored(pos).unwrap_or(EMPTY))
+    }
+
+    // every cell in the area of the scene, empty ones too
+    pub fn iter(&self) -> impl Iterator<Item=(Point2<i32>, &Tile)>
+    {
+        let size = self.size.map(|x| x as i32);
+
+        self.iter_area(-self.offset, size - self.offset - 1)
+    }
+
+    // only the non empty tiles in no particular order, way faster than iter for sparse scenes
+    pub fn filled(&self) -> impl Iterator<Item=(Point2<i32>, &Tile)>
+    {
+        self.chunks.iter().flat_map(move |(chunk_pos, chunk)|
+        {
+            chunk.iter().filter(|(_, tile)| !tile.is_none()).map(move |(pos, tile)|
+            {
+                (Self::chunk_to_global(*chunk_pos, pos) - self.shift, tile)
+            })
+        })
+    }
+
+    // only the cells between the inclusive corners, without going through the rest
+    pub fn iter_area(
+        &self,
+        min: Point2<i32>,
+        max: Point2<i32>
+    ) -> impl Iterator<Item=(Point2<i32>, &Tile)>
+    {
+        let size = self.size.map(|x| x as i32);
+
+        let local_min = (min + self.offset).map(|x| x.max(0));
+        let local_max = (max + self.offset).zip(size).map(|(x, size)| x.min(size - 1));
+
+        (local_min.y..=local_max.y).flat_map(move |y|
+        {
+            (local_min.x..=local_max.x).map(move |x|
+            {
+                let pos = Point2::new(x, y) - self.offset;
+
+                (pos, self.stored(pos).unwrap_or(EMPTY))
+            })
+        })
+    }
+
+    fn stored(&self, pos: Point2<i32>) -> Option<&Tile>
+    {
+        let (chunk_pos, inner) = Self::to_chunk(pos + self.shift);
+
+        self.chunks.get(&chunk_pos).map(|chunk| &chunk[inner])
+    }
+
+    fn to_chunk(pos: Point2<i32>) -> (Point2<i32>, Point2<usize>)
+    {
+        (
+            pos.map(|x| x.div_euclid(CHUNK_SIZE)),
+            pos.map(|x| x.rem_euclid(CHUNK_SIZE) as usize)
+        )
+    }
+
+    fn chunk_to_global(chunk_pos: Point2<i32>, pos: Point2<usize>) -> Point2<i32>
+    {
+        chunk_pos * CHUNK_SIZE + pos.map(|x| x as i32)
+    }
+}
+
+fn moved_cells<T: Clone>(
+    cells: &HashMap<Point2<i32>, T>,
+    moved: impl Fn(Point2<i32>) -> Option<Point2<i32>>
+) -> HashMap<Point2<i32>, T>
+{
+    cells.iter().filter_map(|(pos, value)| Some((moved(*pos)?, value.clone()))).collect()
+}
+
+impl Index<Point2<i32>> for Scene
+{
+    type Output = Tile;
+
+    fn index(&self, index: Point2<i32>) -> &Self::Output
+    {
+        self.get(index).expect("position outside of the scene")
+    }
+}
+
+impl IndexMut<Point2<i32>> for Scene
+{
+    fn index_mut(&mut self, index: Point2<i32>) -> &mut Self::Output
+    {
+        self.extend_to_contain(index);
+
+        self.revision = next_revision();
+
+        let (chunk_pos, inner) = Self::to_chunk(index + self.shift);
+
+        let chunk = self.chunks.entry(chunk_pos).or_insert_with(||
+        {
+            Container2d::new(Point2::repeat(CHUNK_SIZE as usize))
+        });
+
+        &mut chunk[inner]
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn tints_follow_mirror()
+    {
+        let mut scene = Scene::new(Point2::new(4, 3), Point2::new(0, 0));
+
+        let pos = Point2::new(0, 1);
+        scene[pos] = Tile::new(5);
+        scene.set_tint(pos, [255, 0, 0]);
+
+        let _ = scene.mirror_x();
+
+        let mirrored = Point2::new(3, 1);
+        assert_eq!(scene[mirrored], Tile::new(5));
+        assert_eq!(scene.tint(mirrored), [255, 0, 0]);
+        assert_eq!(scene.tint(pos), NO_TINT);
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf}
+};
+
+use serde::Deserialize;
+
+use crate::Point2;
+
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Spritesheet
+{
+    pub image: PathBuf,
+    // the projects tile size if its left out
+    #[serde(default)]
+    pub tile_size: Option<Point2<usize>>,
+    #[serde(default)]
+    pub margin: usize,
+    #[serde(default)]
+    pub spacing: usize
+}
+
+impl Spritesheet
+{
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let path = path.as_ref();
+
+        let text = fs::read_to_string(path)?;
+        let mut this: Self = toml::from_str(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        // the image path is relative to the manifest
+        if let Some(parent) = path.parent()
+        {
+            this.image = parent.join(&this.image);
+        }
+
+        Ok(this)
+    }
+
+    // positions of every tile in the image in row order
+    pub fn tiles(
+        &self,
+        image_size: Point2<usize>,
+        tile_size: Point2<usize>
+    ) -> impl Iterator<Item=Point2<usize>> + '_
+    {
+        let amount = image_size.map(|size|
+        {
+            size.saturating_sub(self.margin * 2) + self.spacing
+        }).zip(tile_size).map(|(size, tile_size)| size / (tile_size + self.spacing).max(1));
+
+        (0..amount.y).flat_map(move |y|
+        {
+            (0..amount.x).map(move |x|
+            {
+                let pos = Point2::new(x, y);
+
+                pos * (tile_size + self.spacing) + self.margin
+            })
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    fmt,
+    thread,
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, TryRecvError},
+        atomic::{AtomicU32, Ordering}
+    }
+};
+
+
+// how far along the work is from 0 to 1, the work sets it and whoever waits reads it
+#[derive(Debug, Clone, Default)]
+pub struct Progress(Arc<AtomicU32>);
+
+impl Progress
+{
+    pub fn set(&self, fraction: f32)
+    {
+        self.0.store(fraction.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f32
+    {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+// the work panicked so theres no result, the panic message already got printed
+#[derive(Debug, Clone)]
+pub struct TaskDied(pub String);
+
+impl fmt::Display for TaskDied
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "task {} died", self.0)
+    }
+}
+
+// runs on its own thread so whatever started it can keep going (like drawing frames)
+pub struct Task<T>
+{
+    name: String,
+    progress: Progress,
+    receiver: Receiver<T>
+}
+
+impl<T: Send + 'static> Task<T>
+{
+    pub fn spawn(name: impl Into<String>, f: impl FnOnce(&Progress) -> T + Send + 'static) -> Self
+    {
+        let progress = Progress::default();
+        let (sender, receiver) = mpsc::channel();
+
+        {
+            let progress = progress.clone();
+
+            thread::spawn(move ||
+            {
+                // nobody cares about the result if the receiver is gone
+                let _ = sender.send(f(&progress));
+            });
+        }
+
+        Self{name: name.into(), progress, receiver}
+    }
+
+    pub fn name(&self) -> &str
+    {
+        &self.name
+    }
+
+    pub fn progress(&self) -> f32
+    {
+        self.progress.get()
+    }
+
+    // the result once its done, an error if the work panicked
+    pub fn poll(&mut self) -> Option<Result<T, TaskDied>>
+    {
+        match self.receiver.try_recv()
+        {
+            Ok(x) => Some(Ok(x)),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(TaskDied(self.name.clone())))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn wait<T: Send + 'static>(mut task: Task<T>) -> Result<T, TaskDied>
+    {
+        loop
+        {
+            if let Some(result) = task.poll()
+            {
+                return result;
+            }
+
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn finished_task_gives_its_result()
+    {
+        assert_eq!(wait(Task::spawn("adding", |_| 1 + 2)).unwrap(), 3);
+    }
+
+    #[test]
+    fn panicking_task_gives_an_error()
+    {
+        let task = Task::spawn("panicking", |_| -> i32 { panic!("oh no") });
+
+        assert_eq!(wait(task).unwrap_err().to_string(), "task panicking died");
+    }
+}
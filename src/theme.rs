@@ -0,0 +1,70 @@
+use std::{
+    fs,
+    io,
+    path::Path,
+    collections::HashMap
+};
+
+use serde::Deserialize;
+
+use sdl2::pixels::Color;
+
+
+// everything is optional, whatever is missing stays the same as the default look
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme
+{
+    // ui texture names like "panel" to files in the ui directory, unlisted names use name.png
+    pub textures: HashMap<String, String>,
+    // tints all text, white keeps the colors the labels asked for
+    pub text: [u8; 3],
+    pub focus: [u8; 3],
+    // in pixels, between the edges of bars and the text in them
+    pub margin: f32,
+    // an atlas in the ui directory laid out like the built in one
+    pub font: Option<String>
+}
+
+impl Default for Theme
+{
+    fn default() -> Self
+    {
+        Self{
+            textures: HashMap::new(),
+            text: [255, 255, 255],
+            focus: [255, 255, 0],
+            margin: 6.0,
+            font: None
+        }
+    }
+}
+
+impl Theme
+{
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let text = fs::read_to_string(path)?;
+
+        toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn texture_file(&self, name: &str) -> String
+    {
+        self.textures.get(name).cloned().unwrap_or_else(|| format!("{name}.png"))
+    }
+
+    pub fn text_color(&self) -> Color
+    {
+        let [r, g, b] = self.text;
+
+        Color::RGB(r, g, b)
+    }
+
+    pub fn focus_color(&self) -> Color
+    {
+        let [r, g, b] = self.focus;
+
+        Color::RGB(r, g, b)
+    }
+}
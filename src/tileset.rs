@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    io,
+    time::Duration,
+    collections::HashMap,
+    path::{Path, PathBuf}
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Point2, format::TileSource};
+
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FrameDefinition
+{
+    pub image: PathBuf,
+    pub duration_ms: u64
+}
+
+impl FrameDefinition
+{
+    pub fn duration(&self) -> Duration
+    {
+        Duration::from_millis(self.duration_ms)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TileDefinition
+{
+    pub name: String,
+    // shown instead of the name, the name is what maps and prefabs refer to it by
+    pub display_name: Option<String>,
+    // same numbering as in map files, tiles without one get the first free id in order
+    pub id: Option<usize>,
+    pub image: Option<PathBuf>,
+    #[serde(default)]
+    pub frames: Vec<FrameDefinition>,
+    // which tab of the tile picker its in
+    pub category: Option<String>,
+    // other images for the same tile, which one gets drawn depends on the position
+    #[serde(default)]
+    pub variants: Vec<PathBuf>,
+    #[serde(default)]
+    pub properties: toml::Table
+}
+
+impl TileDefinition
+{
+    // a tile with a single image is just a 1 frame animation
+    pub fn frames(&self) -> Vec<FrameDefinition>
+    {
+        if let Some(image) = self.image.as_ref()
+        {
+            vec![FrameDefinition{image: image.clone(), duration_ms: 0}]
+        } else
+        {
+            self.frames.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tileset
+{
+    // every tile gets these properties unless it sets them itself
+    #[serde(default)]
+    pub default_properties: toml::Table,
+    pub tiles: Vec<TileDefinition>
+}
+
+impl Tileset
+{
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self>
+    {
+        let path = path.as_ref();
+
+        let text = fs::read_to_string(path)?;
+        let mut this: Self = toml::from_str(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        for tile in this.tiles.iter()
+        {
+            if tile.image.is_none() && tile.frames.is_empty()
+            {
+                let message = format!("tile {} has no image or frames", tile.name);
+
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+
+            if tile.id == Some(0)
+            {
+                let message = format!("tile {} cant have id 0, its reserved for empty", tile.name);
+
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+        }
+
+        // images r relative to the tileset file
+        if let Some(parent) = path.parent()
+        {
+            this.tiles.iter_mut().for_each(|tile|
+            {
+                if let Some(image) = tile.image.as_mut()
+                {
+                    *image = parent.join(&image);
+                }
+
+                tile.frames.iter_mut().for_each(|frame| frame.image = parent.join(&frame.image));
+                tile.variants.iter_mut().for_each(|image| *image = parent.join(&image));
+            });
+        }
+
+        Ok(this)
+    }
+}
+
+// what happens to tiles that arent the project tile size (or a multiple of it)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TileFit
+{
+    // drawn squished into the tile like everything else
+    #[default]
+    Stretch,
+    // centered on a see through tile, bigger ones get cut off
+    Pad,
+    // as big as fits without squishing, the rest gets padded
+    Scale
+}
+
+impl TileFit
+{
+    pub fn is_stretch(&self) -> bool
+    {
+        *self == Self::Stretch
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SizeMismatch
+{
+    pub path: PathBuf,
+    pub size: Point2<usize>
+}
+
+// the size most of the tiles have
+pub fn common_size<'a>(sources: impl IntoIterator<Item=&'a TileSource>) -> Option<Point2<usize>>
+{
+    let mut amounts: HashMap<Point2<usize>, usize> = HashMap::new();
+    sources.into_iter().for_each(|source| *amounts.entry(source.size).or_insert(0) += 1);
+
+    amounts.into_iter().max_by_key(|(size, amount)| (*amount, size.x * size.y))
+        .map(|(size, _)| size)
+}
+
+// the path from the tiles directory without the extension, like walls/stone, so tiles with
+// the same file name in different folders dont clash
+pub fn tile_name(root: &Path, path: &Path) -> String
+{
+    let relative = path.strip_prefix(root).unwrap_or(path);
+
+    let folders = relative.parent().into_iter().flat_map(|parent| parent.components())
+        .map(|part| part.as_os_str().to_string_lossy().into_owned());
+
+    let stem = relative.file_stem().map(|x| x.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    folders.chain(Some(stem)).collect::<Vec<_>>().join("/")
+}
+
+// oversized tiles r fine as long as they cover a whole number of tiles
+pub fn fits_size(tile_size: Point2<usize>, size: Point2<usize>) -> bool
+{
+    let fits = size.zip(tile_size).map(|(size, tile_size)|
+    {
+        size != 0 && tile_size != 0 && size % tile_size == 0
+    });
+
+    fits.x && fits.y
+}
+
+pub fn validate_sizes<'a>(
+    tile_size: Point2<usize>,
+    sources: impl IntoIterator<Item=&'a TileSource>
+) -> Vec<SizeMismatch>
+{
+    let mut mismatches: Vec<SizeMismatch> = sources.into_iter().filter(|source|
+    {
+        !fits_size(tile_size, source.size)
+    }).map(|source| SizeMismatch{path: source.path.clone(), size: source.size}).collect();
+
+    mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+    mismatches.dedup_by(|a, b| a.path == b.path);
+
+    mismatches
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn same_stem_different_folders()
+    {
+        let root = Path::new("assets/tiles");
+
+        let walls = tile_name(root, &root.join("walls").join("stone.png"));
+        let floors = tile_name(root, &root.join("floors").join("stone.png"));
+
+        assert_eq!(walls, "walls/stone");
+        assert_eq!(floors, "floors/stone");
+
+        assert_eq!(tile_name(root, &root.join("grass.png")), "grass");
+    }
+}
@@ -1,12 +1,25 @@
 use std::{
+    mem,
     rc::Rc,
     cell::RefCell,
     ops::ControlFlow
 };
 
-use sdl2::rect::Rect;
+use sdl2::{
+    rect::Rect,
+    pixels::Color
+};
 
-use crate::{Point2, GameWindow, Assets, TextureId, animator::Animatable};
+use crate::{
+    Point2,
+    Assets,
+    TextureId,
+    Renderer,
+    RenderTexture,
+    font,
+    assets::NineSlice,
+    animator::{Animatable, Animator, AnimationState}
+};
 
 
 // i could just store the children in a vec but this is much cooler
@@ -43,17 +56,88 @@ impl ElementId
             self.child = Some(Box::new(Self::new(child_id)));
         }
     }
+
+    // what this id turns into after removed is gone, none if it was removed along with it
+    fn after_removal(&self, removed: &Self) -> Option<Self>
+    {
+        if self.id != removed.id
+        {
+            let id = if removed.child.is_none() && self.id > removed.id
+            {
+                self.id - 1
+            } else
+            {
+                self.id
+            };
+
+            return Some(Self{id, child: self.child.clone()});
+        }
+
+        match (self.child.as_ref(), removed.child.as_ref())
+        {
+            (_, None) => None,
+            (None, Some(_)) => Some(self.clone()),
+            (Some(child), Some(removed_child)) =>
+            {
+                child.after_removal(removed_child).map(|child|
+                {
+                    Self{id: self.id, child: Some(Box::new(child))}
+                })
+            }
+        }
+    }
+
+    // true for children of other, but not for other itself
+    fn is_inside(&self, other: &Self) -> bool
+    {
+        if self.id != other.id
+        {
+            return false;
+        }
+
+        match (self.child.as_ref(), other.child.as_ref())
+        {
+            (Some(_), None) => true,
+            (Some(child), Some(other_child)) => child.is_inside(other_child),
+            (None, _) => false
+        }
+    }
 }
 
-pub struct UiEvent
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align
+{
+    Left,
+    Center,
+    Right
+}
+
+pub struct Label
 {
-    pub element_id: ElementId
+    pub text: String,
+    // height of a line in pixels, none makes the text as big as fits in the element
+    pub font_size: Option<f32>,
+    pub color: Color,
+    pub align: Align
 }
 
 pub enum UiElementType
 {
     Panel,
-    Button
+    // can be scrolled vertically when the children dont fit
+    ScrollPanel{scrollbar: TextureId},
+    // the fill covers the left part of it, progress goes from 0 to 1
+    ProgressBar{fill: TextureId, progress: f32},
+    Button,
+    // draws text instead of its texture
+    Label(Label),
+    // these 2 draw nothing and place their children whenever the ui gets resized,
+    // they keep their top edge and grow down to fit everything
+    // rows of square cells
+    Grid{columns: usize, spacing: Length},
+    // children keep their size and go to the next row when they dont fit
+    Flow{spacing: Length}
 }
 
 pub struct UiElement
@@ -61,7 +145,247 @@ pub struct UiElement
     pub kind: UiElementType,
     pub pos: Point2<f32>,
     pub size: Point2<f32>,
-    pub texture: TextureId
+    pub texture: TextureId,
+    // multiplies the texture, none leaves it as is
+    pub color: Option<Color>,
+    // children get faded along with their parent
+    pub alpha: u8
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor
+{
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side
+{
+    Start,
+    Middle,
+    End
+}
+
+impl Anchor
+{
+    // start is left or bottom
+    fn sides(&self) -> Point2<Side>
+    {
+        let (x, y) = match self
+        {
+            Self::TopLeft => (Side::Start, Side::End),
+            Self::Top => (Side::Middle, Side::End),
+            Self::TopRight => (Side::End, Side::End),
+            Self::Left => (Side::Start, Side::Middle),
+            Self::Center => (Side::Middle, Side::Middle),
+            Self::Right => (Side::End, Side::Middle),
+            Self::BottomLeft => (Side::Start, Side::Start),
+            Self::Bottom => (Side::Middle, Side::Start),
+            Self::BottomRight => (Side::End, Side::Start)
+        };
+
+        Point2::new(x, y)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length
+{
+    // part of the parent, 1.0 is all of it
+    Relative(f32),
+    // in pixels, stays the same size no matter how big the window is
+    Absolute(f32)
+}
+
+impl Length
+{
+    fn resolve(&self, parent_pixels: f32) -> f32
+    {
+        match self
+        {
+            Self::Relative(x) => *x,
+            Self::Absolute(x) => *x / parent_pixels
+        }
+    }
+}
+
+// margin pushes the element away from the sides its anchored to, in the middle it just offsets it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout
+{
+    pub anchor: Anchor,
+    pub margin: Point2<Length>,
+    pub size: Point2<Length>
+}
+
+impl Layout
+{
+    pub fn new(anchor: Anchor, size: Point2<Length>) -> Self
+    {
+        Self{anchor, margin: Point2::new(Length::Absolute(0.0), Length::Absolute(0.0)), size}
+    }
+
+    pub fn with_margin(mut self, margin: Point2<Length>) -> Self
+    {
+        self.margin = margin;
+
+        self
+    }
+
+    // position and size in the parent's units
+    fn resolve(&self, parent_pixels: Point2<f32>) -> (Point2<f32>, Point2<f32>)
+    {
+        let size = self.size.zip(parent_pixels).map(|(length, pixels)| length.resolve(pixels));
+        let margin = self.margin.zip(parent_pixels).map(|(length, pixels)| length.resolve(pixels));
+
+        let pos = self.anchor.sides().zip(size.zip(margin)).map(|(side, (size, margin))|
+        {
+            match side
+            {
+                Side::Start => margin,
+                Side::Middle => (1.0 - size) / 2.0 + margin,
+                Side::End => 1.0 - size - margin
+            }
+        });
+
+        (pos, size)
+    }
+}
+
+type Area = (Point2<f32>, Point2<f32>);
+
+// higher z goes on top, the same z keeps the order they were pushed in
+fn in_z_order(
+    elements: &[Rc<RefCell<UiElementInner>>]
+) -> Vec<(usize, &Rc<RefCell<UiElementInner>>)>
+{
+    let mut elements: Vec<_> = elements.iter().enumerate().collect();
+
+    elements.sort_by_key(|(_, element)| element.borrow().z);
+
+    elements
+}
+
+fn area_contains((area_pos, area_size): Area, pos: Point2<f32>) -> bool
+{
+    (area_pos.x..=(area_pos.x + area_size.x)).contains(&pos.x)
+        && (area_pos.y..=(area_pos.y + area_size.y)).contains(&pos.y)
+}
+
+fn area_overlap((a_pos, a_size): Area, (b_pos, b_size): Area) -> Area
+{
+    let start = a_pos.zip(b_pos).map(|(a, b)| a.max(b));
+    let end = (a_pos + a_size).zip(b_pos + b_size).map(|(a, b)| a.min(b));
+
+    (start, (end - start).map(|x| x.max(0.0)))
+}
+
+fn area_to_screen((pos, size): Area, window_size: Point2<f32>) -> Rect
+{
+    let mut pos = pos;
+    pos.y = 1.0 - pos.y - size.y;
+
+    let pos = (pos * window_size).map(|x| x.round() as i32);
+    let size = (size * window_size).map(|x| x.round() as u32);
+
+    Rect::new(pos.x, pos.y, size.x, size.y)
+}
+
+// lines r aligned horizontally by themselves and centered vertically as a whole
+pub fn draw_label(
+    renderer: &mut dyn Renderer,
+    assets: &Assets,
+    label: &Label,
+    alpha: u8,
+    pos: Point2<i32>,
+    size: Point2<u32>
+)
+{
+    let cell = (font::GLYPH_SIZE + font::GLYPH_SPACING).map(|x| x as f32);
+
+    let lines = label.text.lines().count();
+    let longest = label.text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+
+    // in font pixels
+    let text_size = Point2::new(
+        longest as f32 * cell.x - font::GLYPH_SPACING as f32,
+        lines as f32 * cell.y - font::GLYPH_SPACING as f32
+    );
+
+    let scale = if let Some(font_size) = label.font_size
+    {
+        font_size / font::GLYPH_SIZE.y as f32
+    } else
+    {
+        let fit = size.map(|x| x as f32) / text_size;
+
+        fit.x.min(fit.y)
+    };
+
+    let glyph_size = font::GLYPH_SIZE.map(|x| (x as f32 * scale).round() as u32);
+    let advance = (cell.x * scale).round() as i32;
+    let line_height = (cell.y * scale).round() as i32;
+
+    let text_height = (text_size.y * scale).round() as i32;
+
+    let top = pos.y + (size.y as i32 - text_height) / 2;
+
+    // the theme tints whatever color the label has
+    let tint = assets.theme().text_color();
+    let mix = |a: u8, b: u8| (a as u32 * b as u32 / 255) as u8;
+
+    let font_texture = assets.font();
+    let font_tint = Color::RGBA(
+        mix(label.color.r, tint.r),
+        mix(label.color.g, tint.g),
+        mix(label.color.b, tint.b),
+        mix(label.color.a, alpha)
+    );
+
+    label.text.lines().enumerate().for_each(|(line_index, line)|
+    {
+        let y = top + line_index as i32 * line_height;
+
+        let line_width = (line.chars().count() as i32 * advance - advance + glyph_size.x as i32)
+            .max(0);
+
+        let left = match label.align
+        {
+            Align::Left => pos.x,
+            Align::Center => pos.x + (size.x as i32 - line_width) / 2,
+            Align::Right => pos.x + size.x as i32 - line_width
+        };
+
+        line.chars().enumerate().for_each(|(index, c)|
+        {
+            let source_x = font::glyph_index(c) * (font::GLYPH_SIZE.x + font::GLYPH_SPACING);
+            let source = Rect::new(
+                source_x as i32,
+                0,
+                font::GLYPH_SIZE.x as u32,
+                font::GLYPH_SIZE.y as u32
+            );
+
+            let x = left + index as i32 * advance;
+
+            renderer.copy_tinted(
+                font_texture,
+                Some(source),
+                Rect::new(x, y, glyph_size.x, glyph_size.y),
+                font_tint
+            );
+        });
+    });
 }
 
 struct UiElementGlobal
@@ -69,14 +393,66 @@ struct UiElementGlobal
     inner: UiElement,
     global_size: Point2<f32>,
     global_pos: Point2<f32>,
+    // children get moved up by this much, in this element's units
+    scroll: f32,
+    max_scroll: f32,
+    // the part of the screen this can be seen in, parents cut off their children
+    clip: Option<Area>,
+    // false if this or any parent is disabled
+    enabled: bool,
+    // with the parents alpha applied
+    alpha: u8
 }
 
 impl UiElementGlobal
 {
     pub fn intersects(&self, pos: Point2<f32>) -> bool
     {
-        (self.global_pos.x..=(self.global_pos.x + self.global_size.x)).contains(&pos.x)
-            && (self.global_pos.y..=(self.global_pos.y + self.global_size.y)).contains(&pos.y)
+        area_contains((self.global_pos, self.global_size), pos)
+            && self.clip.map(|clip| area_contains(clip, pos)).unwrap_or(true)
+    }
+
+    // the whole bar and the part that moves
+    fn scrollbar(&self) -> Option<(Area, Area)>
+    {
+        if !matches!(self.inner.kind, UiElementType::ScrollPanel{..}) || self.max_scroll <= 0.0
+        {
+            return None;
+        }
+
+        let width = self.global_size.x * 0.02;
+
+        let track_pos = Point2::new(
+            self.global_pos.x + self.global_size.x - width * 1.75,
+            self.global_pos.y
+        );
+        let track_size = Point2::new(width, self.global_size.y);
+
+        let thumb_height = track_size.y / (1.0 + self.max_scroll);
+        let thumb_travel = track_size.y - thumb_height;
+
+        let thumb_pos = Point2::new(
+            track_pos.x,
+            track_pos.y + thumb_travel * (1.0 - self.scroll / self.max_scroll)
+        );
+
+        Some(((track_pos, track_size), (thumb_pos, Point2::new(width, thumb_height))))
+    }
+
+    // scroll amount that puts the middle of the thumb at this height
+    fn scroll_at(&self, pos: Point2<f32>) -> Option<f32>
+    {
+        let ((track_pos, track_size), (_, thumb_size)) = self.scrollbar()?;
+
+        let thumb_travel = track_size.y - thumb_size.y;
+        if thumb_travel <= 0.0
+        {
+            return None;
+        }
+
+        let fraction = (pos.y - track_pos.y - thumb_size.y * 0.5) / thumb_travel;
+
+        Some((1.0 - fraction) * self.max_scroll)
     }
 }
 
@@ -87,13 +463,23 @@ pub enum UiAnimatableId
     ScaleX,
     ScaleY,
     PositionX,
-    PositionY
+    PositionY,
+    Alpha
 }
 
 pub struct UiElementInner
 {
     parent: Option<(usize, Rc<RefCell<Self>>)>,
     element: UiElementGlobal,
+    layout: Option<Layout>,
+    // only matters between siblings, children always go on top of their parent
+    z: i32,
+    // hidden elements r skipped along with their children
+    visible: bool,
+    // disabled elements still get drawn but cant be clicked
+    enabled: bool,
+    // children outside of this dont get drawn or clicked there
+    clip_children: bool,
     children: Vec<Rc<RefCell<Self>>>
 }
 
@@ -104,6 +490,78 @@ impl UiElementInner
         &mut self.element.inner.texture
     }
 
+    pub fn color(&mut self) -> &mut Option<Color>
+    {
+        &mut self.element.inner.color
+    }
+
+    // where it is on the screen and how big
+    pub fn area(&self) -> (Point2<f32>, Point2<f32>)
+    {
+        (self.element.global_pos, self.element.global_size)
+    }
+
+    pub fn label(&mut self) -> Option<&mut Label>
+    {
+        if let UiElementType::Label(label) = &mut self.element.inner.kind
+        {
+            Some(label)
+        } else
+        {
+            None
+        }
+    }
+
+    pub fn progress(&mut self) -> Option<&mut f32>
+    {
+        if let UiElementType::ProgressBar{progress, ..} = &mut self.element.inner.kind
+        {
+            Some(progress)
+        } else
+        {
+            None
+        }
+    }
+
+    // how far the children r moved up, in this element's units
+    pub fn scroll(&self) -> f32
+    {
+        self.element.scroll
+    }
+
+    pub fn scroll_by(&mut self, amount: f32)
+    {
+        self.set_scroll(self.element.scroll + amount);
+    }
+
+    pub fn set_scroll(&mut self, scroll: f32)
+    {
+        self.element.scroll = scroll.clamp(0.0, self.element.max_scroll);
+
+        self.update_children();
+    }
+
+    // the lowest child ends up as far from the bottom as the highest one is from the top
+    fn update_max_scroll(&mut self)
+    {
+        let (top, bottom) = self.children.iter().fold((f32::MIN, f32::MAX), |(top, bottom), child|
+        {
+            let child = &child.borrow().element.inner;
+
+            (top.max(child.pos.y + child.size.y), bottom.min(child.pos.y))
+        });
+
+        self.element.max_scroll = if self.children.is_empty()
+        {
+            0.0
+        } else
+        {
+            (1.0 - top - bottom).max(0.0)
+        };
+
+        self.element.scroll = self.element.scroll.min(self.element.max_scroll);
+    }
+
     fn new_parent(element: UiElement) -> Rc<RefCell<Self>>
     {
         Self::new_inner(None, element)
@@ -125,8 +583,18 @@ impl UiElementInner
                 
                 global_size: element.size,
                 global_pos: element.pos,
+                scroll: 0.0,
+                max_scroll: 0.0,
+                clip: None,
+                enabled: true,
+                alpha: element.alpha,
                 inner: element
             },
+            layout: None,
+            z: 0,
+            visible: true,
+            enabled: true,
+            clip_children: false,
             children: Vec::new()
         }))
     }
@@ -141,23 +609,90 @@ impl UiElementInner
 
         this.children.push(Self::new_child(parent, id, element));
 
+        this.update_max_scroll();
         this.update_child(id);
 
         id
     }
 
+    fn remove_child(&mut self, id: usize)
+    {
+        self.children.remove(id);
+
+        // everything after it moved down by 1
+        self.children.iter().skip(id).for_each(|child|
+        {
+            if let Some((index, _)) = child.borrow_mut().parent.as_mut()
+            {
+                *index -= 1;
+            }
+        });
+
+        self.update_max_scroll();
+        self.update_children();
+    }
+
+    fn replace_child(this: &Rc<RefCell<Self>>, id: usize, element: UiElement)
+    {
+        let mut this_ref = this.borrow_mut();
+
+        let z = this_ref.children[id].borrow().z;
+
+        let child = Self::new_child(this.clone(), id, element);
+        child.borrow_mut().z = z;
+
+        this_ref.children[id] = child;
+
+        this_ref.update_max_scroll();
+        this_ref.update_child(id);
+    }
+
+    // scrolls just enough for the area (on the screen) to be fully in view
+    fn scroll_to(&mut self, (global_pos, global_size): Area)
+    {
+        let scroll = self.element.scroll;
+
+        let pos = (global_pos.y - self.element.global_pos.y) / self.element.global_size.y - scroll;
+        let size = global_size.y / self.element.global_size.y;
+
+        if pos + scroll < 0.0
+        {
+            self.set_scroll(-pos);
+        } else if pos + size + scroll > 1.0
+        {
+            self.set_scroll(1.0 - pos - size);
+        }
+    }
+
     fn update_child(&mut self, id: usize)
     {
         let mut child = self.children[id].borrow_mut();
+        let clip_children = self.clip_children;
         let this = &mut self.element;
         
         {
             let child = &mut child.element;
 
-            child.global_pos = this.global_pos + child.inner.pos * this.global_size;
+            let scroll = Point2::new(0.0, this.scroll);
+
+            child.global_pos = this.global_pos + (child.inner.pos + scroll) * this.global_size;
             child.global_size = child.inner.size * this.global_size;
+
+            // whatever clips this clips its children too
+            child.clip = if clip_children
+            {
+                let area = (this.global_pos, this.global_size);
+
+                Some(this.clip.map(|clip| area_overlap(area, clip)).unwrap_or(area))
+            } else
+            {
+                this.clip
+            };
         }
 
+        child.element.enabled = this.enabled && child.enabled;
+        child.element.alpha = (this.alpha as u32 * child.element.inner.alpha as u32 / 255) as u8;
+
         child.update_children();
     }
 
@@ -178,6 +713,8 @@ impl UiElementInner
         {
             self.element.global_pos = self.element.inner.pos;
             self.element.global_size = self.element.inner.size;
+            self.element.enabled = self.enabled;
+            self.element.alpha = self.element.inner.alpha;
 
             self.update_children();
         }
@@ -196,17 +733,119 @@ impl UiElementInner
         }
     }
 
+    // doesnt update the global positions, the caller does that once its all done
+    fn resolve_layouts(&mut self, parent_pixels: Point2<f32>)
+    {
+        if let Some(layout) = self.layout
+        {
+            let (pos, size) = layout.resolve(parent_pixels);
+
+            self.element.inner.pos = pos;
+            self.element.inner.size = size;
+        }
+
+        let pixels = self.element.inner.size * parent_pixels;
+        self.children.iter().for_each(|child| child.borrow_mut().resolve_layouts(pixels));
+
+        // children that r containers might have grown
+        self.arrange(parent_pixels);
+        self.update_max_scroll();
+    }
+
+    fn arrange(&mut self, parent_pixels: Point2<f32>)
+    {
+        let pixels = self.element.inner.size * parent_pixels;
+
+        if pixels.x <= 0.0
+        {
+            return;
+        }
+
+        // positions from the top left and sizes, in pixels
+        let placed: Vec<(Point2<f32>, Point2<f32>)> = match self.element.inner.kind
+        {
+            UiElementType::Grid{columns, spacing} =>
+            {
+                let columns = columns.max(1);
+
+                let spacing = spacing.resolve(pixels.x) * pixels.x;
+                let cell = (pixels.x - spacing * (columns - 1) as f32) / columns as f32;
+
+                (0..self.children.len()).map(|index|
+                {
+                    let pos = Point2::new(index % columns, index / columns)
+                        .map(|x| x as f32 * (cell + spacing));
+
+                    (pos, Point2::repeat(cell))
+                }).collect()
+            },
+            UiElementType::Flow{spacing} =>
+            {
+                let spacing = spacing.resolve(pixels.x) * pixels.x;
+
+                let mut cursor = Point2::new(0.0, 0.0);
+                let mut row_height: f32 = 0.0;
+
+                self.children.iter().map(|child|
+                {
+                    let size = child.borrow().element.inner.size * pixels;
+
+                    if cursor.x > 0.0 && cursor.x + size.x > pixels.x
+                    {
+                        cursor = Point2::new(0.0, cursor.y + row_height + spacing);
+                        row_height = 0.0;
+                    }
+
+                    let pos = cursor;
+
+                    cursor.x += size.x + spacing;
+                    row_height = row_height.max(size.y);
+
+                    (pos, size)
+                }).collect()
+            },
+            _ => return
+        };
+
+        let height = placed.iter().map(|(pos, size)| pos.y + size.y).fold(0.0, f32::max);
+
+        let inner = &mut self.element.inner;
+
+        let top = inner.pos.y + inner.size.y;
+        inner.size.y = height / parent_pixels.y;
+        inner.pos.y = top - inner.size.y;
+
+        if height <= 0.0
+        {
+            return;
+        }
+
+        // back to this element's units with y going up
+        self.children.iter().zip(placed).for_each(|(child, (pos, size))|
+        {
+            let inner = &mut child.borrow_mut().element.inner;
+
+            inner.pos = Point2::new(pos.x / pixels.x, 1.0 - (pos.y + size.y) / height);
+            inner.size = Point2::new(size.x / pixels.x, size.y / height);
+        });
+    }
+
     fn try_for_each_element<T, F>(&self, id: ElementId, f: &mut F) -> ControlFlow<T>
     where
         F: FnMut(&ElementId, &UiElementGlobal) -> ControlFlow<T>
     {
+        if !self.visible
+        {
+            return ControlFlow::Continue(());
+        }
+
         match f(&id, &self.element)
         {
             ControlFlow::Continue(_) => (),
             x => return x
         }
 
-        self.children.iter().enumerate().try_for_each(|(index, child)|
+        in_z_order(&self.children).into_iter().try_for_each(|(index, child)|
         {
             let id = id.push(index);
 
@@ -236,6 +875,10 @@ impl Animatable<UiAnimatableId> for UiElementInner
             UiAnimatableId::PositionY =>
             {
                 self.element.inner.pos.y = value;
+            },
+            UiAnimatableId::Alpha =>
+            {
+                self.element.inner.alpha = value.round().clamp(0.0, 255.0) as u8;
             }
         }
 
@@ -243,18 +886,137 @@ impl Animatable<UiAnimatableId> for UiElementInner
     }
 }
 
-pub struct Ui
-{
-    window: Rc<RefCell<GameWindow>>,
-    assets: Rc<RefCell<Assets>>,
-    elements: Vec<Rc<RefCell<UiElementInner>>>
-}
+// the unselected buttons r drawn darker
+const RADIO_UNSELECTED: Color = Color::RGB(130, 130, 130);
 
-impl Ui
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadioGroupId(usize);
+
+// makes a message out of the new scroll amount
+type ScrollMessage<M> = fn(f32) -> M;
+
+// exactly 1 of the buttons is selected at a time
+struct RadioGroup
+{
+    buttons: Vec<ElementId>,
+    selected: usize
+}
+
+// clicking a button gives back whatever message it got with on_click
+pub struct Ui<M=()>
 {
-    pub fn new(window: Rc<RefCell<GameWindow>>, assets: Rc<RefCell<Assets>>) -> Self
+    renderer: Rc<RefCell<dyn Renderer>>,
+    assets: Rc<RefCell<Assets>>,
+    elements: Vec<Rc<RefCell<UiElementInner>>>,
+    messages: Vec<(ElementId, M)>,
+    mouse: Option<Point2<f32>>,
+    mouse_held: bool,
+    dragged_scrollbar: Option<ElementId>,
+    // where the keyboard is, enter clicks it
+    focused: Option<ElementId>,
+    radio_groups: Vec<RadioGroup>,
+    scroll_messages: Vec<(ElementId, ScrollMessage<M>)>,
+    // scrolling happens outside of clicks so these wait until someone takes them
+    pending: Vec<M>,
+    // ticked every draw until theyre over
+    animations: Vec<(ElementId, Animator<UiAnimatableId>)>
+}
+
+impl<M: Clone> Ui<M>
+{
+    pub fn new(renderer: Rc<RefCell<dyn Renderer>>, assets: Rc<RefCell<Assets>>) -> Self
+    {
+        Self{
+            renderer,
+            assets,
+            elements: Vec::new(),
+            messages: Vec::new(),
+            mouse: None,
+            mouse_held: false,
+            dragged_scrollbar: None,
+            focused: None,
+            radio_groups: Vec::new(),
+            scroll_messages: Vec::new(),
+            pending: Vec::new(),
+            animations: Vec::new()
+        }
+    }
+
+    // buttons under the mouse get darker, even more so while its held
+    pub fn set_mouse(&mut self, pos: Point2<f32>, held: bool)
+    {
+        self.mouse = Some(pos);
+        self.mouse_held = held;
+
+        if !held
+        {
+            self.dragged_scrollbar = None;
+        }
+
+        if let Some(id) = self.dragged_scrollbar.clone()
+        {
+            let element = self.get(&id);
+            let scroll = element.borrow().element.scroll_at(pos);
+
+            if let Some(scroll) = scroll
+            {
+                element.borrow_mut().set_scroll(scroll);
+
+                self.scrolled(&id);
+            }
+        }
+    }
+
+    // scrolls the innermost scrollable panel under the mouse, returns if anything scrolled
+    pub fn scroll(&mut self, pos: Point2<f32>, amount: f32) -> bool
+    {
+        let mut found = None;
+        self.for_each_element(|id, element|
+        {
+            if element.max_scroll > 0.0 && element.intersects(pos)
+            {
+                found = Some(id.clone());
+            }
+        });
+
+        if let Some(id) = found.filter(|id| self.reachable(id, pos))
+        {
+            self.get(&id).borrow_mut().scroll_by(amount);
+
+            self.scrolled(&id);
+
+            true
+        } else
+        {
+            false
+        }
+    }
+
+    // starts dragging a scrollbar if its under the mouse, returns if it was
+    pub fn press(&mut self, pos: Point2<f32>) -> bool
     {
-        Self{window, assets, elements: Vec::new()}
+        let mut found = None;
+        self.for_each_element(|id, element|
+        {
+            if let Some((track, _)) = element.scrollbar()
+            {
+                if area_contains(track, pos)
+                {
+                    found = Some(id.clone());
+                }
+            }
+        });
+
+        if let Some(id) = found.filter(|id| self.reachable(id, pos))
+        {
+            self.dragged_scrollbar = Some(id);
+            self.set_mouse(pos, true);
+
+            true
+        } else
+        {
+            false
+        }
     }
 
     pub fn push(&mut self, element: UiElement) -> ElementId
@@ -273,6 +1035,369 @@ impl Ui
         parent_id.push(id)
     }
 
+    // pos and size of the element get ignored, theyre figured out from the layout
+    pub fn push_anchored(&mut self, element: UiElement, layout: Layout) -> ElementId
+    {
+        let id = self.push(element);
+
+        self.set_layout(&id, layout);
+
+        id
+    }
+
+    pub fn push_child_anchored(
+        &mut self,
+        parent_id: &ElementId,
+        element: UiElement,
+        layout: Layout
+    ) -> ElementId
+    {
+        let id = self.push_child(parent_id, element);
+
+        self.set_layout(&id, layout);
+
+        id
+    }
+
+    pub fn set_layout(&mut self, id: &ElementId, layout: Layout)
+    {
+        self.get(id).borrow_mut().layout = Some(layout);
+
+        self.resize();
+    }
+
+    // ids of the siblings pushed after it (and their children) go down by 1
+    pub fn remove(&mut self, id: &ElementId)
+    {
+        let parent = self.get(id).borrow().parent.clone();
+
+        if let Some((index, parent)) = parent
+        {
+            parent.borrow_mut().remove_child(index);
+        } else
+        {
+            self.elements.remove(id.id);
+        }
+
+        self.messages = mem::take(&mut self.messages).into_iter().filter_map(|(element, message)|
+        {
+            element.after_removal(id).map(|element| (element, message))
+        }).collect();
+
+        self.scroll_messages = mem::take(&mut self.scroll_messages).into_iter()
+            .filter_map(|(element, message)|
+            {
+                element.after_removal(id).map(|element| (element, message))
+            }).collect();
+
+        self.animations = mem::take(&mut self.animations).into_iter()
+            .filter_map(|(element, animator)|
+            {
+                element.after_removal(id).map(|element| (element, animator))
+            }).collect();
+
+        self.focused = self.focused.take().and_then(|focused| focused.after_removal(id));
+
+        self.radio_groups.iter_mut().for_each(|group|
+        {
+            group.buttons = mem::take(&mut group.buttons).into_iter()
+                .filter_map(|button| button.after_removal(id))
+                .collect();
+
+            group.selected = group.selected.min(group.buttons.len().saturating_sub(1));
+        });
+
+        self.dragged_scrollbar = None;
+    }
+
+    // the id stays the same but the old children are gone
+    #[allow(dead_code)]
+    pub fn replace(&mut self, id: &ElementId, element: UiElement)
+    {
+        let parent = self.get(id).borrow().parent.clone();
+
+        if let Some((index, parent)) = parent
+        {
+            UiElementInner::replace_child(&parent, index, element);
+        } else
+        {
+            let z = self.elements[id.id].borrow().z;
+
+            let replaced = UiElementInner::new_parent(element);
+            replaced.borrow_mut().z = z;
+
+            self.elements[id.id] = replaced;
+        }
+
+        self.messages.retain(|(element, _)| !element.is_inside(id));
+        self.scroll_messages.retain(|(element, _)| !element.is_inside(id));
+        self.animations.retain(|(element, _)| element != id && !element.is_inside(id));
+
+        if self.focused.as_ref().map(|focused| focused.is_inside(id)).unwrap_or(false)
+        {
+            self.focused = None;
+        }
+
+        if self.dragged_scrollbar.as_ref() == Some(id)
+        {
+            self.dragged_scrollbar = None;
+        }
+    }
+
+    // clicking a button gives back the message for its index, selecting it is up to the caller
+    pub fn push_radio_group(
+        &mut self,
+        buttons: Vec<ElementId>,
+        selected: usize,
+        message: impl Fn(usize) -> M
+    ) -> RadioGroupId
+    {
+        buttons.iter().enumerate().for_each(|(index, button)|
+        {
+            self.on_click(button, message(index));
+        });
+
+        let id = RadioGroupId(self.radio_groups.len());
+
+        self.radio_groups.push(RadioGroup{buttons, selected: 0});
+        self.select(id, selected);
+
+        id
+    }
+
+    // unselects the old one, returns if the selection changed
+    pub fn select(&mut self, group: RadioGroupId, index: usize) -> bool
+    {
+        let buttons = self.radio_groups[group.0].buttons.clone();
+
+        if index >= buttons.len()
+        {
+            return false;
+        }
+
+        buttons.iter().enumerate().for_each(|(button_index, button)|
+        {
+            let color = (button_index != index).then_some(RADIO_UNSELECTED);
+
+            self.get(button).borrow_mut().element.inner.color = color;
+        });
+
+        let group = &mut self.radio_groups[group.0];
+        let changed = group.selected != index;
+
+        group.selected = index;
+
+        changed
+    }
+
+    #[allow(dead_code)]
+    pub fn selected(&self, group: RadioGroupId) -> usize
+    {
+        self.radio_groups[group.0].selected
+    }
+
+    // the message gets the new scroll amount whenever the mouse scrolls the panel
+    pub fn on_scroll(&mut self, id: &ElementId, message: ScrollMessage<M>)
+    {
+        self.scroll_messages.retain(|(element, _)| element != id);
+        self.scroll_messages.push((id.clone(), message));
+    }
+
+    fn scrolled(&mut self, id: &ElementId)
+    {
+        if let Some((_, message)) = self.scroll_messages.iter().find(|(element, _)| element == id)
+        {
+            let scroll = self.get(id).borrow().scroll();
+
+            self.pending.push(message(scroll));
+        }
+    }
+
+    // messages that didnt come from clicking, oldest first
+    pub fn take_messages(&mut self) -> Vec<M>
+    {
+        mem::take(&mut self.pending)
+    }
+
+    // replaces the message if it already had one
+    pub fn on_click(&mut self, id: &ElementId, message: M)
+    {
+        if let Some((_, old)) = self.messages.iter_mut().find(|(element, _)| element == id)
+        {
+            *old = message;
+        } else
+        {
+            self.messages.push((id.clone(), message));
+        }
+    }
+
+    // buttons that do something when clicked, in the order theyre drawn
+    fn focusable(&self) -> Vec<ElementId>
+    {
+        let mut focusable = Vec::new();
+        self.for_each_element(|id, element|
+        {
+            let is_button = matches!(element.inner.kind, UiElementType::Button);
+            let has_message = self.messages.iter().any(|(element, _)| element == id);
+
+            if is_button && element.enabled && has_message
+            {
+                focusable.push(id.clone());
+            }
+        });
+
+        focusable
+    }
+
+    // goes around to the other end after the last one
+    pub fn move_focus(&mut self, forward: bool)
+    {
+        let focusable = self.focusable();
+
+        if focusable.is_empty()
+        {
+            self.focused = None;
+
+            return;
+        }
+
+        let current = self.focused.as_ref()
+            .and_then(|focused| focusable.iter().position(|id| id == focused));
+
+        let index = match current
+        {
+            Some(index) if forward => (index + 1) % focusable.len(),
+            Some(index) => (index + focusable.len() - 1) % focusable.len(),
+            None if forward => 0,
+            None => focusable.len() - 1
+        };
+
+        let id = focusable[index].clone();
+
+        // it might be inside a container inside the scroll panel
+        let element = self.get(&id);
+        let area = element.borrow().area();
+
+        let mut parent = element.borrow().parent.clone();
+        while let Some((_, current)) = parent
+        {
+            if matches!(current.borrow().element.inner.kind, UiElementType::ScrollPanel{..})
+            {
+                current.borrow_mut().scroll_to(area);
+
+                break;
+            }
+
+            parent = current.borrow().parent.clone();
+        }
+
+        self.focused = Some(id);
+    }
+
+    // the message of the focused button, like clicking it
+    pub fn activate(&self) -> Option<M>
+    {
+        let focused = self.focused.as_ref()?;
+
+        if !self.focusable().contains(focused)
+        {
+            return None;
+        }
+
+        self.messages.iter()
+            .find(|(element, _)| element == focused)
+            .map(|(_, message)| message.clone())
+    }
+
+    pub fn set_visible(&mut self, id: &ElementId, visible: bool)
+    {
+        self.get(id).borrow_mut().visible = visible;
+    }
+
+    pub fn set_enabled(&mut self, id: &ElementId, enabled: bool)
+    {
+        let element = self.get(id);
+
+        element.borrow_mut().enabled = enabled;
+
+        let parent = element.borrow().parent.clone();
+        if let Some((index, parent)) = parent
+        {
+            parent.borrow_mut().update_child(index);
+        } else
+        {
+            element.borrow_mut().update();
+        }
+    }
+
+    pub fn set_z(&mut self, id: &ElementId, z: i32)
+    {
+        self.get(id).borrow_mut().z = z;
+    }
+
+    // plays from the start, replaces whatever was animating the element before
+    pub fn animate(&mut self, id: &ElementId, mut animator: Animator<UiAnimatableId>)
+    {
+        animator.reset();
+
+        self.animations.retain(|(element, _)| element != id);
+        self.animations.push((id.clone(), animator));
+    }
+
+    pub fn is_animating(&self, id: &ElementId) -> bool
+    {
+        self.animations.iter().any(|(element, _)| element == id)
+    }
+
+    pub fn is_animating_any(&self) -> bool
+    {
+        !self.animations.is_empty()
+    }
+
+    fn tick_animations(&mut self)
+    {
+        let mut animations = mem::take(&mut self.animations);
+
+        animations.retain_mut(|(id, animator)|
+        {
+            let state = animator.animate(&mut *self.get(id).borrow_mut());
+
+            matches!(state, AnimationState::Playing)
+        });
+
+        self.animations = animations;
+    }
+
+    pub fn set_clip_children(&mut self, id: &ElementId, clip: bool)
+    {
+        let element = self.get(id);
+
+        element.borrow_mut().clip_children = clip;
+
+        let parent = element.borrow().parent.clone();
+        if let Some((index, parent)) = parent
+        {
+            parent.borrow_mut().update_child(index);
+        } else
+        {
+            element.borrow_mut().update();
+        }
+    }
+
+    // call this after the window changes size
+    pub fn resize(&mut self)
+    {
+        let window_size = self.renderer.borrow().size().map(|x| x as f32);
+
+        self.elements.iter().for_each(|element|
+        {
+            let mut element = element.borrow_mut();
+
+            element.resolve_layouts(window_size);
+            element.update();
+        });
+    }
+
     // if i wasnt lazy i wouldnt need to have this be an exact copy of a function above
     pub fn get(&self, id: &ElementId) -> Rc<RefCell<UiElementInner>>
     {
@@ -287,16 +1412,18 @@ impl Ui
         }
     }
 
-    pub fn draw(&self)
+    pub fn draw(&mut self)
     {
-        let mut window = self.window.borrow_mut();
+        self.tick_animations();
+
+        let mut renderer = self.renderer.borrow_mut();
         let assets = self.assets.borrow();
 
-        let window_size = window.window_size().map(|x| x as f32);
+        let window_size = renderer.size().map(|x| x as f32);
 
         self.for_each_element(|_id, element|
         {
-            let texture = assets.texture(element.inner.texture);
+            renderer.set_clip(element.clip.map(|clip| area_to_screen(clip, window_size)));
 
             let scaled_pos = {
                 let mut pos = element.global_pos;
@@ -309,45 +1436,272 @@ impl Ui
             let scaled_size = (element.global_size * window_size)
                 .map(|x| x.round() as u32);
 
+            if matches!(element.inner.kind, UiElementType::Grid{..} | UiElementType::Flow{..})
+            {
+                return;
+            }
+
+            if let UiElementType::Label(label) = &element.inner.kind
+            {
+                draw_label(
+                    &mut *renderer,
+                    &assets,
+                    label,
+                    element.alpha,
+                    scaled_pos,
+                    scaled_size
+                );
+
+                return;
+            }
+
+            let is_button = matches!(element.inner.kind, UiElementType::Button);
+
+            let hovered = is_button
+                && element.enabled
+                && self.mouse.map(|pos| element.intersects(pos)).unwrap_or(false);
+
+            let tint = match (hovered, self.mouse_held)
+            {
+                _ if is_button && !element.enabled => 100,
+                (false, _) => 255,
+                (true, false) => 210,
+                (true, true) => 160
+            };
+
+            let nine_slice = assets.nine_slice(element.inner.texture);
+
+            let color = element.inner.color.unwrap_or(Color::WHITE);
+            let tinted = |x: u8| (x as u32 * tint / 255) as u8;
+
+            let texture_tint = Color::RGBA(
+                tinted(color.r),
+                tinted(color.g),
+                tinted(color.b),
+                element.alpha
+            );
+
             let x = scaled_pos.x;
             let y = scaled_pos.y;
             let width = scaled_size.x;
             let height = scaled_size.y;
 
-            window.canvas.copy(&texture, None, Rect::new(x, y, width, height))
-                .unwrap();
+            // tiles get drawn from their shrunk previews
+            let (texture, source) = assets.preview_texture(
+                element.inner.texture,
+                Point2::new(width, height)
+            );
+
+            let destination = Rect::new(x, y, width, height);
+
+            if let Some(nine_slice) = nine_slice
+            {
+                let source = source.unwrap_or_else(||
+                {
+                    let size = renderer.texture_size(texture);
+
+                    Rect::new(0, 0, size.x, size.y)
+                });
+
+                Self::draw_nine_slice(
+                    &mut *renderer,
+                    texture,
+                    texture_tint,
+                    source,
+                    destination,
+                    nine_slice
+                );
+            } else
+            {
+                renderer.copy_tinted(texture, source, destination, texture_tint);
+            }
+
+            if let (UiElementType::ScrollPanel{scrollbar}, Some((track, thumb))) =
+                (&element.inner.kind, element.scrollbar())
+            {
+                let (texture, source) = assets.texture(*scrollbar);
+
+                [(track, 90), (thumb, 255)].into_iter().for_each(|(area, tint)|
+                {
+                    let destination = area_to_screen(area, window_size);
+                    let tint = Color::RGB(tint, tint, tint);
+
+                    renderer.copy_tinted(texture, source, destination, tint);
+                });
+            }
+
+            if let UiElementType::ProgressBar{fill, progress} = &element.inner.kind
+            {
+                let (texture, source) = assets.texture(*fill);
+
+                let size = Point2::new(
+                    element.global_size.x * progress.clamp(0.0, 1.0),
+                    element.global_size.y
+                );
+
+                renderer.copy_tinted(
+                    texture,
+                    source,
+                    area_to_screen((element.global_pos, size), window_size),
+                    Color::RGBA(255, 255, 255, element.alpha)
+                );
+            }
         });
+
+        // it might be hidden or disabled by now
+        let focused = self.focused.as_ref().filter(|focused| self.focusable().contains(focused));
+
+        if let Some(focused) = focused
+        {
+            let color = assets.theme().focus_color();
+
+            self.draw_focus(&mut *renderer, focused, color, window_size);
+        }
+
+        renderer.set_clip(None);
     }
 
-    pub fn click(&self, pos: Point2<f32>) -> Option<UiEvent>
+    fn draw_focus(
+        &self,
+        renderer: &mut dyn Renderer,
+        focused: &ElementId,
+        color: Color,
+        window_size: Point2<f32>
+    )
     {
-        match self.try_for_each_element(|id, element|
+        let element = self.get(focused);
+        let element = &element.borrow().element;
+
+        renderer.set_clip(element.clip.map(|clip| area_to_screen(clip, window_size)));
+
+        let area = area_to_screen((element.global_pos, element.global_size), window_size);
+
+        renderer.set_color(color);
+
+        // 2 pixels thick
+        renderer.draw_rect(area);
+
+        if area.width() > 2 && area.height() > 2
         {
+            let inner = Rect::new(area.x() + 1, area.y() + 1, area.width() - 2, area.height() - 2);
+
+            renderer.draw_rect(inner);
+        }
+    }
+
+    fn draw_nine_slice(
+        renderer: &mut dyn Renderer,
+        texture: RenderTexture,
+        tint: Color,
+        source: Rect,
+        destination: Rect,
+        nine_slice: NineSlice
+    )
+    {
+        let corner = nine_slice.corner.min(source.width() / 2).min(source.height() / 2);
+        let screen_corner = (corner * nine_slice.scale)
+            .min(destination.width() / 2)
+            .min(destination.height() / 2);
+
+        // start and length of the 3 parts along 1 axis
+        let parts = |start: i32, length: u32, corner: u32|
+        {
+            let middle = length - corner * 2;
+
+            [
+                (start, corner),
+                (start + corner as i32, middle),
+                (start + (corner + middle) as i32, corner)
+            ]
+        };
+
+        let source_columns = parts(source.x(), source.width(), corner);
+        let source_rows = parts(source.y(), source.height(), corner);
+
+        let columns = parts(destination.x(), destination.width(), screen_corner);
+        let rows = parts(destination.y(), destination.height(), screen_corner);
+
+        let sources = source_rows.into_iter().zip(rows);
+        sources.for_each(|((source_y, source_height), (y, height))|
+        {
+            let columns = source_columns.into_iter().zip(columns);
+            columns.for_each(|((source_x, source_width), (x, width))|
+            {
+                // sdl makes empty rects 1 pixel big
+                if [source_width, source_height, width, height].contains(&0)
+                {
+                    return;
+                }
+
+                let source = Rect::new(source_x, source_y, source_width, source_height);
+
+                renderer.copy_tinted(texture, Some(source), Rect::new(x, y, width, height), tint);
+            });
+        });
+    }
+
+    // only the topmost element under the mouse gets clicked, labels dont block anything
+    // and neither do disabled elements
+    pub fn click(&self, pos: Point2<f32>) -> Option<M>
+    {
+        let (id, is_button) = self.topmost(pos)?;
+
+        if !is_button
+        {
+            return None;
+        }
+
+        self.messages.iter()
+            .find(|(element, _)| *element == id)
+            .map(|(_, message)| message.clone())
+    }
+
+    // the last one drawn wins, labels and containers dont count so clicks go through them
+    fn topmost(&self, pos: Point2<f32>) -> Option<(ElementId, bool)>
+    {
+        let mut topmost = None;
+        self.for_each_element(|id, element|
+        {
+            if !element.enabled
+            {
+                return;
+            }
+
             match element.inner.kind
             {
-                UiElementType::Button =>
+                UiElementType::Button
+                | UiElementType::Panel
+                | UiElementType::ScrollPanel{..}
+                | UiElementType::ProgressBar{..} =>
                 {
                     if element.intersects(pos)
                     {
-                        return ControlFlow::Break(UiEvent{element_id: id.clone()});
+                        let is_button = matches!(element.inner.kind, UiElementType::Button);
+
+                        topmost = Some((id.clone(), is_button));
                     }
                 },
-                UiElementType::Panel => ()
+                UiElementType::Label(_)
+                | UiElementType::Grid{..}
+                | UiElementType::Flow{..} => ()
             }
+        });
 
-            ControlFlow::Continue(())
-        })
-        {
-            ControlFlow::Break(x) => Some(x),
-            ControlFlow::Continue(_) => None
-        }
+        topmost
+    }
+
+    // if nothing drawn over the element is in the way at that position
+    fn reachable(&self, id: &ElementId, pos: Point2<f32>) -> bool
+    {
+        self.topmost(pos).map(|(topmost, _)| topmost == *id || topmost.is_inside(id))
+            .unwrap_or(false)
     }
 
     fn try_for_each_element<T, F>(&self, mut f: F) -> ControlFlow<T>
     where
         F: FnMut(&ElementId, &UiElementGlobal) -> ControlFlow<T>
     {
-        self.elements.iter().enumerate().try_for_each(|(index, element)|
+        in_z_order(&self.elements).into_iter().try_for_each(|(index, element)|
         {
             let id = ElementId::new(index);
 
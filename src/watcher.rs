@@ -0,0 +1,132 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    collections::HashMap,
+    time::{Instant, Duration, SystemTime}
+};
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange
+{
+    Added(PathBuf),
+    Changed(PathBuf),
+    Removed(PathBuf)
+}
+
+impl FileChange
+{
+    pub fn path(&self) -> &Path
+    {
+        match self
+        {
+            Self::Added(x) | Self::Changed(x) | Self::Removed(x) => x
+        }
+    }
+}
+
+// polls modification times, simple and good enough for a handful of images
+pub struct DirectoryWatcher
+{
+    directories: Vec<PathBuf>,
+    extensions: Vec<String>,
+    files: HashMap<PathBuf, SystemTime>,
+    interval: Duration,
+    last_check: Instant
+}
+
+impl DirectoryWatcher
+{
+    pub fn new(directories: Vec<PathBuf>, extensions: &[&str]) -> Self
+    {
+        let mut this = Self{
+            directories,
+            extensions: extensions.iter().map(|x| x.to_string()).collect(),
+            files: HashMap::new(),
+            interval: Duration::from_millis(500),
+            last_check: Instant::now()
+        };
+
+        this.files = this.scan();
+
+        this
+    }
+
+    // does nothing until enough time passed since the last check
+    pub fn poll(&mut self) -> Vec<FileChange>
+    {
+        if self.last_check.elapsed() < self.interval
+        {
+            return Vec::new();
+        }
+
+        self.last_check = Instant::now();
+
+        let files = self.scan();
+
+        let mut changes: Vec<FileChange> = files.iter().filter_map(|(path, modified)|
+        {
+            match self.files.get(path)
+            {
+                None => Some(FileChange::Added(path.clone())),
+                Some(old) if old != modified => Some(FileChange::Changed(path.clone())),
+                _ => None
+            }
+        }).collect();
+
+        changes.extend(self.files.keys().filter(|path| !files.contains_key(*path))
+            .map(|path| FileChange::Removed(path.clone())));
+
+        self.files = files;
+
+        changes
+    }
+
+    fn scan(&self) -> HashMap<PathBuf, SystemTime>
+    {
+        let mut files = HashMap::new();
+
+        self.directories.iter().for_each(|directory| self.scan_directory(directory, &mut files));
+
+        files
+    }
+
+    // subdirectories get scanned too
+    fn scan_directory(&self, directory: &Path, files: &mut HashMap<PathBuf, SystemTime>)
+    {
+        let Ok(entries) = fs::read_dir(directory)
+        else
+        {
+            return;
+        };
+
+        entries.filter_map(|entry| entry.ok()).for_each(|entry|
+        {
+            let path = entry.path();
+
+            let Ok(metadata) = entry.metadata()
+            else
+            {
+                return;
+            };
+
+            if metadata.is_dir()
+            {
+                self.scan_directory(&path, files);
+
+                return;
+            }
+
+            let extension = path.extension().map(|x| x.to_string_lossy().to_lowercase());
+            if !extension.map(|x| self.extensions.contains(&x)).unwrap_or(false)
+            {
+                return;
+            }
+
+            if let Ok(modified) = metadata.modified()
+            {
+                files.insert(path, modified);
+            }
+        });
+    }
+}